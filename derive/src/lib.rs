@@ -0,0 +1,196 @@
+//! Provides `#[derive(JiraIssue)]` and `jql!` for `jimberlage_jira_client`.
+//!
+//! Use these through the `derive` and `macros` features of `jimberlage_jira_client`, which documents the attributes
+//! of the derive on its `mapping::JiraIssue` trait.
+
+mod template;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, ExprPath, Fields,
+    LitStr,
+};
+
+/// Implements `jimberlage_jira_client::mapping::JiraIssue` for a struct with named fields.
+#[proc_macro_derive(JiraIssue, attributes(jira))]
+pub fn derive_jira_issue(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Builds a `JQLStatement` from JQL which is checked when it is compiled, filling in placeholders with Rust values.
+///
+/// Placeholders work like those of `format!`: `{}` takes the next argument, `{0}` an argument by index, and `{name}`
+/// a named argument, or a variable of that name if there is no such argument.  Each stands for a value, which is
+/// anything that converts into a `JQLValue`, and is escaped as it needs to be.  A placeholder right after `IN`, `NOT
+/// IN` or `WAS IN`, rather than inside parentheses, stands for a whole list, and takes anything which iterates over
+/// values.
+///
+/// Placeholders cannot be used for fields or for the arguments of functions, and braces outside of quoted strings are
+/// always placeholders.
+///
+/// Arguments are taken by value, like those of a function, so pass a reference to keep using one.
+///
+/// The query is only parsed when it is compiled; the macro expands to the code which builds its `JQLStatement`.
+#[proc_macro]
+pub fn jql(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as template::Input);
+
+    template::expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Where a struct field is read from.
+enum Source {
+    /// The key of the issue.
+    Key,
+
+    /// The ID of the issue.
+    Id,
+
+    /// An issue field, by its ID.
+    Field(LitStr),
+
+    /// An issue field, by its display name.
+    Name(LitStr),
+}
+
+struct FieldAttrs {
+    source: Option<Source>,
+    deserialize_with: Option<ExprPath>,
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "JiraIssue can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "JiraIssue can only be derived for structs",
+            ))
+        }
+    };
+
+    let krate = quote!(::jimberlage_jira_client);
+    let mut refs = vec![];
+    let mut inits = vec![];
+
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named fields have names");
+        let attrs = parse_attrs(field)?;
+        let source = attrs.source.unwrap_or_else(|| {
+            Source::Field(LitStr::new(&ident.unraw().to_string(), ident.span()))
+        });
+
+        let field_ref = match source {
+            Source::Key | Source::Id if attrs.deserialize_with.is_some() => {
+                return Err(Error::new(
+                    field.span(),
+                    "`deserialize_with` cannot be used with `key` or `id`",
+                ))
+            }
+            Source::Key => {
+                inits.push(quote!(#ident: ::std::clone::Clone::clone(&issue.key)));
+                continue;
+            }
+            Source::Id => {
+                inits.push(quote!(#ident: ::std::clone::Clone::clone(&issue.id)));
+                continue;
+            }
+            Source::Field(id) => quote!(#krate::mapping::FieldRef::Id(#id)),
+            Source::Name(name) => quote!(#krate::mapping::FieldRef::Name(#name)),
+        };
+
+        // Fields are resolved to IDs in the order they are listed, so each is read back by its index.
+        let index = refs.len();
+        refs.push(field_ref);
+
+        let read = match attrs.deserialize_with {
+            Some(path) => quote! {
+                #krate::mapping::read_field_with(issue, fields.id(#index), |value| #path(value))?
+            },
+            None => quote!(#krate::mapping::read_field(issue, fields.id(#index))?),
+        };
+        inits.push(quote!(#ident: #read));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::mapping::JiraIssue for #name #ty_generics #where_clause {
+            fn fields() -> &'static [#krate::mapping::FieldRef] {
+                &[#(#refs),*]
+            }
+
+            fn from_issue(
+                issue: &#krate::SearchIssue,
+                fields: &#krate::mapping::ResolvedFields<Self>,
+            ) -> ::std::result::Result<Self, #krate::Error> {
+                ::std::result::Result::Ok(Self {
+                    #(#inits),*
+                })
+            }
+        }
+    })
+}
+
+fn parse_attrs(field: &syn::Field) -> Result<FieldAttrs, Error> {
+    let mut attrs = FieldAttrs {
+        source: None,
+        deserialize_with: None,
+    };
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("jira"))
+    {
+        attr.parse_nested_meta(|meta| {
+            let source = if meta.path.is_ident("key") {
+                Source::Key
+            } else if meta.path.is_ident("id") {
+                Source::Id
+            } else if meta.path.is_ident("field") {
+                Source::Field(meta.value()?.parse()?)
+            } else if meta.path.is_ident("name") {
+                Source::Name(meta.value()?.parse()?)
+            } else if meta.path.is_ident("deserialize_with") {
+                let path: LitStr = meta.value()?.parse()?;
+                if attrs.deserialize_with.is_some() {
+                    return Err(meta.error("`deserialize_with` is given more than once"));
+                }
+                attrs.deserialize_with = Some(path.parse()?);
+                return Ok(());
+            } else {
+                return Err(meta.error(
+                    "expected `key`, `id`, `field = \"...\"`, `name = \"...\"` or `deserialize_with = \"...\"`",
+                ));
+            };
+
+            if attrs.source.is_some() {
+                return Err(meta.error(
+                    "only one of `key`, `id`, `field` and `name` can be given for a field",
+                ));
+            }
+            attrs.source = Some(source);
+            Ok(())
+        })?;
+    }
+
+    Ok(attrs)
+}
@@ -0,0 +1,564 @@
+use std::{collections::HashMap, iter::Peekable, vec::IntoIter};
+
+use chrono::{Datelike, Timelike};
+use jimberlage_jira_client_jql::{
+    __private::{parse_template, placeholder_value},
+    JQLClause, JQLDuration, JQLDurationUnit, JQLEmpty, JQLField, JQLFunction, JQLHistoryPredicate,
+    JQLOrderBy, JQLOrdering, JQLStatement, JQLValue,
+};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Expr, Ident, LitStr, Token,
+};
+
+/// The input to `jql!`: a template, then the values for its placeholders, in the same form as the arguments to
+/// `format!`.
+pub struct Input {
+    template: LitStr,
+    arguments: Vec<Argument>,
+}
+
+struct Argument {
+    name: Option<Ident>,
+    value: Expr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let template = input.parse()?;
+        let mut arguments: Vec<Argument> = vec![];
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let name = if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+                let name = input.parse()?;
+                input.parse::<Token![=]>()?;
+                Some(name)
+            } else {
+                None
+            };
+            let value: Expr = input.parse()?;
+
+            if name.is_none() && arguments.iter().any(|argument| argument.name.is_some()) {
+                return Err(Error::new(
+                    value.span(),
+                    "positional arguments cannot follow named arguments",
+                ));
+            }
+            arguments.push(Argument { name, value });
+        }
+
+        Ok(Input {
+            template,
+            arguments,
+        })
+    }
+}
+
+pub fn expand(input: Input) -> Result<TokenStream2, Error> {
+    let Input {
+        template,
+        mut arguments,
+    } = input;
+    let jql = template.value();
+
+    let (statement, placeholders) = parse_template(&jql).map_err(|error| {
+        let message = match jql.get(error.span.clone()) {
+            // Most messages already say what was found.
+            Some(found) if !found.is_empty() && !error.message.contains("found") => {
+                format!("{}, at `{}`", error.message, found)
+            }
+            _ => error.message,
+        };
+        Error::new(template.span(), format!("invalid JQL: {}", message))
+    })?;
+
+    let positional = arguments
+        .iter()
+        .take_while(|argument| argument.name.is_none())
+        .count();
+    let mut named = HashMap::new();
+    for (index, argument) in arguments.iter().enumerate().skip(positional) {
+        let name = argument.name.as_ref().expect("named arguments come last");
+        if named.insert(name.to_string(), index).is_some() {
+            return Err(Error::new(
+                name.span(),
+                format!("`{}` is given more than once", name),
+            ));
+        }
+    }
+
+    // Works out which argument each placeholder is filled in with, following `format!`: `{}` takes the next
+    // positional argument, `{0}` a positional argument by index, and `{name}` a named argument or else the variable.
+    let mut next_positional = 0;
+    let mut sources = vec![];
+    for placeholder in &placeholders {
+        let index = if let Ok(name) = syn::parse_str::<Ident>(&placeholder.name) {
+            *named.entry(name.to_string()).or_insert_with(|| {
+                let variable = Ident::new(&placeholder.name, template.span());
+                arguments.push(Argument {
+                    name: Some(variable.clone()),
+                    value: syn::parse_quote!(#variable),
+                });
+                arguments.len() - 1
+            })
+        } else {
+            let index = if placeholder.name.is_empty() {
+                next_positional += 1;
+                next_positional - 1
+            } else {
+                placeholder.name.parse::<usize>().map_err(|_| {
+                    Error::new(
+                        template.span(),
+                        format!(
+                            "invalid placeholder `{{{}}}`; expected `{{}}`, an index like `{{0}}` or a name",
+                            placeholder.name
+                        ),
+                    )
+                })?
+            };
+
+            if index >= positional {
+                return Err(Error::new(
+                    template.span(),
+                    format!(
+                        "`{{{}}}` refers to argument {}, but only {} positional {} given",
+                        placeholder.name,
+                        index,
+                        positional,
+                        if positional == 1 {
+                            "argument was"
+                        } else {
+                            "arguments were"
+                        }
+                    ),
+                ));
+            }
+            index
+        };
+
+        sources.push((index, placeholder.list));
+    }
+
+    let mut uses = vec![0; arguments.len()];
+    for (index, _) in &sources {
+        uses[*index] += 1;
+    }
+    if let Some(unused) = arguments
+        .iter()
+        .zip(&uses)
+        .find_map(|(argument, uses)| (*uses == 0).then_some(argument))
+    {
+        return Err(Error::new(
+            unused.value.span(),
+            "argument never used by a placeholder",
+        ));
+    }
+
+    let krate = quote!(::jimberlage_jira_client::jql);
+    // Each argument is evaluated once, in order, and cloned for all but its last use.
+    let bindings = (0..arguments.len())
+        .map(|index| Ident::new(&format!("argument_{}", index), Span::mixed_site()))
+        .collect::<Vec<Ident>>();
+    let values = arguments
+        .iter()
+        .map(|argument| argument.value.to_token_stream());
+    let filled = sources
+        .iter()
+        .map(|(index, list)| {
+            let binding = &bindings[*index];
+            uses[*index] -= 1;
+            let value = if uses[*index] > 0 {
+                quote!(::std::clone::Clone::clone(&#binding))
+            } else {
+                quote!(#binding)
+            };
+
+            if *list {
+                (quote!(#krate::__private::list(#value)), true)
+            } else {
+                (quote!(::std::convert::Into::into(#value)), false)
+            }
+        })
+        .collect::<Vec<(TokenStream2, bool)>>();
+
+    // The statement is built as it was parsed, so the query is only read when it is compiled.
+    let statement = Expander {
+        krate,
+        filled: filled.into_iter().peekable(),
+    }
+    .statement(&statement);
+
+    Ok(quote! {
+        {
+            #(let #bindings = #values;)*
+            #statement
+        }
+    })
+}
+
+/// Writes out a parsed statement as the Rust which builds it, filling in each placeholder with the value given for it,
+/// in the order the placeholders appear.
+struct Expander {
+    krate: TokenStream2,
+
+    /// The value for each placeholder, and whether it is for a whole list.
+    filled: Peekable<IntoIter<(TokenStream2, bool)>>,
+}
+
+impl Expander {
+    fn statement(&mut self, statement: &JQLStatement) -> TokenStream2 {
+        let krate = self.krate.clone();
+        let clause = self.clause(&statement.clause);
+        let order_by = match &statement.order_by {
+            Some(JQLOrderBy(parts)) => {
+                let parts = parts.iter().map(|part| {
+                    let field = self.field(&part.field);
+                    let ordering = match &part.ordering {
+                        Some(JQLOrdering::Asc) => {
+                            quote!(::std::option::Option::Some(#krate::JQLOrdering::Asc))
+                        }
+                        Some(JQLOrdering::Desc) => {
+                            quote!(::std::option::Option::Some(#krate::JQLOrdering::Desc))
+                        }
+                        None => quote!(::std::option::Option::None),
+                    };
+                    quote!(#krate::JQLOrderByPart { field: #field, ordering: #ordering })
+                });
+                quote!(::std::option::Option::Some(#krate::JQLOrderBy(::std::vec![#(#parts),*])))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+
+        quote!(#krate::JQLStatement { clause: #clause, order_by: #order_by })
+    }
+
+    fn clause(&mut self, clause: &JQLClause) -> TokenStream2 {
+        let krate = self.krate.clone();
+
+        match clause {
+            JQLClause::And(clauses) | JQLClause::Or(clauses) => {
+                let variant = if matches!(clause, JQLClause::And(_)) {
+                    quote!(And)
+                } else {
+                    quote!(Or)
+                };
+                let clauses = clauses
+                    .iter()
+                    .map(|clause| {
+                        let clause = self.clause(clause);
+                        quote!(::std::boxed::Box::new(#clause))
+                    })
+                    .collect::<Vec<TokenStream2>>();
+                quote!(#krate::JQLClause::#variant(::std::vec![#(#clauses),*]))
+            }
+            JQLClause::Not(clause) => {
+                let clause = self.clause(clause);
+                quote!(#krate::JQLClause::Not(::std::boxed::Box::new(#clause)))
+            }
+            JQLClause::Equals(field, value)
+            | JQLClause::NotEquals(field, value)
+            | JQLClause::GreaterThan(field, value)
+            | JQLClause::GreaterThanEquals(field, value)
+            | JQLClause::LessThan(field, value)
+            | JQLClause::LessThanEquals(field, value)
+            | JQLClause::Contains(field, value)
+            | JQLClause::NotContains(field, value) => {
+                let variant = match clause {
+                    JQLClause::Equals(..) => quote!(Equals),
+                    JQLClause::NotEquals(..) => quote!(NotEquals),
+                    JQLClause::GreaterThan(..) => quote!(GreaterThan),
+                    JQLClause::GreaterThanEquals(..) => quote!(GreaterThanEquals),
+                    JQLClause::LessThan(..) => quote!(LessThan),
+                    JQLClause::LessThanEquals(..) => quote!(LessThanEquals),
+                    JQLClause::Contains(..) => quote!(Contains),
+                    _ => quote!(NotContains),
+                };
+                let field = self.field(field);
+                let value = self.value(value);
+                quote!(#krate::JQLClause::#variant(#field, #value))
+            }
+            JQLClause::In(field, values) | JQLClause::NotIn(field, values) => {
+                let variant = if matches!(clause, JQLClause::In(..)) {
+                    quote!(In)
+                } else {
+                    quote!(NotIn)
+                };
+                let field = self.field(field);
+                let values = self.list(values);
+                quote!(#krate::JQLClause::#variant(#field, #values))
+            }
+            JQLClause::Is(field, empty) | JQLClause::IsNot(field, empty) => {
+                let variant = if matches!(clause, JQLClause::Is(..)) {
+                    quote!(Is)
+                } else {
+                    quote!(IsNot)
+                };
+                let field = self.field(field);
+                let empty = match empty {
+                    JQLEmpty::Empty => quote!(#krate::JQLEmpty::Empty),
+                    JQLEmpty::Null => quote!(#krate::JQLEmpty::Null),
+                };
+                quote!(#krate::JQLClause::#variant(#field, #empty))
+            }
+            JQLClause::Was(field, value, predicates)
+            | JQLClause::WasNot(field, value, predicates) => {
+                let variant = if matches!(clause, JQLClause::Was(..)) {
+                    quote!(Was)
+                } else {
+                    quote!(WasNot)
+                };
+                let field = self.field(field);
+                let value = self.value(value);
+                let predicates = self.predicates(predicates);
+                quote!(#krate::JQLClause::#variant(#field, #value, #predicates))
+            }
+            JQLClause::WasIn(field, values, predicates)
+            | JQLClause::WasNotIn(field, values, predicates) => {
+                let variant = if matches!(clause, JQLClause::WasIn(..)) {
+                    quote!(WasIn)
+                } else {
+                    quote!(WasNotIn)
+                };
+                let field = self.field(field);
+                let values = self.list(values);
+                let predicates = self.predicates(predicates);
+                quote!(#krate::JQLClause::#variant(#field, #values, #predicates))
+            }
+            JQLClause::Changed(field, predicates) => {
+                let field = self.field(field);
+                let predicates = self.predicates(predicates);
+                quote!(#krate::JQLClause::Changed(#field, #predicates))
+            }
+        }
+    }
+
+    fn predicates(&mut self, predicates: &[JQLHistoryPredicate]) -> TokenStream2 {
+        let krate = self.krate.clone();
+        let predicates = predicates
+            .iter()
+            .map(|predicate| match predicate {
+                JQLHistoryPredicate::During(start, end) => {
+                    let start = self.value(start);
+                    let end = self.value(end);
+                    quote!(#krate::JQLHistoryPredicate::During(#start, #end))
+                }
+                JQLHistoryPredicate::After(value)
+                | JQLHistoryPredicate::Before(value)
+                | JQLHistoryPredicate::On(value)
+                | JQLHistoryPredicate::By(value)
+                | JQLHistoryPredicate::From(value)
+                | JQLHistoryPredicate::To(value) => {
+                    let variant = match predicate {
+                        JQLHistoryPredicate::After(_) => quote!(After),
+                        JQLHistoryPredicate::Before(_) => quote!(Before),
+                        JQLHistoryPredicate::On(_) => quote!(On),
+                        JQLHistoryPredicate::By(_) => quote!(By),
+                        JQLHistoryPredicate::From(_) => quote!(From),
+                        _ => quote!(To),
+                    };
+                    let value = self.value(value);
+                    quote!(#krate::JQLHistoryPredicate::#variant(#value))
+                }
+            })
+            .collect::<Vec<TokenStream2>>();
+
+        quote!(::std::vec![#(#predicates),*])
+    }
+
+    fn field(&self, field: &JQLField) -> TokenStream2 {
+        let krate = &self.krate;
+
+        match field {
+            JQLField::System(name) => {
+                quote!(#krate::JQLField::System(::std::borrow::ToOwned::to_owned(#name)))
+            }
+            JQLField::Custom(number) => quote!(#krate::JQLField::Custom(#number)),
+            JQLField::Name(name) => {
+                quote!(#krate::JQLField::Name(::std::borrow::ToOwned::to_owned(#name)))
+            }
+        }
+    }
+
+    /// Writes out a list, which is either a placeholder for the whole list, or a list of values some of which are
+    /// placeholders.  Both look the same once parsed, as a list of one placeholder, so the placeholder tells them
+    /// apart.
+    fn list(&mut self, values: &[JQLValue]) -> TokenStream2 {
+        if let [value] = values {
+            if *value == placeholder_value() && matches!(self.filled.peek(), Some((_, true))) {
+                let (list, _) = self
+                    .filled
+                    .next()
+                    .expect("the placeholder was just peeked at");
+                return list;
+            }
+        }
+
+        let values = values
+            .iter()
+            .map(|value| self.value(value))
+            .collect::<Vec<TokenStream2>>();
+
+        quote!(::std::vec![#(#values),*])
+    }
+
+    fn value(&mut self, value: &JQLValue) -> TokenStream2 {
+        let krate = self.krate.clone();
+
+        match value {
+            _ if *value == placeholder_value() => {
+                let (value, _) = self
+                    .filled
+                    .next()
+                    .expect("there is a value for each placeholder");
+                value
+            }
+            JQLValue::String(contents) => {
+                quote!(#krate::JQLValue::String(::std::borrow::ToOwned::to_owned(#contents)))
+            }
+            JQLValue::Int(number) => quote!(#krate::JQLValue::Int(#number)),
+            JQLValue::Float(number) => quote!(#krate::JQLValue::Float(#number)),
+            JQLValue::NaiveDate(date) => {
+                let (year, month, day) = (date.year(), date.month(), date.day());
+                quote!(#krate::__private::date(#year, #month, #day))
+            }
+            JQLValue::NaiveDateTime(datetime) => {
+                let (year, month, day) = (datetime.year(), datetime.month(), datetime.day());
+                let (hour, minute) = (datetime.hour(), datetime.minute());
+                quote!(#krate::__private::datetime(#year, #month, #day, #hour, #minute))
+            }
+            JQLValue::Duration(duration) => {
+                let duration = self.duration(duration);
+                quote!(#krate::JQLValue::Duration(#duration))
+            }
+            JQLValue::Empty => quote!(#krate::JQLValue::Empty),
+            JQLValue::Null => quote!(#krate::JQLValue::Null),
+            JQLValue::Function(function) => {
+                let function = self.function(function);
+                quote!(#krate::JQLValue::Function(#function))
+            }
+        }
+    }
+
+    fn duration(&self, duration: &JQLDuration) -> TokenStream2 {
+        let krate = &self.krate;
+        let amount = duration.amount;
+        let unit = match duration.unit {
+            JQLDurationUnit::Minute => quote!(Minute),
+            JQLDurationUnit::Hour => quote!(Hour),
+            JQLDurationUnit::Day => quote!(Day),
+            JQLDurationUnit::Week => quote!(Week),
+            JQLDurationUnit::Month => quote!(Month),
+            JQLDurationUnit::Year => quote!(Year),
+        };
+
+        quote!(#krate::JQLDuration::new(#amount, #krate::JQLDurationUnit::#unit))
+    }
+
+    /// Writes out a function.  Placeholders cannot be used in the arguments of functions, so none are filled in here.
+    fn function(&mut self, function: &JQLFunction) -> TokenStream2 {
+        let krate = self.krate.clone();
+        let text = |text: &String| quote!(::std::borrow::ToOwned::to_owned(#text));
+        let optional_text = |text: &Option<String>| match text {
+            Some(text) => {
+                quote!(::std::option::Option::Some(::std::borrow::ToOwned::to_owned(#text)))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+        let offset = |offset: &Option<JQLDuration>| match offset {
+            Some(duration) => {
+                let duration = self.duration(duration);
+                quote!(::std::option::Option::Some(#duration))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+
+        match function {
+            JQLFunction::CurrentUser => quote!(#krate::JQLFunction::CurrentUser),
+            JQLFunction::MembersOf(group) => {
+                let group = text(group);
+                quote!(#krate::JQLFunction::MembersOf(#group))
+            }
+            JQLFunction::StartOfDay(duration) => {
+                let duration = offset(duration);
+                quote!(#krate::JQLFunction::StartOfDay(#duration))
+            }
+            JQLFunction::StartOfWeek(duration) => {
+                let duration = offset(duration);
+                quote!(#krate::JQLFunction::StartOfWeek(#duration))
+            }
+            JQLFunction::StartOfMonth(duration) => {
+                let duration = offset(duration);
+                quote!(#krate::JQLFunction::StartOfMonth(#duration))
+            }
+            JQLFunction::StartOfYear(duration) => {
+                let duration = offset(duration);
+                quote!(#krate::JQLFunction::StartOfYear(#duration))
+            }
+            JQLFunction::EndOfDay(duration) => {
+                let duration = offset(duration);
+                quote!(#krate::JQLFunction::EndOfDay(#duration))
+            }
+            JQLFunction::EndOfWeek(duration) => {
+                let duration = offset(duration);
+                quote!(#krate::JQLFunction::EndOfWeek(#duration))
+            }
+            JQLFunction::EndOfMonth(duration) => {
+                let duration = offset(duration);
+                quote!(#krate::JQLFunction::EndOfMonth(#duration))
+            }
+            JQLFunction::EndOfYear(duration) => {
+                let duration = offset(duration);
+                quote!(#krate::JQLFunction::EndOfYear(#duration))
+            }
+            JQLFunction::Now => quote!(#krate::JQLFunction::Now),
+            JQLFunction::OpenSprints => quote!(#krate::JQLFunction::OpenSprints),
+            JQLFunction::ClosedSprints => quote!(#krate::JQLFunction::ClosedSprints),
+            JQLFunction::FutureSprints => quote!(#krate::JQLFunction::FutureSprints),
+            JQLFunction::IssueHistory => quote!(#krate::JQLFunction::IssueHistory),
+            JQLFunction::LinkedIssues(key, link_type) => {
+                let key = text(key);
+                let link_type = optional_text(link_type);
+                quote!(#krate::JQLFunction::LinkedIssues(#key, #link_type))
+            }
+            JQLFunction::ReleasedVersions(project) => {
+                let project = optional_text(project);
+                quote!(#krate::JQLFunction::ReleasedVersions(#project))
+            }
+            JQLFunction::UnreleasedVersions(project) => {
+                let project = optional_text(project);
+                quote!(#krate::JQLFunction::UnreleasedVersions(#project))
+            }
+            JQLFunction::LatestReleasedVersion(project) => {
+                let project = text(project);
+                quote!(#krate::JQLFunction::LatestReleasedVersion(#project))
+            }
+            JQLFunction::UpdatedBy(user, from, to) => {
+                let user = text(user);
+                let [from, to] = [from, to].map(|date| match date {
+                    Some(date) => {
+                        let date = self.value(date);
+                        quote!(::std::option::Option::Some(::std::boxed::Box::new(#date)))
+                    }
+                    None => quote!(::std::option::Option::None),
+                });
+                quote!(#krate::JQLFunction::UpdatedBy(#user, #from, #to))
+            }
+            JQLFunction::Other(name, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.value(argument))
+                    .collect::<Vec<TokenStream2>>();
+                let name = text(name);
+                quote!(#krate::JQLFunction::Other(#name, ::std::vec![#(#arguments),*]))
+            }
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::ops::Not;
+
+use crate::{
+    JQLClause, JQLEmpty, JQLField, JQLHistoryPredicate, JQLOrderBy, JQLOrderByPart, JQLOrdering,
+    JQLStatement, JQLValue,
+};
+
+/// Starts a clause on a field, for building JQL without spelling out each `JQLClause` and `JQLValue`.
+///
+/// Values are anything which converts into a `JQLValue`, like strings, numbers, dates and `JQLFunction`s.  Clauses
+/// are combined with `JQLClause::and`, `JQLClause::or` and `!`.
+///
+/// ### Example
+///
+/// ```
+/// use chrono::TimeDelta;
+/// use jimberlage_jira_client::jql::{field, JQLFunction, SerializableToJQL};
+///
+/// let statement = field("project")
+///     .in_(["SRE", "PE"])
+///     .and(field("status").ne("Done"))
+///     .and(field("assignee").eq(JQLFunction::CurrentUser).or(field("assignee").is_empty()))
+///     .and(field("status").changed().after(TimeDelta::weeks(-1)))
+///     .order_by([field("Story Points").desc(), field("created").asc()]);
+///
+/// assert_eq!(
+///     statement.serialize_to_jql(),
+///     "project IN (\"SRE\", \"PE\") AND status != \"Done\" AND (assignee = currentUser() OR assignee IS EMPTY) \
+///      AND status CHANGED AFTER -1w ORDER BY \"Story Points\" DESC, created ASC"
+/// );
+/// ```
+pub fn field(field: impl Into<JQLField>) -> JQLFieldBuilder {
+    JQLFieldBuilder(field.into())
+}
+
+/// Builds clauses on a field; see `field`.
+///
+/// Each method borrows the builder, so one can be kept around to build several clauses on the same field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JQLFieldBuilder(JQLField);
+
+impl JQLFieldBuilder {
+    /// Returns the field the builder makes clauses on.
+    pub fn field(&self) -> &JQLField {
+        &self.0
+    }
+
+    /// `field = value`
+    pub fn eq(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::Equals(self.0.clone(), value.into())
+    }
+
+    /// `field != value`
+    pub fn ne(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::NotEquals(self.0.clone(), value.into())
+    }
+
+    /// `field > value`
+    pub fn gt(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::GreaterThan(self.0.clone(), value.into())
+    }
+
+    /// `field >= value`
+    pub fn ge(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::GreaterThanEquals(self.0.clone(), value.into())
+    }
+
+    /// `field < value`
+    pub fn lt(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::LessThan(self.0.clone(), value.into())
+    }
+
+    /// `field <= value`
+    pub fn le(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::LessThanEquals(self.0.clone(), value.into())
+    }
+
+    /// `field ~ value`, which matches text fields by words rather than exactly.
+    pub fn contains(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::Contains(self.0.clone(), value.into())
+    }
+
+    /// `field !~ value`
+    pub fn not_contains(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::NotContains(self.0.clone(), value.into())
+    }
+
+    /// `field IN (values)`.  This is named with a trailing underscore since `in` is a keyword in Rust.
+    pub fn in_<V: Into<JQLValue>>(&self, values: impl IntoIterator<Item = V>) -> JQLClause {
+        JQLClause::In(self.0.clone(), values_of(values))
+    }
+
+    /// `field NOT IN (values)`
+    pub fn not_in<V: Into<JQLValue>>(&self, values: impl IntoIterator<Item = V>) -> JQLClause {
+        JQLClause::NotIn(self.0.clone(), values_of(values))
+    }
+
+    /// `field IS EMPTY`
+    pub fn is_empty(&self) -> JQLClause {
+        JQLClause::Is(self.0.clone(), JQLEmpty::Empty)
+    }
+
+    /// `field IS NOT EMPTY`
+    pub fn is_not_empty(&self) -> JQLClause {
+        JQLClause::IsNot(self.0.clone(), JQLEmpty::Empty)
+    }
+
+    /// `field WAS value`, which can be narrowed down with `JQLClause::after` and the like.
+    pub fn was(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::Was(self.0.clone(), value.into(), vec![])
+    }
+
+    /// `field WAS IN (values)`
+    pub fn was_in<V: Into<JQLValue>>(&self, values: impl IntoIterator<Item = V>) -> JQLClause {
+        JQLClause::WasIn(self.0.clone(), values_of(values), vec![])
+    }
+
+    /// `field WAS NOT value`
+    pub fn was_not(&self, value: impl Into<JQLValue>) -> JQLClause {
+        JQLClause::WasNot(self.0.clone(), value.into(), vec![])
+    }
+
+    /// `field WAS NOT IN (values)`
+    pub fn was_not_in<V: Into<JQLValue>>(&self, values: impl IntoIterator<Item = V>) -> JQLClause {
+        JQLClause::WasNotIn(self.0.clone(), values_of(values), vec![])
+    }
+
+    /// `field CHANGED`, which can be narrowed down with `JQLClause::after` and the like.
+    pub fn changed(&self) -> JQLClause {
+        JQLClause::Changed(self.0.clone(), vec![])
+    }
+
+    /// Orders by the field, ascending.
+    pub fn asc(&self) -> JQLOrderByPart {
+        JQLOrderByPart {
+            field: self.0.clone(),
+            ordering: Some(JQLOrdering::Asc),
+        }
+    }
+
+    /// Orders by the field, descending.
+    pub fn desc(&self) -> JQLOrderByPart {
+        JQLOrderByPart {
+            field: self.0.clone(),
+            ordering: Some(JQLOrdering::Desc),
+        }
+    }
+}
+
+/// Orders by the field, in the order JIRA uses for it by default.
+impl From<JQLFieldBuilder> for JQLOrderByPart {
+    fn from(builder: JQLFieldBuilder) -> Self {
+        JQLOrderByPart {
+            field: builder.0,
+            ordering: None,
+        }
+    }
+}
+
+fn values_of<V: Into<JQLValue>>(values: impl IntoIterator<Item = V>) -> Vec<JQLValue> {
+    values.into_iter().map(Into::into).collect()
+}
+
+impl JQLClause {
+    /// Joins two clauses with `AND`.  Clauses which are already joined with `AND` are added to, rather than nested.
+    pub fn and(self, other: JQLClause) -> JQLClause {
+        let mut clauses = vec![];
+        for clause in [self, other] {
+            match clause {
+                JQLClause::And(group) => clauses.extend(group),
+                clause => clauses.push(Box::new(clause)),
+            }
+        }
+
+        JQLClause::And(clauses)
+    }
+
+    /// Joins two clauses with `OR`.  Clauses which are already joined with `OR` are added to, rather than nested.
+    pub fn or(self, other: JQLClause) -> JQLClause {
+        let mut clauses = vec![];
+        for clause in [self, other] {
+            match clause {
+                JQLClause::Or(group) => clauses.extend(group),
+                clause => clauses.push(Box::new(clause)),
+            }
+        }
+
+        JQLClause::Or(clauses)
+    }
+
+    /// Adds `AFTER value` to a `WAS` or `CHANGED` clause.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the clause is not a `WAS` or `CHANGED` clause, which are the only ones JQL allows history on.  The
+    /// same goes for `before`, `on`, `during`, `by`, `changed_from` and `changed_to`.
+    pub fn after(self, value: impl Into<JQLValue>) -> JQLClause {
+        self.with_predicate(JQLHistoryPredicate::After(value.into()))
+    }
+
+    /// Adds `BEFORE value` to a `WAS` or `CHANGED` clause.
+    pub fn before(self, value: impl Into<JQLValue>) -> JQLClause {
+        self.with_predicate(JQLHistoryPredicate::Before(value.into()))
+    }
+
+    /// Adds `ON value` to a `WAS` or `CHANGED` clause.
+    pub fn on(self, value: impl Into<JQLValue>) -> JQLClause {
+        self.with_predicate(JQLHistoryPredicate::On(value.into()))
+    }
+
+    /// Adds `DURING (start, end)` to a `WAS` or `CHANGED` clause.
+    pub fn during(self, start: impl Into<JQLValue>, end: impl Into<JQLValue>) -> JQLClause {
+        self.with_predicate(JQLHistoryPredicate::During(start.into(), end.into()))
+    }
+
+    /// Adds `BY user` to a `WAS` or `CHANGED` clause.
+    pub fn by(self, user: impl Into<JQLValue>) -> JQLClause {
+        self.with_predicate(JQLHistoryPredicate::By(user.into()))
+    }
+
+    /// Adds `FROM value` to a `CHANGED` clause.  This is not named `from`, so that it does not hide `From::from`.
+    pub fn changed_from(self, value: impl Into<JQLValue>) -> JQLClause {
+        self.with_predicate(JQLHistoryPredicate::From(value.into()))
+    }
+
+    /// Adds `TO value` to a `CHANGED` clause.
+    pub fn changed_to(self, value: impl Into<JQLValue>) -> JQLClause {
+        self.with_predicate(JQLHistoryPredicate::To(value.into()))
+    }
+
+    fn with_predicate(mut self, predicate: JQLHistoryPredicate) -> JQLClause {
+        match &mut self {
+            JQLClause::Was(_, _, predicates)
+            | JQLClause::WasIn(_, _, predicates)
+            | JQLClause::WasNot(_, _, predicates)
+            | JQLClause::WasNotIn(_, _, predicates)
+            | JQLClause::Changed(_, predicates) => predicates.push(predicate),
+            clause => panic!(
+                "history predicates only apply to WAS and CHANGED clauses, not {}",
+                crate::SerializableToJQL::serialize_to_jql(clause)
+            ),
+        }
+        self
+    }
+
+    /// Orders the issues the clause matches by some fields, like `field("created").desc()`, giving a statement to
+    /// search with.
+    pub fn order_by<P: Into<JQLOrderByPart>>(
+        self,
+        parts: impl IntoIterator<Item = P>,
+    ) -> JQLStatement {
+        JQLStatement {
+            clause: self,
+            order_by: Some(JQLOrderBy(parts.into_iter().map(Into::into).collect())),
+        }
+    }
+}
+
+/// `NOT clause`
+impl Not for JQLClause {
+    type Output = JQLClause;
+
+    fn not(self) -> JQLClause {
+        JQLClause::Not(Box::new(self))
+    }
+}
+
+impl From<JQLClause> for JQLStatement {
+    fn from(clause: JQLClause) -> Self {
+        JQLStatement {
+            clause,
+            order_by: None,
+        }
+    }
+}
@@ -0,0 +1,1264 @@
+//! Provides the JQL types, parser and builder for `jimberlage_jira_client`.
+//!
+//! Use these through the `jql` module of `jimberlage_jira_client`, which re-exports everything here.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, TimeZone};
+use serde::{Serialize, Serializer};
+
+mod builder;
+mod parse;
+mod template;
+
+pub use self::builder::{field, JQLFieldBuilder};
+pub use self::parse::JQLParseError;
+
+/// Supports `jql!`, which expands to code using these.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::template::*;
+}
+
+/// Escapes text for use in a JQL query.
+///
+/// See ["Restricted words and characters"][1] to see where these escape characters are sourced from.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::jql;
+///
+/// assert_eq!(jql::escape_text_field("foo.bar@example.com"), "\"foo.bar@example.com\"".to_owned());
+/// assert_eq!(jql::escape_text_field("[foo]:(bar)"), "\"\\\\[foo\\\\]\\\\:\\\\(bar\\\\)\"".to_owned());
+/// assert_eq!(jql::escape_text_field("C:\\"), "\"C\\\\:\\\\\\\\\"".to_owned());
+/// ```
+///
+/// [1]: https://support.atlassian.com/jira-software-cloud/docs/what-is-advanced-searching-in-jira-cloud/#Advancedsearching-restrictionsRestrictedwordsandcharacters
+pub fn escape_text_field(s: &str) -> String {
+    let mut escaped_chars: Vec<char> = vec![];
+
+    for c in s.chars() {
+        match c {
+            '"' => {
+                escaped_chars.push('\\');
+            }
+            // The backslash escaping the backslash needs escaping too, or it would escape the next character.
+            '\\' => {
+                escaped_chars.push('\\');
+                escaped_chars.push('\\');
+                escaped_chars.push('\\');
+            }
+            '+' | '-' | '&' | '|' | '!' | '(' | ')' | '{' | '}' | '[' | ']' | '^' | '~' | '*'
+            | '?' | ':' => {
+                escaped_chars.push('\\');
+                escaped_chars.push('\\');
+            }
+            _ => (),
+        }
+
+        escaped_chars.push(c);
+    }
+
+    format!("\"{}\"", escaped_chars.iter().collect::<String>())
+}
+
+/// Represents an object that has a string representation in JQL, either as a standalone query or as part of a query.
+pub trait SerializableToJQL {
+    fn serialize_to_jql(&self) -> String;
+}
+
+/// Represents a [value][1] in JQL.
+///
+/// [1]: https://support.atlassian.com/jira-software-cloud/docs/what-is-advanced-searching-in-jira-cloud/#Advancedsearching-ConstructingJQLqueries
+#[derive(Debug, Clone, PartialEq)]
+pub enum JQLValue {
+    String(String),
+    Int(i64),
+    /// A number with a fractional part.  This should be finite: JQL has no way to write NaN or infinities, which are
+    /// serialized as `NaN`, `inf` and `-inf`, and JIRA does not read those as numbers.
+    Float(f64),
+    NaiveDate(NaiveDate),
+    /// A date and time, to the minute, in the time zone of the user searching.
+    NaiveDateTime(NaiveDateTime),
+    /// A time relative to now, like `-2w` for two weeks ago.
+    Duration(JQLDuration),
+    Empty,
+    Null,
+    Function(JQLFunction),
+}
+
+impl SerializableToJQL for JQLValue {
+    /// Serialize the JQL value to its representation as part of a string.
+    ///
+    /// This involves escaping string fields appropriately.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use jimberlage_jira_client::jql::{self, JQLDuration, JQLFunction, SerializableToJQL};
+    ///
+    /// assert_eq!(jql::JQLValue::String("Hello world".to_owned()).serialize_to_jql(), "\"Hello world\"".to_owned());
+    /// assert_eq!(jql::JQLValue::String("^latest".to_owned()).serialize_to_jql(), "\"\\\\^latest\"".to_owned());
+    /// assert_eq!(jql::JQLValue::Float(2.5).serialize_to_jql(), "2.5".to_owned());
+    /// assert_eq!(
+    ///     jql::JQLValue::NaiveDateTime(NaiveDate::from_ymd_opt(2023, 5, 10).unwrap().and_hms_opt(14, 23, 0).unwrap())
+    ///         .serialize_to_jql(),
+    ///     "\"2023/05/10 14:23\"".to_owned()
+    /// );
+    /// assert_eq!(jql::JQLValue::Duration(JQLDuration::weeks(-2)).serialize_to_jql(), "-2w".to_owned());
+    /// assert_eq!(jql::JQLValue::Function(JQLFunction::CurrentUser).serialize_to_jql(), "currentUser()".to_owned());
+    /// ```
+    fn serialize_to_jql(&self) -> String {
+        match self {
+            JQLValue::String(contents) => escape_text_field(contents),
+            JQLValue::Int(number) => number.to_string(),
+            JQLValue::Float(number) => number.to_string(),
+            JQLValue::NaiveDate(date) => format!("\"{}\"", date.format("%Y-%m-%d")),
+            JQLValue::NaiveDateTime(datetime) => {
+                format!("\"{}\"", datetime.format("%Y/%m/%d %H:%M"))
+            }
+            JQLValue::Duration(duration) => duration.serialize_to_jql(),
+            JQLValue::Empty => "EMPTY".to_owned(),
+            JQLValue::Null => "NULL".to_owned(),
+            JQLValue::Function(function) => function.serialize_to_jql(),
+        }
+    }
+}
+
+impl From<String> for JQLValue {
+    fn from(contents: String) -> Self {
+        JQLValue::String(contents)
+    }
+}
+
+impl From<&String> for JQLValue {
+    fn from(contents: &String) -> Self {
+        JQLValue::String(contents.clone())
+    }
+}
+
+impl From<&str> for JQLValue {
+    fn from(contents: &str) -> Self {
+        JQLValue::String(contents.to_owned())
+    }
+}
+
+impl From<i64> for JQLValue {
+    fn from(number: i64) -> Self {
+        JQLValue::Int(number)
+    }
+}
+
+impl From<i8> for JQLValue {
+    fn from(number: i8) -> Self {
+        JQLValue::Int(number.into())
+    }
+}
+
+impl From<i16> for JQLValue {
+    fn from(number: i16) -> Self {
+        JQLValue::Int(number.into())
+    }
+}
+
+impl From<i32> for JQLValue {
+    fn from(number: i32) -> Self {
+        JQLValue::Int(number.into())
+    }
+}
+
+impl From<isize> for JQLValue {
+    fn from(number: isize) -> Self {
+        // Unwrap here is considered safe since no platform Rust supports has pointers wider than 64 bits.
+        JQLValue::Int(number.try_into().unwrap())
+    }
+}
+
+impl From<u8> for JQLValue {
+    fn from(number: u8) -> Self {
+        JQLValue::Int(number.into())
+    }
+}
+
+impl From<u16> for JQLValue {
+    fn from(number: u16) -> Self {
+        JQLValue::Int(number.into())
+    }
+}
+
+impl From<u32> for JQLValue {
+    fn from(number: u32) -> Self {
+        JQLValue::Int(number.into())
+    }
+}
+
+/// Numbers too large for `JQLValue::Int` are written as a string of their digits instead.
+impl From<u64> for JQLValue {
+    fn from(number: u64) -> Self {
+        i64::try_from(number)
+            .map(JQLValue::Int)
+            .unwrap_or_else(|_| JQLValue::String(number.to_string()))
+    }
+}
+
+/// Numbers too large for `JQLValue::Int` are written as a string of their digits instead.
+impl From<usize> for JQLValue {
+    fn from(number: usize) -> Self {
+        i64::try_from(number)
+            .map(JQLValue::Int)
+            .unwrap_or_else(|_| JQLValue::String(number.to_string()))
+    }
+}
+
+impl From<f32> for JQLValue {
+    fn from(number: f32) -> Self {
+        JQLValue::Float(number.into())
+    }
+}
+
+impl From<f64> for JQLValue {
+    fn from(number: f64) -> Self {
+        JQLValue::Float(number)
+    }
+}
+
+impl From<NaiveDate> for JQLValue {
+    fn from(date: NaiveDate) -> Self {
+        JQLValue::NaiveDate(date)
+    }
+}
+
+impl From<NaiveDateTime> for JQLValue {
+    fn from(datetime: NaiveDateTime) -> Self {
+        JQLValue::NaiveDateTime(datetime)
+    }
+}
+
+/// JQL reads dates and times in the time zone of the user searching, so this uses the local time in the time zone of
+/// `datetime`.  Convert it to that time zone first, with `DateTime::with_timezone`, if the two differ.
+impl<Tz: TimeZone> From<DateTime<Tz>> for JQLValue {
+    fn from(datetime: DateTime<Tz>) -> Self {
+        JQLValue::NaiveDateTime(datetime.naive_local())
+    }
+}
+
+impl From<JQLDuration> for JQLValue {
+    fn from(duration: JQLDuration) -> Self {
+        JQLValue::Duration(duration)
+    }
+}
+
+impl From<TimeDelta> for JQLValue {
+    fn from(duration: TimeDelta) -> Self {
+        JQLValue::Duration(duration.into())
+    }
+}
+
+impl From<JQLFunction> for JQLValue {
+    fn from(function: JQLFunction) -> Self {
+        JQLValue::Function(function)
+    }
+}
+
+/// Represents a length of time in JQL, like `-2w` or `4d`, relative to now or to the time a function works from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JQLDuration {
+    /// How many units; negative amounts are in the past.
+    pub amount: i64,
+
+    pub unit: JQLDurationUnit,
+}
+
+impl JQLDuration {
+    pub fn new(amount: i64, unit: JQLDurationUnit) -> Self {
+        JQLDuration { amount, unit }
+    }
+
+    pub fn minutes(amount: i64) -> Self {
+        JQLDuration::new(amount, JQLDurationUnit::Minute)
+    }
+
+    pub fn hours(amount: i64) -> Self {
+        JQLDuration::new(amount, JQLDurationUnit::Hour)
+    }
+
+    pub fn days(amount: i64) -> Self {
+        JQLDuration::new(amount, JQLDurationUnit::Day)
+    }
+
+    pub fn weeks(amount: i64) -> Self {
+        JQLDuration::new(amount, JQLDurationUnit::Week)
+    }
+}
+
+/// Uses the largest unit which the duration is a whole number of, down to minutes.  Any seconds left over are dropped,
+/// since JQL has no unit for them.
+///
+/// ### Example
+///
+/// ```
+/// use chrono::TimeDelta;
+/// use jimberlage_jira_client::jql::JQLDuration;
+///
+/// assert_eq!(JQLDuration::from(TimeDelta::days(-14)), JQLDuration::weeks(-2));
+/// assert_eq!(JQLDuration::from(TimeDelta::hours(36)), JQLDuration::hours(36));
+/// assert_eq!(JQLDuration::from(TimeDelta::seconds(90)), JQLDuration::minutes(1));
+/// ```
+impl From<TimeDelta> for JQLDuration {
+    fn from(duration: TimeDelta) -> Self {
+        let minutes = duration.num_minutes();
+
+        [
+            (7 * 24 * 60, JQLDurationUnit::Week),
+            (24 * 60, JQLDurationUnit::Day),
+            (60, JQLDurationUnit::Hour),
+        ]
+        .into_iter()
+        .find(|(length, _)| minutes != 0 && minutes % length == 0)
+        .map(|(length, unit)| JQLDuration::new(minutes / length, unit))
+        .unwrap_or_else(|| JQLDuration::minutes(minutes))
+    }
+}
+
+impl SerializableToJQL for JQLDuration {
+    fn serialize_to_jql(&self) -> String {
+        format!("{}{}", self.amount, self.unit.serialize_to_jql())
+    }
+}
+
+/// Represents the unit of a `JQLDuration`.  Months and years only work as offsets to functions like `startOfMonth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JQLDurationUnit {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl SerializableToJQL for JQLDurationUnit {
+    fn serialize_to_jql(&self) -> String {
+        match self {
+            JQLDurationUnit::Minute => "m",
+            JQLDurationUnit::Hour => "h",
+            JQLDurationUnit::Day => "d",
+            JQLDurationUnit::Week => "w",
+            JQLDurationUnit::Month => "M",
+            JQLDurationUnit::Year => "y",
+        }
+        .to_owned()
+    }
+}
+
+/// Represents a [function][1] in JQL, which JIRA works out the value of when searching.
+///
+/// The `start_of_*` and `end_of_*` functions take an optional offset, like `startOfWeek(-1w)` for the start of last
+/// week.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::jql::{JQLDuration, JQLFunction, SerializableToJQL};
+///
+/// assert_eq!(JQLFunction::StartOfWeek(Some(JQLDuration::weeks(-1))).serialize_to_jql(), "startOfWeek(-1w)".to_owned());
+/// assert_eq!(JQLFunction::EndOfMonth(None).serialize_to_jql(), "endOfMonth()".to_owned());
+/// assert_eq!(
+///     JQLFunction::LinkedIssues("SRE-1".to_owned(), Some("blocks".to_owned())).serialize_to_jql(),
+///     "linkedIssues(\"SRE-1\", \"blocks\")".to_owned()
+/// );
+/// ```
+///
+/// [1]: https://support.atlassian.com/jira-software-cloud/docs/jql-functions/
+#[derive(Debug, Clone, PartialEq)]
+pub enum JQLFunction {
+    CurrentUser,
+    /// The users in the group with the name.
+    MembersOf(String),
+    StartOfDay(Option<JQLDuration>),
+    StartOfWeek(Option<JQLDuration>),
+    StartOfMonth(Option<JQLDuration>),
+    StartOfYear(Option<JQLDuration>),
+    EndOfDay(Option<JQLDuration>),
+    EndOfWeek(Option<JQLDuration>),
+    EndOfMonth(Option<JQLDuration>),
+    EndOfYear(Option<JQLDuration>),
+    Now,
+    OpenSprints,
+    ClosedSprints,
+    FutureSprints,
+    /// The issues the user searching has viewed recently.
+    IssueHistory,
+    /// The issues linked to the issue with the key, optionally only by links which read one way, like "blocks".
+    LinkedIssues(String, Option<String>),
+    /// The released versions of the project with the key, or of all projects.
+    ReleasedVersions(Option<String>),
+    /// The unreleased versions of the project with the key, or of all projects.
+    UnreleasedVersions(Option<String>),
+    /// The most recently released version of the project with the key.
+    LatestReleasedVersion(String),
+    /// The issues the user has changed, optionally only between two dates or durations.
+    UpdatedBy(String, Option<Box<JQLValue>>, Option<Box<JQLValue>>),
+    /// Any other function, like those added by apps, by name.  String arguments are quoted as they are, like those of
+    /// the functions above, since they are not text searches.
+    Other(String, Vec<JQLValue>),
+}
+
+impl JQLFunction {
+    fn name(&self) -> &str {
+        match self {
+            JQLFunction::CurrentUser => "currentUser",
+            JQLFunction::MembersOf(_) => "membersOf",
+            JQLFunction::StartOfDay(_) => "startOfDay",
+            JQLFunction::StartOfWeek(_) => "startOfWeek",
+            JQLFunction::StartOfMonth(_) => "startOfMonth",
+            JQLFunction::StartOfYear(_) => "startOfYear",
+            JQLFunction::EndOfDay(_) => "endOfDay",
+            JQLFunction::EndOfWeek(_) => "endOfWeek",
+            JQLFunction::EndOfMonth(_) => "endOfMonth",
+            JQLFunction::EndOfYear(_) => "endOfYear",
+            JQLFunction::Now => "now",
+            JQLFunction::OpenSprints => "openSprints",
+            JQLFunction::ClosedSprints => "closedSprints",
+            JQLFunction::FutureSprints => "futureSprints",
+            JQLFunction::IssueHistory => "issueHistory",
+            JQLFunction::LinkedIssues(..) => "linkedIssues",
+            JQLFunction::ReleasedVersions(_) => "releasedVersions",
+            JQLFunction::UnreleasedVersions(_) => "unreleasedVersions",
+            JQLFunction::LatestReleasedVersion(_) => "latestReleasedVersion",
+            JQLFunction::UpdatedBy(..) => "updatedBy",
+            JQLFunction::Other(name, _) => name,
+        }
+    }
+
+    fn arguments(&self) -> Vec<String> {
+        match self {
+            JQLFunction::CurrentUser
+            | JQLFunction::Now
+            | JQLFunction::OpenSprints
+            | JQLFunction::ClosedSprints
+            | JQLFunction::FutureSprints
+            | JQLFunction::IssueHistory => vec![],
+            JQLFunction::MembersOf(argument) | JQLFunction::LatestReleasedVersion(argument) => {
+                vec![quote(argument)]
+            }
+            JQLFunction::StartOfDay(offset)
+            | JQLFunction::StartOfWeek(offset)
+            | JQLFunction::StartOfMonth(offset)
+            | JQLFunction::StartOfYear(offset)
+            | JQLFunction::EndOfDay(offset)
+            | JQLFunction::EndOfWeek(offset)
+            | JQLFunction::EndOfMonth(offset)
+            | JQLFunction::EndOfYear(offset) => offset
+                .iter()
+                .map(|offset| offset.serialize_to_jql())
+                .collect(),
+            JQLFunction::ReleasedVersions(project) | JQLFunction::UnreleasedVersions(project) => {
+                project.iter().map(|project| quote(project)).collect()
+            }
+            JQLFunction::LinkedIssues(key, link_type) => std::iter::once(key)
+                .chain(link_type)
+                .map(|argument| quote(argument))
+                .collect(),
+            JQLFunction::UpdatedBy(user, from, to) => std::iter::once(quote(user))
+                .chain(from.iter().chain(to).map(|value| serialize_argument(value)))
+                .collect(),
+            JQLFunction::Other(_, arguments) => arguments.iter().map(serialize_argument).collect(),
+        }
+    }
+}
+
+/// Quotes a function argument.  Unlike `escape_text_field`, this leaves characters which only mean something in text
+/// searches alone, since arguments like issue keys are matched exactly.
+fn quote(argument: &str) -> String {
+    format!(
+        "\"{}\"",
+        argument.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Serializes a value passed to a function, quoting strings with `quote` rather than escaping them for text searches.
+fn serialize_argument(argument: &JQLValue) -> String {
+    match argument {
+        JQLValue::String(contents) => quote(contents),
+        _ => argument.serialize_to_jql(),
+    }
+}
+
+impl SerializableToJQL for JQLFunction {
+    fn serialize_to_jql(&self) -> String {
+        format!("{}({})", self.name(), self.arguments().join(", "))
+    }
+}
+
+/// Represents a [field][1] in JQL.
+///
+/// Fields are quoted where they need to be, so that names with spaces or which are reserved words are read as
+/// fields.  Converting from a string works out which kind of field it is: `cf[10016]` or `customfield_10016` is a
+/// custom field, a single word like `fixVersion` or `issue.property[support].level` is a system field, and anything
+/// else is a display name.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::jql::{JQLField, SerializableToJQL};
+///
+/// assert_eq!(JQLField::from("project").serialize_to_jql(), "project".to_owned());
+/// assert_eq!(JQLField::from("cf[10016]"), JQLField::Custom(10016));
+/// assert_eq!(JQLField::from("customfield_10016").serialize_to_jql(), "cf[10016]".to_owned());
+/// assert_eq!(JQLField::from("Story Points").serialize_to_jql(), "\"Story Points\"".to_owned());
+/// assert_eq!(JQLField::System("order".to_owned()).serialize_to_jql(), "\"order\"".to_owned());
+/// ```
+///
+/// [1]: https://support.atlassian.com/jira-software-cloud/docs/jql-fields/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JQLField {
+    /// A field built into JIRA, by its name in JQL, like `project` or `fixVersion`.
+    System(String),
+
+    /// A custom field, by the number in its ID, like 10016 for `customfield_10016`.  This is written `cf[10016]`, which
+    /// unlike its name is the same on every JIRA site it is on, and is never ambiguous.
+    Custom(u64),
+
+    /// A field by the name JIRA shows for it, like "Story Points".  This is always quoted.
+    Name(String),
+}
+
+impl JQLField {
+    /// Refers to a custom field by its ID, like `customfield_10016`, as found in `Field::id`.  Returns `None` for IDs
+    /// of system fields.
+    pub fn from_custom_field_id(id: &str) -> Option<Self> {
+        id.strip_prefix("customfield_")?
+            .parse()
+            .ok()
+            .map(JQLField::Custom)
+    }
+
+    /// Reads a field written as `cf[10016]`.
+    fn from_clause_name(clause_name: &str) -> Option<Self> {
+        let number = match clause_name.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("cf[") => {
+                clause_name[3..].strip_suffix(']')?
+            }
+            _ => return None,
+        };
+
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            number.parse().ok().map(JQLField::Custom)
+        } else {
+            None
+        }
+    }
+}
+
+impl SerializableToJQL for JQLField {
+    fn serialize_to_jql(&self) -> String {
+        match self {
+            JQLField::System(name) if is_bare_field(name) => name.clone(),
+            JQLField::System(name) | JQLField::Name(name) => quote(name),
+            JQLField::Custom(number) => format!("cf[{}]", number),
+        }
+    }
+}
+
+impl From<&str> for JQLField {
+    fn from(field: &str) -> Self {
+        if let Some(custom) =
+            JQLField::from_clause_name(field).or_else(|| JQLField::from_custom_field_id(field))
+        {
+            custom
+        } else if is_bare_field(field) {
+            JQLField::System(field.to_owned())
+        } else {
+            JQLField::Name(field.to_owned())
+        }
+    }
+}
+
+impl From<String> for JQLField {
+    fn from(field: String) -> Self {
+        JQLField::from(field.as_str())
+    }
+}
+
+/// Words JQL reserves, which must be quoted to be used as field names.
+///
+/// See ["Restricted words and characters"][1].
+///
+/// [1]: https://support.atlassian.com/jira-software-cloud/docs/what-is-advanced-searching-in-jira-cloud/#Advancedsearching-restrictionsRestrictedwordsandcharacters
+const RESERVED_WORDS: &[&str] = &[
+    "a",
+    "an",
+    "abort",
+    "access",
+    "add",
+    "after",
+    "alias",
+    "all",
+    "alter",
+    "and",
+    "any",
+    "are",
+    "as",
+    "asc",
+    "audit",
+    "avg",
+    "before",
+    "begin",
+    "between",
+    "boolean",
+    "break",
+    "by",
+    "byte",
+    "catch",
+    "cf",
+    "changed",
+    "char",
+    "character",
+    "check",
+    "checkpoint",
+    "collate",
+    "collation",
+    "column",
+    "commit",
+    "connect",
+    "continue",
+    "count",
+    "create",
+    "current",
+    "date",
+    "decimal",
+    "declare",
+    "decrement",
+    "default",
+    "defaults",
+    "define",
+    "delete",
+    "delimiter",
+    "desc",
+    "difference",
+    "distinct",
+    "divide",
+    "do",
+    "double",
+    "drop",
+    "during",
+    "else",
+    "empty",
+    "encoding",
+    "end",
+    "equals",
+    "escape",
+    "exclusive",
+    "exec",
+    "execute",
+    "exists",
+    "explain",
+    "false",
+    "fetch",
+    "file",
+    "field",
+    "first",
+    "float",
+    "for",
+    "from",
+    "function",
+    "go",
+    "goto",
+    "grant",
+    "greater",
+    "group",
+    "having",
+    "identified",
+    "if",
+    "immediate",
+    "in",
+    "increment",
+    "index",
+    "initial",
+    "inner",
+    "inout",
+    "input",
+    "insert",
+    "int",
+    "integer",
+    "intersect",
+    "intersection",
+    "into",
+    "is",
+    "isempty",
+    "isnull",
+    "join",
+    "last",
+    "left",
+    "less",
+    "like",
+    "limit",
+    "lock",
+    "long",
+    "max",
+    "merge",
+    "min",
+    "minus",
+    "mode",
+    "modify",
+    "modulo",
+    "more",
+    "multiply",
+    "next",
+    "noaudit",
+    "not",
+    "notin",
+    "nowait",
+    "null",
+    "number",
+    "object",
+    "of",
+    "on",
+    "option",
+    "or",
+    "order",
+    "outer",
+    "output",
+    "power",
+    "previous",
+    "prior",
+    "privileges",
+    "public",
+    "raise",
+    "raw",
+    "remainder",
+    "rename",
+    "resume",
+    "return",
+    "returns",
+    "revoke",
+    "right",
+    "row",
+    "rowid",
+    "rownum",
+    "rows",
+    "select",
+    "session",
+    "set",
+    "share",
+    "size",
+    "sqrt",
+    "start",
+    "string",
+    "subtract",
+    "sum",
+    "synonym",
+    "table",
+    "then",
+    "to",
+    "trans",
+    "transaction",
+    "trigger",
+    "true",
+    "uid",
+    "union",
+    "unique",
+    "update",
+    "user",
+    "validate",
+    "values",
+    "view",
+    "was",
+    "when",
+    "whenever",
+    "where",
+    "while",
+    "with",
+];
+
+fn is_reserved(name: &str) -> bool {
+    RESERVED_WORDS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Returns whether a field can be written without quotes, which is any single word that is not reserved, so that
+/// fields like `issue.property[x].y` are left alone.  Converting from a string reads such fields as system fields.
+fn is_bare_field(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(parse::is_word_char) && !is_reserved(name)
+}
+
+/// Represents whether a field has no value, for `JQLClause::Is` and `JQLClause::IsNot`.  JIRA treats the two the
+/// same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JQLEmpty {
+    Empty,
+    Null,
+}
+
+impl SerializableToJQL for JQLEmpty {
+    fn serialize_to_jql(&self) -> String {
+        match self {
+            JQLEmpty::Empty => "EMPTY".to_owned(),
+            JQLEmpty::Null => "NULL".to_owned(),
+        }
+    }
+}
+
+/// Narrows down a history clause, like `WAS` or `CHANGED`, to when, by whom or how a field changed.
+///
+/// `From` and `To` only work with `JQLClause::Changed`.
+///
+/// See ["Operators"][1] for which predicates each history operator takes.
+///
+/// [1]: https://support.atlassian.com/jira-software-cloud/docs/jql-operators/
+#[derive(Debug, Clone, PartialEq)]
+pub enum JQLHistoryPredicate {
+    After(JQLValue),
+    Before(JQLValue),
+    On(JQLValue),
+    During(JQLValue, JQLValue),
+    By(JQLValue),
+    From(JQLValue),
+    To(JQLValue),
+}
+
+impl SerializableToJQL for JQLHistoryPredicate {
+    fn serialize_to_jql(&self) -> String {
+        match self {
+            JQLHistoryPredicate::After(value) => format!("AFTER {}", value.serialize_to_jql()),
+            JQLHistoryPredicate::Before(value) => format!("BEFORE {}", value.serialize_to_jql()),
+            JQLHistoryPredicate::On(value) => format!("ON {}", value.serialize_to_jql()),
+            JQLHistoryPredicate::During(start, end) => format!(
+                "DURING ({}, {})",
+                start.serialize_to_jql(),
+                end.serialize_to_jql()
+            ),
+            JQLHistoryPredicate::By(value) => format!("BY {}", value.serialize_to_jql()),
+            JQLHistoryPredicate::From(value) => format!("FROM {}", value.serialize_to_jql()),
+            JQLHistoryPredicate::To(value) => format!("TO {}", value.serialize_to_jql()),
+        }
+    }
+}
+
+/// Represents a [clause][1] in JQL.
+///
+/// `And`, `Or` and `Not` combine other clauses; the rest compare a field against values.  An empty `And` or `Or`, or a
+/// `Not` of one, cannot be written in JQL, so it is left out of the clause it is in, and an empty statement matches
+/// every issue.
+///
+/// [1]: https://support.atlassian.com/jira-software-cloud/docs/what-is-advanced-searching-in-jira-cloud/#Advancedsearching-ConstructingJQLqueries
+#[derive(Debug, Clone, PartialEq)]
+pub enum JQLClause {
+    And(Vec<Box<JQLClause>>),
+    Or(Vec<Box<JQLClause>>),
+    Not(Box<JQLClause>),
+    Equals(JQLField, JQLValue),
+    NotEquals(JQLField, JQLValue),
+    GreaterThan(JQLField, JQLValue),
+    GreaterThanEquals(JQLField, JQLValue),
+    LessThan(JQLField, JQLValue),
+    LessThanEquals(JQLField, JQLValue),
+    In(JQLField, Vec<JQLValue>),
+    NotIn(JQLField, Vec<JQLValue>),
+    /// `~`, which matches text fields by words rather than exactly.
+    Contains(JQLField, JQLValue),
+    /// `!~`
+    NotContains(JQLField, JQLValue),
+    Is(JQLField, JQLEmpty),
+    IsNot(JQLField, JQLEmpty),
+    Was(JQLField, JQLValue, Vec<JQLHistoryPredicate>),
+    WasIn(JQLField, Vec<JQLValue>, Vec<JQLHistoryPredicate>),
+    WasNot(JQLField, JQLValue, Vec<JQLHistoryPredicate>),
+    WasNotIn(JQLField, Vec<JQLValue>, Vec<JQLHistoryPredicate>),
+    Changed(JQLField, Vec<JQLHistoryPredicate>),
+}
+
+// How tightly each kind of clause binds, loosest first.  A clause is wrapped in parentheses when it is inside one
+// which binds more tightly than it does.
+const OR_PRECEDENCE: u8 = 1;
+const AND_PRECEDENCE: u8 = 2;
+const NOT_PRECEDENCE: u8 = 3;
+
+impl JQLClause {
+    /// Returns whether the clause is an `And` or `Or` with nothing in it, once empty clauses inside it are left out, or
+    /// a `Not` of one.
+    fn is_empty(&self) -> bool {
+        match self {
+            JQLClause::And(clauses) | JQLClause::Or(clauses) => {
+                clauses.iter().all(|clause| clause.is_empty())
+            }
+            JQLClause::Not(clause) => clause.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Serializes the clause as it appears inside a clause which binds as tightly as `precedence`.
+    fn serialize_within(&self, precedence: u8) -> String {
+        match self {
+            JQLClause::And(clauses) => {
+                serialize_group(clauses, " AND ", AND_PRECEDENCE, precedence)
+            }
+            JQLClause::Or(clauses) => serialize_group(clauses, " OR ", OR_PRECEDENCE, precedence),
+            JQLClause::Not(clause) if clause.is_empty() => String::new(),
+            JQLClause::Not(clause) => {
+                format!("NOT {}", clause.serialize_within(NOT_PRECEDENCE))
+            }
+            JQLClause::Equals(field, value) => {
+                format!(
+                    "{} = {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                )
+            }
+            JQLClause::NotEquals(field, value) => {
+                format!(
+                    "{} != {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                )
+            }
+            JQLClause::GreaterThan(field, value) => {
+                format!(
+                    "{} > {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                )
+            }
+            JQLClause::GreaterThanEquals(field, value) => {
+                format!(
+                    "{} >= {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                )
+            }
+            JQLClause::LessThan(field, value) => {
+                format!(
+                    "{} < {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                )
+            }
+            JQLClause::LessThanEquals(field, value) => {
+                format!(
+                    "{} <= {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                )
+            }
+            JQLClause::In(field, values) => {
+                format!("{} IN {}", field.serialize_to_jql(), serialize_list(values))
+            }
+            JQLClause::NotIn(field, values) => {
+                format!(
+                    "{} NOT IN {}",
+                    field.serialize_to_jql(),
+                    serialize_list(values)
+                )
+            }
+            JQLClause::Contains(field, value) => {
+                format!(
+                    "{} ~ {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                )
+            }
+            JQLClause::NotContains(field, value) => {
+                format!(
+                    "{} !~ {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                )
+            }
+            JQLClause::Is(field, empty) => format!(
+                "{} IS {}",
+                field.serialize_to_jql(),
+                empty.serialize_to_jql()
+            ),
+            JQLClause::IsNot(field, empty) => {
+                format!(
+                    "{} IS NOT {}",
+                    field.serialize_to_jql(),
+                    empty.serialize_to_jql()
+                )
+            }
+            JQLClause::Was(field, value, predicates) => with_predicates(
+                format!(
+                    "{} WAS {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                ),
+                predicates,
+            ),
+            JQLClause::WasIn(field, values, predicates) => with_predicates(
+                format!(
+                    "{} WAS IN {}",
+                    field.serialize_to_jql(),
+                    serialize_list(values)
+                ),
+                predicates,
+            ),
+            JQLClause::WasNot(field, value, predicates) => with_predicates(
+                format!(
+                    "{} WAS NOT {}",
+                    field.serialize_to_jql(),
+                    value.serialize_to_jql()
+                ),
+                predicates,
+            ),
+            JQLClause::WasNotIn(field, values, predicates) => with_predicates(
+                format!(
+                    "{} WAS NOT IN {}",
+                    field.serialize_to_jql(),
+                    serialize_list(values)
+                ),
+                predicates,
+            ),
+            JQLClause::Changed(field, predicates) => {
+                with_predicates(format!("{} CHANGED", field.serialize_to_jql()), predicates)
+            }
+        }
+    }
+}
+
+/// Joins the clauses of an `And` or `Or`, wrapping them in parentheses only if the group is inside a clause which
+/// binds more tightly.  A group of one clause is the same as that clause, so it is not wrapped either.
+fn serialize_group(
+    clauses: &[Box<JQLClause>],
+    separator: &str,
+    group_precedence: u8,
+    precedence: u8,
+) -> String {
+    let clauses = clauses
+        .iter()
+        .filter(|clause| !clause.is_empty())
+        .collect::<Vec<&Box<JQLClause>>>();
+
+    if let [clause] = clauses.as_slice() {
+        return clause.serialize_within(precedence);
+    }
+
+    let joined_clauses = clauses
+        .iter()
+        .map(|clause| clause.serialize_within(group_precedence))
+        .collect::<Vec<String>>()
+        .join(separator);
+
+    if group_precedence < precedence {
+        format!("({})", joined_clauses)
+    } else {
+        joined_clauses
+    }
+}
+
+fn serialize_list(values: &[JQLValue]) -> String {
+    let joined_values = values
+        .iter()
+        .map(|value| value.serialize_to_jql())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("({})", joined_values)
+}
+
+fn with_predicates(clause: String, predicates: &[JQLHistoryPredicate]) -> String {
+    predicates.iter().fold(clause, |clause, predicate| {
+        format!("{} {}", clause, predicate.serialize_to_jql())
+    })
+}
+
+impl SerializableToJQL for JQLClause {
+    /// Serialize the JQL clause to its representation as part of a string.
+    ///
+    /// This involves formatting values correctly, and ensuring operator precedence rules are respected.  `NOT` binds
+    /// most tightly, then `AND`, then `OR`, and parentheses are only added where they are needed to keep that order.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use jimberlage_jira_client::jql::{
+    ///     self, JQLClause, JQLEmpty, JQLHistoryPredicate, JQLValue, SerializableToJQL,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     JQLClause::In("project".into(), vec![]).serialize_to_jql(),
+    ///     "project IN ()".to_owned()
+    /// );
+    /// assert_eq!(
+    ///     JQLClause::In("project".into(), vec![JQLValue::String("SRE".to_owned())]).serialize_to_jql(),
+    ///     "project IN (\"SRE\")".to_owned()
+    /// );
+    /// assert_eq!(
+    ///     JQLClause::In("project".into(), vec![JQLValue::String("PE".to_owned()), JQLValue::String("SRE".to_owned())]).serialize_to_jql(),
+    ///     "project IN (\"PE\", \"SRE\")".to_owned()
+    /// );
+    /// assert_eq!(JQLClause::And(vec![]).serialize_to_jql(), "".to_owned());
+    /// assert_eq!(
+    ///     JQLClause::And(vec![
+    ///         Box::new(JQLClause::In("project".into(), vec![JQLValue::String("SRE".to_owned())]))
+    ///     ]).serialize_to_jql(),
+    ///     "project IN (\"SRE\")".to_owned()
+    /// );
+    /// assert_eq!(
+    ///     JQLClause::And(vec![
+    ///         Box::new(JQLClause::In("project".into(), vec![JQLValue::String("SRE".to_owned())])),
+    ///         Box::new(JQLClause::In("labels".into(), vec![JQLValue::String("v2022.5.10".to_owned()), JQLValue::String("v2022.6.13".to_owned())]))
+    ///     ]).serialize_to_jql(),
+    ///     "project IN (\"SRE\") AND labels IN (\"v2022.5.10\", \"v2022.6.13\")".to_owned()
+    /// );
+    /// assert_eq!(
+    ///     JQLClause::And(vec![
+    ///         Box::new(JQLClause::Equals("project".into(), JQLValue::String("SRE".to_owned()))),
+    ///         Box::new(JQLClause::Or(vec![
+    ///             Box::new(JQLClause::Is("assignee".into(), JQLEmpty::Empty)),
+    ///             Box::new(JQLClause::Not(Box::new(JQLClause::Contains("summary".into(), JQLValue::String("flaky".to_owned()))))),
+    ///         ])),
+    ///     ]).serialize_to_jql(),
+    ///     "project = \"SRE\" AND (assignee IS EMPTY OR NOT summary ~ \"flaky\")".to_owned()
+    /// );
+    /// assert_eq!(
+    ///     JQLClause::Changed("status".into(), vec![
+    ///         JQLHistoryPredicate::From(JQLValue::String("In Progress".to_owned())),
+    ///         JQLHistoryPredicate::To(JQLValue::String("Done".to_owned())),
+    ///     ]).serialize_to_jql(),
+    ///     "status CHANGED FROM \"In Progress\" TO \"Done\"".to_owned()
+    /// );
+    /// ```
+    fn serialize_to_jql(&self) -> String {
+        self.serialize_within(OR_PRECEDENCE)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JQLOrdering {
+    Asc,
+    Desc,
+}
+
+impl SerializableToJQL for JQLOrdering {
+    fn serialize_to_jql(&self) -> String {
+        match self {
+            JQLOrdering::Asc => "ASC".to_owned(),
+            JQLOrdering::Desc => "DESC".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JQLOrderByPart {
+    pub field: JQLField,
+    pub ordering: Option<JQLOrdering>,
+}
+
+impl SerializableToJQL for JQLOrderByPart {
+    fn serialize_to_jql(&self) -> String {
+        match &self.ordering {
+            Some(ordering) => format!(
+                "{} {}",
+                self.field.serialize_to_jql(),
+                ordering.serialize_to_jql()
+            ),
+            None => self.field.serialize_to_jql(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JQLOrderBy(pub Vec<JQLOrderByPart>);
+
+impl SerializableToJQL for JQLOrderBy {
+    fn serialize_to_jql(&self) -> String {
+        let serialized_fields = self
+            .0
+            .iter()
+            .map(|order| order.serialize_to_jql())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!("ORDER BY {}", serialized_fields)
+    }
+}
+
+/// Represents a [statement][1] in JQL.
+///
+/// Right now, this just represents parts of a statement I demonstrably use in my own code.
+/// It may make sense to add fields to this struct.
+///
+/// [1]: https://support.atlassian.com/jira-software-cloud/docs/what-is-advanced-searching-in-jira-cloud/#Advancedsearching-ConstructingJQLqueries
+#[derive(Debug, Clone, PartialEq)]
+pub struct JQLStatement {
+    pub clause: JQLClause,
+    pub order_by: Option<JQLOrderBy>,
+}
+
+impl SerializableToJQL for JQLStatement {
+    /// Serialize the JQL statement to its representation as part of a string.
+    ///
+    /// This involves formatting values correctly, and ensuring operator precedence rules are respected.
+    /// It may optionally involve setting an order by on the statement as well.
+    fn serialize_to_jql(&self) -> String {
+        match &self.order_by {
+            Some(order_by) if order_by.0.is_empty() => self.clause.serialize_to_jql(),
+            Some(order_by) if self.clause.is_empty() => order_by.serialize_to_jql(),
+            Some(order_by) => format!(
+                "{} {}",
+                self.clause.serialize_to_jql(),
+                order_by.serialize_to_jql()
+            ),
+            None => self.clause.serialize_to_jql(),
+        }
+    }
+}
+
+impl JQLStatement {
+    /// Parses a JQL query, like one from a saved filter.
+    ///
+    /// Unquoted values are read as what they look like: `42` is a number, `-2w` a relative duration and `2023-05-10` a
+    /// date.  Numbers not written the way they serialize, like `1.10` or `007`, are read as strings, as are quoted
+    /// values other than dates and relative dates like `"-2w"`.  Functions JIRA provides are read into their
+    /// `JQLFunction`, and any others, like those added by apps, into `JQLFunction::Other`.  Quoted fields are read as `JQLField::Name`, and
+    /// fields like `cf[10016]` as `JQLField::Custom`.  Serializing the statement again gives JQL which means the same,
+    /// though it may be written differently.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use jimberlage_jira_client::jql::{JQLClause, JQLFunction, JQLStatement, JQLValue, SerializableToJQL};
+    ///
+    /// let statement = JQLStatement::parse("project = SRE and assignee = currentUser() order by created desc").unwrap();
+    ///
+    /// assert!(matches!(
+    ///     &statement.clause,
+    ///     JQLClause::And(clauses) if *clauses[1] == JQLClause::Equals(
+    ///         "assignee".into(),
+    ///         JQLValue::Function(JQLFunction::CurrentUser)
+    ///     )
+    /// ));
+    /// assert_eq!(
+    ///     statement.serialize_to_jql(),
+    ///     "project = \"SRE\" AND assignee = currentUser() ORDER BY created DESC".to_owned()
+    /// );
+    ///
+    /// let error = JQLStatement::parse("project = SRE and").unwrap_err();
+    /// assert_eq!(error.message, "expected a field, found the end of the query");
+    /// assert_eq!(error.span, 17..17);
+    /// ```
+    pub fn parse(jql: &str) -> Result<Self, JQLParseError> {
+        parse::parse(jql)
+    }
+}
+
+impl FromStr for JQLStatement {
+    type Err = JQLParseError;
+
+    fn from_str(jql: &str) -> Result<Self, Self::Err> {
+        JQLStatement::parse(jql)
+    }
+}
+
+impl Serialize for JQLStatement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let contents = self.serialize_to_jql();
+
+        serializer.serialize_str(&contents)
+    }
+}
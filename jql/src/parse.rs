@@ -0,0 +1,818 @@
+use std::{fmt, ops::Range};
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use super::{
+    JQLClause, JQLDuration, JQLDurationUnit, JQLEmpty, JQLField, JQLFunction, JQLHistoryPredicate,
+    JQLOrderBy, JQLOrderByPart, JQLOrdering, JQLStatement, JQLValue,
+};
+
+/// Represents JQL which could not be parsed.
+///
+/// `span` is the range of bytes in the JQL where the problem is, for pointing it out to whoever wrote it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JQLParseError {
+    pub message: String,
+
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for JQLParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for JQLParseError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LeftParen,
+    RightParen,
+    Comma,
+    Equals,
+    NotEquals,
+    GreaterThan,
+    GreaterThanEquals,
+    LessThan,
+    LessThanEquals,
+    Contains,
+    NotContains,
+    /// `!`, `&&` and `||` are shorthand for `NOT`, `AND` and `OR`.
+    Bang,
+    And,
+    Or,
+    Word(String),
+    /// A quoted string, with the escapes for the quotes themselves undone, but not those for text searches.
+    Quoted(String),
+    /// `{name}` in a template for `jql!`, with what is between the braces.
+    Placeholder(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LeftParen => write!(f, "\"(\""),
+            Token::RightParen => write!(f, "\")\""),
+            Token::Comma => write!(f, "\",\""),
+            Token::Equals => write!(f, "\"=\""),
+            Token::NotEquals => write!(f, "\"!=\""),
+            Token::GreaterThan => write!(f, "\">\""),
+            Token::GreaterThanEquals => write!(f, "\">=\""),
+            Token::LessThan => write!(f, "\"<\""),
+            Token::LessThanEquals => write!(f, "\"<=\""),
+            Token::Contains => write!(f, "\"~\""),
+            Token::NotContains => write!(f, "\"!~\""),
+            Token::Bang => write!(f, "\"!\""),
+            Token::And => write!(f, "\"&&\""),
+            Token::Or => write!(f, "\"||\""),
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Quoted(_) => write!(f, "a quoted string"),
+            Token::Placeholder(name) => write!(f, "{{{}}}", name),
+        }
+    }
+}
+
+/// Splits JQL into tokens, along with where each one is.  In a template, braces outside of quotes are placeholders.
+fn tokenize(jql: &str, template: bool) -> Result<Vec<(Token, Range<usize>)>, JQLParseError> {
+    let mut tokens = vec![];
+    let mut chars = jql.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut next_is = |expected: char| chars.next_if(|(_, next)| *next == expected).is_some();
+
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            '=' => Token::Equals,
+            '~' => Token::Contains,
+            '!' if next_is('=') => Token::NotEquals,
+            '!' if next_is('~') => Token::NotContains,
+            '!' => Token::Bang,
+            '>' if next_is('=') => Token::GreaterThanEquals,
+            '>' => Token::GreaterThan,
+            '<' if next_is('=') => Token::LessThanEquals,
+            '<' => Token::LessThan,
+            '&' => {
+                next_is('&');
+                Token::And
+            }
+            '|' => {
+                next_is('|');
+                Token::Or
+            }
+            '"' | '\'' => {
+                let (contents, end) = quoted(jql, start, c, &mut chars)?;
+                tokens.push((Token::Quoted(contents), start..end));
+                continue;
+            }
+            '{' if template => {
+                let Some((end, _)) = chars.find(|(_, next)| *next == '}') else {
+                    return Err(JQLParseError {
+                        message: "unterminated placeholder".to_owned(),
+                        span: start..jql.len(),
+                    });
+                };
+                tokens.push((
+                    Token::Placeholder(jql[start + 1..end].trim().to_owned()),
+                    start..end + 1,
+                ));
+                continue;
+            }
+            _ => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, next)) =
+                    chars.next_if(|(_, next)| is_word_char(*next) && !(template && *next == '{'))
+                {
+                    end = i + next.len_utf8();
+                }
+                tokens.push((Token::Word(jql[start..end].to_owned()), start..end));
+                continue;
+            }
+        };
+
+        let end = chars.peek().map(|(i, _)| *i).unwrap_or(jql.len());
+        tokens.push((token, start..end));
+    }
+
+    Ok(tokens)
+}
+
+pub(super) fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !"(),=!<>~\"'&|".contains(c)
+}
+
+/// Reads a quoted string up to its closing quote, undoing the escapes JQL allows in one.
+fn quoted(
+    jql: &str,
+    start: usize,
+    quote: char,
+    chars: &mut std::iter::Peekable<std::str::CharIndices>,
+) -> Result<(String, usize), JQLParseError> {
+    let mut contents = String::new();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            _ if c == quote => return Ok((contents, i + 1)),
+            '\\' => {
+                let Some((_, escaped)) = chars.next() else {
+                    break;
+                };
+
+                match escaped {
+                    'n' => contents.push('\n'),
+                    't' => contents.push('\t'),
+                    'r' => contents.push('\r'),
+                    'u' => {
+                        let digits = jql.get(i + 2..i + 6).unwrap_or_default();
+                        let unicode = u32::from_str_radix(digits, 16)
+                            .ok()
+                            .filter(|_| digits.len() == 4)
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| JQLParseError {
+                                message: "expected four hex digits after \\u".to_owned(),
+                                span: i..(i + 6).min(jql.len()),
+                            })?;
+                        contents.push(unicode);
+                        for _ in 0..4 {
+                            chars.next();
+                        }
+                    }
+                    '"' | '\'' | ' ' | '\\' => contents.push(escaped),
+                    // Backslashes before anything else are kept, since they escape characters in text searches.
+                    _ => {
+                        contents.push('\\');
+                        contents.push(escaped);
+                    }
+                }
+            }
+            _ => contents.push(c),
+        }
+    }
+
+    Err(JQLParseError {
+        message: "unterminated string".to_owned(),
+        span: start..jql.len(),
+    })
+}
+
+/// Undoes the escapes `escape_text_field` adds for text searches, once the string has been read out of its quotes.
+fn unescape_text_field(contents: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next_if(|next| "+-&|!(){}[]^~*?\\:".contains(*next)) {
+                unescaped.push(escaped);
+                continue;
+            }
+        }
+        unescaped.push(c);
+    }
+
+    unescaped
+}
+
+/// Represents a value as written, before it is known whether it is an argument to a function, which is read
+/// differently.
+#[derive(Clone)]
+enum Operand {
+    Word(String),
+    Quoted(String),
+    Function(String, Vec<Operand>),
+    Placeholder,
+}
+
+impl Operand {
+    /// Returns the text of a word or string, for function arguments which are names, like issue keys.
+    fn text(&self) -> Option<&str> {
+        match self {
+            Operand::Word(text) | Operand::Quoted(text) => Some(text),
+            Operand::Function(..) | Operand::Placeholder => None,
+        }
+    }
+
+    fn value(&self) -> JQLValue {
+        match self {
+            Operand::Word(word) => word_value(word),
+            Operand::Quoted(contents) => quoted_value(contents),
+            Operand::Function(name, arguments) => JQLValue::Function(function(name, arguments)),
+            Operand::Placeholder => placeholder_value(),
+        }
+    }
+
+    /// Reads an argument to a function.  Quoted strings other than dates are taken as they are, since `quote` only
+    /// escapes quotes and backslashes in them, not the characters which mean something in text searches.
+    fn argument_value(&self) -> JQLValue {
+        match (self, self.value()) {
+            (Operand::Quoted(contents), JQLValue::String(_) | JQLValue::Duration(_)) => {
+                JQLValue::String(contents.clone())
+            }
+            (_, value) => value,
+        }
+    }
+}
+
+/// Works out what an unquoted word means, like a number, a relative duration or a date.
+///
+/// A word is only read as a number if it is written the way the number serializes, so that `1.10` and `007` stay as
+/// written, as the versions or IDs they usually are, rather than becoming `1.1` and `7`.
+fn word_value(word: &str) -> JQLValue {
+    if word.eq_ignore_ascii_case("empty") {
+        JQLValue::Empty
+    } else if word.eq_ignore_ascii_case("null") {
+        JQLValue::Null
+    } else if let Some(number) = word
+        .parse::<i64>()
+        .ok()
+        .filter(|number| number.to_string() == word)
+    {
+        JQLValue::Int(number)
+    } else if let Some(number) = word
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && number.to_string() == word)
+    {
+        JQLValue::Float(number)
+    } else if let Some(duration) = duration(word) {
+        JQLValue::Duration(duration)
+    } else if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        JQLValue::NaiveDate(date)
+    } else {
+        JQLValue::String(word.to_owned())
+    }
+}
+
+/// Works out what a quoted string means.  Dates and times are quoted, so are read as them, as are relative dates with
+/// a sign, like `"-2w"`, which would otherwise be escaped as text when serialized and no longer read as dates.  Anything
+/// else, even if it looks like a number, stays a string.
+///
+/// Dates written with slashes stay strings, since `escape_text_field` does not escape slashes and so cannot tell the
+/// two apart; JIRA reads either as a date for date fields.
+fn quoted_value(contents: &str) -> JQLValue {
+    if let Ok(date) = NaiveDate::parse_from_str(contents, "%Y-%m-%d") {
+        JQLValue::NaiveDate(date)
+    } else if let Some(datetime) = ["%Y/%m/%d %H:%M", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(contents, format).ok())
+    {
+        JQLValue::NaiveDateTime(datetime)
+    } else if let Some(duration) = duration(contents).filter(|_| contents.starts_with(['-', '+'])) {
+        JQLValue::Duration(duration)
+    } else {
+        JQLValue::String(unescape_text_field(contents))
+    }
+}
+
+fn duration(text: &str) -> Option<JQLDuration> {
+    let unit = match text.chars().last()? {
+        'm' => JQLDurationUnit::Minute,
+        'h' => JQLDurationUnit::Hour,
+        'd' => JQLDurationUnit::Day,
+        'w' => JQLDurationUnit::Week,
+        'M' => JQLDurationUnit::Month,
+        'y' => JQLDurationUnit::Year,
+        _ => return None,
+    };
+    let amount = &text[..text.len() - 1];
+    let digits = amount.strip_prefix(['-', '+']).unwrap_or(amount);
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(JQLDuration::new(amount.parse().ok()?, unit))
+}
+
+/// Reads a function call into a `JQLFunction`.  Calls which do not fit one of the typed functions, like those added by
+/// apps or with arguments of another type, are kept as `JQLFunction::Other`.
+fn function(name: &str, arguments: &[Operand]) -> JQLFunction {
+    typed_function(&name.to_ascii_lowercase(), arguments).unwrap_or_else(|| {
+        JQLFunction::Other(
+            name.to_owned(),
+            arguments.iter().map(Operand::argument_value).collect(),
+        )
+    })
+}
+
+fn typed_function(name: &str, arguments: &[Operand]) -> Option<JQLFunction> {
+    let offset_unit = match name {
+        "startofday" | "endofday" => Some(JQLDurationUnit::Day),
+        "startofweek" | "endofweek" => Some(JQLDurationUnit::Week),
+        "startofmonth" | "endofmonth" => Some(JQLDurationUnit::Month),
+        "startofyear" | "endofyear" => Some(JQLDurationUnit::Year),
+        _ => None,
+    };
+
+    if let Some(unit) = offset_unit {
+        let offset = match arguments {
+            [] => None,
+            [argument] => match argument.value() {
+                JQLValue::Duration(duration) => Some(duration),
+                // A bare number counts in the unit of the function, like months for startOfMonth(-1).
+                JQLValue::Int(amount) => Some(JQLDuration::new(amount, unit)),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        return Some(match name {
+            "startofday" => JQLFunction::StartOfDay(offset),
+            "startofweek" => JQLFunction::StartOfWeek(offset),
+            "startofmonth" => JQLFunction::StartOfMonth(offset),
+            "startofyear" => JQLFunction::StartOfYear(offset),
+            "endofday" => JQLFunction::EndOfDay(offset),
+            "endofweek" => JQLFunction::EndOfWeek(offset),
+            "endofmonth" => JQLFunction::EndOfMonth(offset),
+            _ => JQLFunction::EndOfYear(offset),
+        });
+    }
+
+    if name == "updatedby" {
+        let [user, dates @ ..] = arguments else {
+            return None;
+        };
+        if dates.len() > 2 {
+            return None;
+        }
+        let mut dates = dates.iter().map(|date| Box::new(date.argument_value()));
+
+        return Some(JQLFunction::UpdatedBy(
+            user.text()?.to_owned(),
+            dates.next(),
+            dates.next(),
+        ));
+    }
+
+    let texts = arguments
+        .iter()
+        .map(|argument| argument.text().map(str::to_owned))
+        .collect::<Option<Vec<String>>>()?;
+
+    match (name, texts.as_slice()) {
+        ("currentuser", []) => Some(JQLFunction::CurrentUser),
+        ("now", []) => Some(JQLFunction::Now),
+        ("opensprints", []) => Some(JQLFunction::OpenSprints),
+        ("closedsprints", []) => Some(JQLFunction::ClosedSprints),
+        ("futuresprints", []) => Some(JQLFunction::FutureSprints),
+        ("issuehistory", []) => Some(JQLFunction::IssueHistory),
+        ("membersof", [group]) => Some(JQLFunction::MembersOf(group.clone())),
+        ("latestreleasedversion", [project]) => {
+            Some(JQLFunction::LatestReleasedVersion(project.clone()))
+        }
+        ("releasedversions", []) => Some(JQLFunction::ReleasedVersions(None)),
+        ("releasedversions", [project]) => {
+            Some(JQLFunction::ReleasedVersions(Some(project.clone())))
+        }
+        ("unreleasedversions", []) => Some(JQLFunction::UnreleasedVersions(None)),
+        ("unreleasedversions", [project]) => {
+            Some(JQLFunction::UnreleasedVersions(Some(project.clone())))
+        }
+        ("linkedissues", [key]) => Some(JQLFunction::LinkedIssues(key.clone(), None)),
+        ("linkedissues", [key, link_type]) => Some(JQLFunction::LinkedIssues(
+            key.clone(),
+            Some(link_type.clone()),
+        )),
+        _ => None,
+    }
+}
+
+/// Represents a placeholder in a template for `jql!`, which is filled in with a Rust value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placeholder {
+    /// What is between the braces: nothing, the index of an argument, or a name.
+    pub name: String,
+
+    pub span: Range<usize>,
+
+    /// Whether the placeholder stands for a whole list of values, as in `status IN {statuses}`, rather than one value.
+    pub list: bool,
+}
+
+/// Stands in for a placeholder in a parsed template.  No function parsed from JQL has an empty name.
+pub fn placeholder_value() -> JQLValue {
+    JQLValue::Function(JQLFunction::Other(String::new(), vec![]))
+}
+
+/// Parses JQL by recursive descent, with `NOT` binding most tightly, then `AND`, then `OR`.
+struct Parser {
+    tokens: Vec<(Token, Range<usize>)>,
+    position: usize,
+    length: usize,
+    placeholders: Vec<Placeholder>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        self.keyword_at(self.position, keyword)
+    }
+
+    fn keyword_at(&self, position: usize, keyword: &str) -> bool {
+        matches!(
+            self.tokens.get(position),
+            Some((Token::Word(word), _)) if word.eq_ignore_ascii_case(keyword)
+        )
+    }
+
+    fn eat(&mut self, expected: &Token) -> bool {
+        let found = self.peek() == Some(expected);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_keyword(keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    /// Describes what was found where something else was expected.
+    fn unexpected(&self, expected: &str) -> JQLParseError {
+        match self.tokens.get(self.position) {
+            Some((token, span)) => JQLParseError {
+                message: format!("expected {}, found {}", expected, token),
+                span: span.clone(),
+            },
+            None => JQLParseError {
+                message: format!("expected {}, found the end of the query", expected),
+                span: self.length..self.length,
+            },
+        }
+    }
+
+    fn expect(&mut self, expected: &Token, description: &str) -> Result<(), JQLParseError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.unexpected(description))
+        }
+    }
+
+    fn at_order_by(&self) -> bool {
+        self.peek_keyword("order") && self.keyword_at(self.position + 1, "by")
+    }
+
+    fn statement(&mut self) -> Result<JQLStatement, JQLParseError> {
+        let clause = if self.peek().is_none() || self.at_order_by() {
+            JQLClause::And(vec![])
+        } else {
+            self.or()?
+        };
+
+        let order_by = if self.at_order_by() {
+            self.position += 2;
+            Some(self.order_by()?)
+        } else {
+            None
+        };
+
+        if self.peek().is_some() {
+            return Err(self.unexpected("AND, OR, ORDER BY or the end of the query"));
+        }
+
+        Ok(JQLStatement { clause, order_by })
+    }
+
+    fn or(&mut self) -> Result<JQLClause, JQLParseError> {
+        let mut clauses = vec![self.and()?];
+
+        while self.eat_keyword("or") || self.eat(&Token::Or) {
+            clauses.push(self.and()?);
+        }
+
+        Ok(group(clauses, JQLClause::Or))
+    }
+
+    fn and(&mut self) -> Result<JQLClause, JQLParseError> {
+        let mut clauses = vec![self.not()?];
+
+        while self.eat_keyword("and") || self.eat(&Token::And) {
+            clauses.push(self.not()?);
+        }
+
+        Ok(group(clauses, JQLClause::And))
+    }
+
+    fn not(&mut self) -> Result<JQLClause, JQLParseError> {
+        if self.eat_keyword("not") || self.eat(&Token::Bang) {
+            return Ok(JQLClause::Not(Box::new(self.not()?)));
+        }
+
+        if self.eat(&Token::LeftParen) {
+            let clause = self.or()?;
+            self.expect(&Token::RightParen, "\")\"")?;
+            return Ok(clause);
+        }
+
+        self.clause()
+    }
+
+    fn field(&mut self) -> Result<JQLField, JQLParseError> {
+        let field = match self.peek() {
+            Some(Token::Word(field)) => {
+                JQLField::from_clause_name(field).unwrap_or_else(|| JQLField::System(field.clone()))
+            }
+            Some(Token::Quoted(name)) => JQLField::Name(name.clone()),
+            _ => return Err(self.unexpected("a field")),
+        };
+        self.position += 1;
+
+        Ok(field)
+    }
+
+    fn clause(&mut self) -> Result<JQLClause, JQLParseError> {
+        let field = self.field()?;
+
+        let comparison = match self.peek() {
+            Some(Token::Equals) => Some(JQLClause::Equals as fn(JQLField, JQLValue) -> JQLClause),
+            Some(Token::NotEquals) => Some(JQLClause::NotEquals as _),
+            Some(Token::GreaterThan) => Some(JQLClause::GreaterThan as _),
+            Some(Token::GreaterThanEquals) => Some(JQLClause::GreaterThanEquals as _),
+            Some(Token::LessThan) => Some(JQLClause::LessThan as _),
+            Some(Token::LessThanEquals) => Some(JQLClause::LessThanEquals as _),
+            Some(Token::Contains) => Some(JQLClause::Contains as _),
+            Some(Token::NotContains) => Some(JQLClause::NotContains as _),
+            _ => None,
+        };
+        if let Some(comparison) = comparison {
+            self.position += 1;
+            return Ok(comparison(field, self.value()?));
+        }
+
+        if self.eat_keyword("in") {
+            return Ok(JQLClause::In(field, self.list()?));
+        }
+        if self.peek_keyword("not") && self.keyword_at(self.position + 1, "in") {
+            self.position += 2;
+            return Ok(JQLClause::NotIn(field, self.list()?));
+        }
+        if self.eat_keyword("is") {
+            let negated = self.eat_keyword("not");
+            let empty = if self.eat_keyword("empty") {
+                JQLEmpty::Empty
+            } else if self.eat_keyword("null") {
+                JQLEmpty::Null
+            } else {
+                return Err(self.unexpected("EMPTY or NULL"));
+            };
+            return Ok(if negated {
+                JQLClause::IsNot(field, empty)
+            } else {
+                JQLClause::Is(field, empty)
+            });
+        }
+        if self.eat_keyword("was") {
+            let negated = self.eat_keyword("not");
+            return Ok(if self.eat_keyword("in") {
+                let values = self.list()?;
+                let predicates = self.predicates()?;
+                if negated {
+                    JQLClause::WasNotIn(field, values, predicates)
+                } else {
+                    JQLClause::WasIn(field, values, predicates)
+                }
+            } else {
+                let value = self.value()?;
+                let predicates = self.predicates()?;
+                if negated {
+                    JQLClause::WasNot(field, value, predicates)
+                } else {
+                    JQLClause::Was(field, value, predicates)
+                }
+            });
+        }
+        if self.eat_keyword("changed") {
+            return Ok(JQLClause::Changed(field, self.predicates()?));
+        }
+
+        Err(self.unexpected("an operator"))
+    }
+
+    fn operand(&mut self) -> Result<Operand, JQLParseError> {
+        let operand = match self.peek() {
+            Some(Token::Quoted(contents)) => Operand::Quoted(contents.clone()),
+            Some(Token::Word(word)) => Operand::Word(word.clone()),
+            Some(Token::Placeholder(_)) => {
+                self.placeholder(false);
+                return Ok(Operand::Placeholder);
+            }
+            _ => return Err(self.unexpected("a value")),
+        };
+        self.position += 1;
+
+        let Operand::Word(name) = operand else {
+            return Ok(operand);
+        };
+        if !self.eat(&Token::LeftParen) {
+            return Ok(Operand::Word(name));
+        }
+
+        let mut arguments = vec![];
+        if !self.eat(&Token::RightParen) {
+            loop {
+                if let Some((Token::Placeholder(_), span)) = self.tokens.get(self.position) {
+                    return Err(JQLParseError {
+                        message: "values cannot be interpolated into function arguments".to_owned(),
+                        span: span.clone(),
+                    });
+                }
+                arguments.push(self.operand()?);
+                if self.eat(&Token::RightParen) {
+                    break;
+                }
+                self.expect(&Token::Comma, "\",\" or \")\"")?;
+            }
+        }
+
+        Ok(Operand::Function(name, arguments))
+    }
+
+    fn value(&mut self) -> Result<JQLValue, JQLParseError> {
+        Ok(self.operand()?.value())
+    }
+
+    /// Notes down the placeholder at the current position, and moves past it.
+    fn placeholder(&mut self, list: bool) {
+        if let Some((Token::Placeholder(name), span)) = self.tokens.get(self.position) {
+            self.placeholders.push(Placeholder {
+                name: name.clone(),
+                span: span.clone(),
+                list,
+            });
+            self.position += 1;
+        }
+    }
+
+    /// Reads a list of values in parentheses, or a single function which returns a list, like `openSprints()`.
+    fn list(&mut self) -> Result<Vec<JQLValue>, JQLParseError> {
+        if let Some(Token::Placeholder(_)) = self.peek() {
+            self.placeholder(true);
+            return Ok(vec![placeholder_value()]);
+        }
+
+        if !self.eat(&Token::LeftParen) {
+            let is_function = matches!(self.peek(), Some(Token::Word(_)))
+                && matches!(
+                    self.tokens.get(self.position + 1),
+                    Some((Token::LeftParen, _))
+                );
+
+            return if is_function {
+                Ok(vec![self.value()?])
+            } else {
+                Err(self.unexpected("a list of values or a function"))
+            };
+        }
+
+        let mut values = vec![];
+        if self.eat(&Token::RightParen) {
+            return Ok(values);
+        }
+        loop {
+            values.push(self.value()?);
+            if self.eat(&Token::RightParen) {
+                return Ok(values);
+            }
+            self.expect(&Token::Comma, "\",\" or \")\"")?;
+        }
+    }
+
+    fn predicates(&mut self) -> Result<Vec<JQLHistoryPredicate>, JQLParseError> {
+        let mut predicates = vec![];
+
+        loop {
+            let predicate = if self.eat_keyword("after") {
+                JQLHistoryPredicate::After(self.value()?)
+            } else if self.eat_keyword("before") {
+                JQLHistoryPredicate::Before(self.value()?)
+            } else if self.eat_keyword("on") {
+                JQLHistoryPredicate::On(self.value()?)
+            } else if self.eat_keyword("by") {
+                JQLHistoryPredicate::By(self.value()?)
+            } else if self.eat_keyword("from") {
+                JQLHistoryPredicate::From(self.value()?)
+            } else if self.eat_keyword("to") {
+                JQLHistoryPredicate::To(self.value()?)
+            } else if self.eat_keyword("during") {
+                self.expect(&Token::LeftParen, "\"(\"")?;
+                let start = self.value()?;
+                self.expect(&Token::Comma, "\",\"")?;
+                let end = self.value()?;
+                self.expect(&Token::RightParen, "\")\"")?;
+                JQLHistoryPredicate::During(start, end)
+            } else {
+                return Ok(predicates);
+            };
+            predicates.push(predicate);
+        }
+    }
+
+    fn order_by(&mut self) -> Result<JQLOrderBy, JQLParseError> {
+        let mut parts = vec![];
+
+        loop {
+            let field = self.field()?;
+            let ordering = if self.eat_keyword("asc") {
+                Some(JQLOrdering::Asc)
+            } else if self.eat_keyword("desc") {
+                Some(JQLOrdering::Desc)
+            } else {
+                None
+            };
+            parts.push(JQLOrderByPart { field, ordering });
+
+            if !self.eat(&Token::Comma) {
+                return Ok(JQLOrderBy(parts));
+            }
+        }
+    }
+}
+
+/// Joins clauses with `AND` or `OR`, unless there is only one.
+fn group(mut clauses: Vec<JQLClause>, join: fn(Vec<Box<JQLClause>>) -> JQLClause) -> JQLClause {
+    if clauses.len() == 1 {
+        clauses.remove(0)
+    } else {
+        join(clauses.into_iter().map(Box::new).collect())
+    }
+}
+
+pub(super) fn parse(jql: &str) -> Result<JQLStatement, JQLParseError> {
+    Parser {
+        tokens: tokenize(jql, false)?,
+        position: 0,
+        length: jql.len(),
+        placeholders: vec![],
+    }
+    .statement()
+}
+
+/// Parses a template for `jql!`, where values or lists of values can be left as `{name}` placeholders to fill in.
+///
+/// Placeholders are listed in the order they appear, which is the order `jql!` fills them in as it walks the statement.
+pub fn parse_template(jql: &str) -> Result<(JQLStatement, Vec<Placeholder>), JQLParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(jql, true)?,
+        position: 0,
+        length: jql.len(),
+        placeholders: vec![],
+    };
+    let statement = parser.statement()?;
+
+    Ok((statement, parser.placeholders))
+}
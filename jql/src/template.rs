@@ -0,0 +1,26 @@
+use chrono::NaiveDate;
+
+use crate::JQLValue;
+
+pub use crate::parse::{parse_template, placeholder_value, Placeholder};
+
+/// Converts the values given for a list placeholder.
+pub fn list<V: Into<JQLValue>>(values: impl IntoIterator<Item = V>) -> Vec<JQLValue> {
+    values.into_iter().map(Into::into).collect()
+}
+
+/// Builds a date which `jql!` has already read out of its query, and so knows is valid.
+pub fn date(year: i32, month: u32, day: u32) -> JQLValue {
+    JQLValue::NaiveDate(
+        NaiveDate::from_ymd_opt(year, month, day).expect("jql! checks dates when it is compiled"),
+    )
+}
+
+/// Builds a date and time, to the minute, which `jql!` has already read out of its query.
+pub fn datetime(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> JQLValue {
+    JQLValue::NaiveDateTime(
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .expect("jql! checks dates when it is compiled"),
+    )
+}
@@ -0,0 +1,1349 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{value::Value as JSONValue, Map};
+
+use crate::TextBody;
+
+mod markdown;
+mod wiki;
+
+/// The version of ADF documents this module reads and writes.  It is the only version there is so far.
+pub const ADF_VERSION: u32 = 1;
+
+/// Represents an [Atlassian Document Format][1] document, which V3 of the API uses for rich text like issue
+/// descriptions, environments and comment bodies.
+///
+/// Nodes this module does not have a type for, like media, are kept as `Node::Unknown`, so that documents from JIRA
+/// survive being read and written back unchanged.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::adf::{Document, PanelType};
+///
+/// let doc = Document::builder()
+///     .heading(2, |h| h.text("Release 1.2.0"))
+///     .paragraph(|p| p.text("Deployed by ").mention("5b10a2844c20165700ede21g", "@Jane").text(" to ").strong("production"))
+///     .bullet_list(|l| l.text_item("Fixed login").text_item("Faster search"))
+///     .code_block(Some("shell"), "cargo test")
+///     .panel(PanelType::Warning, |b| b.paragraph(|p| p.text("Rollback plan is ").link("here", "https://example.com/rollback")))
+///     .table(|t| t.header_row(&["Service", "Version"]).row(&["api", "1.2.0"]))
+///     .build();
+///
+/// assert!(doc.validate().is_ok());
+/// assert_eq!(
+///     doc.to_plain_text(),
+///     "Release 1.2.0\nDeployed by @Jane to production\nFixed login\nFaster search\ncargo test\nRollback plan is here\nService\tVersion\napi\t1.2.0"
+/// );
+///
+/// let json = serde_json::to_string(&doc).unwrap();
+/// assert_eq!(serde_json::from_str::<Document>(&json).unwrap(), doc);
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/apis/document/structure/
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename = "doc", from = "TaggedDocument")]
+pub struct Document {
+    pub version: u32,
+
+    pub content: Vec<Node>,
+}
+
+/// Reads a document, checking its `type`, which serde does not do for tagged structs.
+#[derive(Deserialize)]
+struct TaggedDocument {
+    #[serde(rename = "type")]
+    _tag: DocumentTag,
+
+    version: u32,
+
+    content: Vec<Node>,
+}
+
+#[derive(Deserialize)]
+enum DocumentTag {
+    #[serde(rename = "doc")]
+    Doc,
+}
+
+impl From<TaggedDocument> for Document {
+    fn from(document: TaggedDocument) -> Self {
+        Document {
+            version: document.version,
+            content: document.content,
+        }
+    }
+}
+
+/// Represents a node in an ADF document, other than the document itself.
+///
+/// Block nodes (paragraphs, lists, tables and so on) make up the structure of the document, and inline nodes (text,
+/// mentions and so on) fill in paragraphs and headings.
+///
+/// Attributes this module has no member for, like `localId`, are kept in `attrs` for nodes without an attrs type, and
+/// in the `extra` member of the attrs type otherwise, so that they are written back.  Marks on paragraphs, headings
+/// and code blocks, like `alignment` and `breakout`, are kept in their `marks` as `Mark::Unknown`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Node {
+    Paragraph {
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        attrs: Map<String, JSONValue>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        content: Vec<Node>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        marks: Vec<Mark>,
+    },
+    Heading {
+        attrs: HeadingAttrs,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        content: Vec<Node>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        marks: Vec<Mark>,
+    },
+    BulletList {
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        attrs: Map<String, JSONValue>,
+        content: Vec<Node>,
+    },
+    OrderedList {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<OrderedListAttrs>,
+        content: Vec<Node>,
+    },
+    ListItem {
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        attrs: Map<String, JSONValue>,
+        content: Vec<Node>,
+    },
+    CodeBlock {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<CodeBlockAttrs>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        content: Vec<Node>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        marks: Vec<Mark>,
+    },
+    Blockquote {
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        attrs: Map<String, JSONValue>,
+        content: Vec<Node>,
+    },
+    Panel {
+        attrs: PanelAttrs,
+        content: Vec<Node>,
+    },
+    Rule {
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        attrs: Map<String, JSONValue>,
+    },
+    Table {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<TableAttrs>,
+        content: Vec<Node>,
+    },
+    TableRow {
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        attrs: Map<String, JSONValue>,
+        content: Vec<Node>,
+    },
+    TableHeader {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<TableCellAttrs>,
+        content: Vec<Node>,
+    },
+    TableCell {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attrs: Option<TableCellAttrs>,
+        content: Vec<Node>,
+    },
+    TaskList {
+        attrs: TaskListAttrs,
+        content: Vec<Node>,
+    },
+    TaskItem {
+        attrs: TaskItemAttrs,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        content: Vec<Node>,
+    },
+    Text {
+        text: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        marks: Vec<Mark>,
+    },
+    HardBreak {
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        attrs: Map<String, JSONValue>,
+    },
+    Mention {
+        attrs: MentionAttrs,
+    },
+    Emoji {
+        attrs: EmojiAttrs,
+    },
+    InlineCard {
+        attrs: CardAttrs,
+    },
+    /// A node this module has no type for, or a node which does not have the shape ADF requires, kept as is.
+    #[serde(untagged)]
+    Unknown(JSONValue),
+}
+
+/// The names of the node types `Node` has a variant for, as they appear in the `type` key.
+const KNOWN_NODE_TYPES: &[&str] = &[
+    "paragraph",
+    "heading",
+    "bulletList",
+    "orderedList",
+    "listItem",
+    "codeBlock",
+    "blockquote",
+    "panel",
+    "rule",
+    "table",
+    "tableRow",
+    "tableHeader",
+    "tableCell",
+    "taskList",
+    "taskItem",
+    "text",
+    "hardBreak",
+    "mention",
+    "emoji",
+    "inlineCard",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HeadingAttrs {
+    /// From 1 to 6, like HTML's `h1` through `h6`.
+    pub level: u8,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OrderedListAttrs {
+    /// The number of the first item.
+    pub order: u32,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CodeBlockAttrs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PanelAttrs {
+    pub panel_type: PanelType,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PanelType {
+    Info,
+    Note,
+    Warning,
+    Success,
+    Error,
+    Custom,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableAttrs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_number_column_enabled: Option<bool>,
+
+    /// How the table is laid out on the page, like `"default"`, `"wide"` or `"full-width"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TableCellAttrs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colspan: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rowspan: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colwidth: Option<Vec<u32>>,
+
+    /// The background color, as a hex code like `"#deebff"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MentionAttrs {
+    /// The account ID of the user.
+    pub id: String,
+
+    /// What to show for the mention, like `"@Jane Smith"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_level: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiAttrs {
+    /// The name of the emoji, like `":grinning:"`.
+    pub short_name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The emoji itself, like `"😀"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskListAttrs {
+    /// An ID for the list which is unique within the document.
+    pub local_id: String,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskItemAttrs {
+    /// An ID for the item which is unique within the document.
+    pub local_id: String,
+
+    pub state: TaskState,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TaskState {
+    Todo,
+    Done,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CardAttrs {
+    pub url: String,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+/// Represents formatting applied to a text node.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Mark {
+    Code,
+    Em,
+    Link {
+        attrs: LinkAttrs,
+    },
+    Strike,
+    Strong,
+    Subsup {
+        attrs: SubsupAttrs,
+    },
+    TextColor {
+        attrs: TextColorAttrs,
+    },
+    Underline,
+    /// A mark this module has no type for, or a mark which does not have the shape ADF requires, kept as is.
+    #[serde(untagged)]
+    Unknown(JSONValue),
+}
+
+impl Mark {
+    /// Returns the name of the mark as it appears in the `type` key, if it has one.
+    pub fn type_name(&self) -> Option<&str> {
+        match self {
+            Mark::Code => Some("code"),
+            Mark::Em => Some("em"),
+            Mark::Link { .. } => Some("link"),
+            Mark::Strike => Some("strike"),
+            Mark::Strong => Some("strong"),
+            Mark::Subsup { .. } => Some("subsup"),
+            Mark::TextColor { .. } => Some("textColor"),
+            Mark::Underline => Some("underline"),
+            Mark::Unknown(value) => value.get("type").and_then(JSONValue::as_str),
+        }
+    }
+
+    fn is_known_type(mark_type: &str) -> bool {
+        matches!(
+            mark_type,
+            "code" | "em" | "link" | "strike" | "strong" | "subsup" | "textColor" | "underline"
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LinkAttrs {
+    pub href: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SubsupAttrs {
+    #[serde(rename = "type")]
+    pub subsup_type: SubsupType,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubsupType {
+    Sub,
+    Sup,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TextColorAttrs {
+    /// The color, as a hex code like `"#ff5630"`.
+    pub color: String,
+
+    #[serde(flatten)]
+    pub extra: Map<String, JSONValue>,
+}
+
+impl Node {
+    /// Returns the name of the node as it appears in the `type` key, if it has one.
+    pub fn type_name(&self) -> Option<&str> {
+        match self {
+            Node::Paragraph { .. } => Some("paragraph"),
+            Node::Heading { .. } => Some("heading"),
+            Node::BulletList { .. } => Some("bulletList"),
+            Node::OrderedList { .. } => Some("orderedList"),
+            Node::ListItem { .. } => Some("listItem"),
+            Node::CodeBlock { .. } => Some("codeBlock"),
+            Node::Blockquote { .. } => Some("blockquote"),
+            Node::Panel { .. } => Some("panel"),
+            Node::Rule { .. } => Some("rule"),
+            Node::Table { .. } => Some("table"),
+            Node::TableRow { .. } => Some("tableRow"),
+            Node::TableHeader { .. } => Some("tableHeader"),
+            Node::TableCell { .. } => Some("tableCell"),
+            Node::TaskList { .. } => Some("taskList"),
+            Node::TaskItem { .. } => Some("taskItem"),
+            Node::Text { .. } => Some("text"),
+            Node::HardBreak { .. } => Some("hardBreak"),
+            Node::Mention { .. } => Some("mention"),
+            Node::Emoji { .. } => Some("emoji"),
+            Node::InlineCard { .. } => Some("inlineCard"),
+            Node::Unknown(value) => value.get("type").and_then(JSONValue::as_str),
+        }
+    }
+
+    /// Returns the child nodes, which is empty for nodes that cannot have children.
+    pub fn content(&self) -> &[Node] {
+        match self {
+            Node::Paragraph { content, .. }
+            | Node::Heading { content, .. }
+            | Node::BulletList { content, .. }
+            | Node::OrderedList { content, .. }
+            | Node::ListItem { content, .. }
+            | Node::CodeBlock { content, .. }
+            | Node::Blockquote { content, .. }
+            | Node::Panel { content, .. }
+            | Node::Table { content, .. }
+            | Node::TableRow { content, .. }
+            | Node::TableHeader { content, .. }
+            | Node::TableCell { content, .. }
+            | Node::TaskList { content, .. }
+            | Node::TaskItem { content, .. } => content,
+            Node::Rule { .. }
+            | Node::Text { .. }
+            | Node::HardBreak { .. }
+            | Node::Mention { .. }
+            | Node::Emoji { .. }
+            | Node::InlineCard { .. }
+            | Node::Unknown(_) => &[],
+        }
+    }
+
+    /// Returns whether this is an inline node, which goes inside paragraphs and headings.
+    pub fn is_inline(&self) -> bool {
+        matches!(
+            self,
+            Node::Text { .. }
+                | Node::HardBreak { .. }
+                | Node::Mention { .. }
+                | Node::Emoji { .. }
+                | Node::InlineCard { .. }
+        )
+    }
+
+    /// Creates a text node without any marks.
+    pub fn text(text: &str) -> Self {
+        Node::Text {
+            text: text.to_owned(),
+            marks: vec![],
+        }
+    }
+
+    /// Creates a paragraph holding a single text node.
+    pub fn text_paragraph(text: &str) -> Self {
+        Node::Paragraph {
+            attrs: Map::new(),
+            content: vec![Node::text(text)],
+            marks: vec![],
+        }
+    }
+}
+
+impl Document {
+    pub fn new(content: Vec<Node>) -> Self {
+        Document {
+            version: ADF_VERSION,
+            content,
+        }
+    }
+
+    /// Starts building a document; see `BlockBuilder`.
+    pub fn builder() -> BlockBuilder {
+        BlockBuilder::default()
+    }
+
+    /// Returns the text of the document, without any formatting.
+    ///
+    /// Each block, like a paragraph or a list item, goes on its own line, and table cells are separated by tabs.
+    /// Mentions, emoji and cards are replaced with what a reader would see.
+    pub fn to_plain_text(&self) -> String {
+        blocks_to_plain_text(&self.content)
+    }
+
+    /// Checks the document against the rules of the [ADF schema][1] for the nodes this module has types for.
+    ///
+    /// Nodes this module has no type for are not checked, but nodes which were not in the shape ADF requires (and so
+    /// were read as `Node::Unknown`) are reported.  JIRA rejects documents which break these rules.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use jimberlage_jira_client::adf::{Document, HeadingAttrs, Node};
+    /// use serde_json::Map;
+    ///
+    /// let doc = Document::new(vec![
+    ///     Node::text("not in a paragraph"),
+    ///     Node::Heading { attrs: HeadingAttrs { level: 7, extra: Map::new() }, content: vec![], marks: vec![] },
+    /// ]);
+    /// let errors = doc.validate().unwrap_err();
+    ///
+    /// assert_eq!(errors[0].to_string(), "content[0]: text is not allowed in doc".to_owned());
+    /// assert_eq!(errors[1].to_string(), "content[1]: heading level must be from 1 to 6, not 7".to_owned());
+    /// ```
+    ///
+    /// [1]: https://unpkg.com/@atlaskit/adf-schema@latest/dist/json-schema/v1/full.json
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+
+        if self.version != ADF_VERSION {
+            errors.push(ValidationError::new(
+                "version",
+                format!("version must be {}, not {}", ADF_VERSION, self.version),
+            ));
+        }
+        validate_children("doc", &self.content, "content", &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl From<Document> for TextBody {
+    fn from(document: Document) -> Self {
+        // Unwrap here is considered safe since documents only contain strings, numbers and string-keyed maps, which
+        // always serialize.
+        TextBody::Adf(serde_json::to_value(document).unwrap())
+    }
+}
+
+impl TextBody {
+    /// Returns the ADF document, if this is one.
+    pub fn to_adf(&self) -> Option<Document> {
+        match self {
+            TextBody::Adf(value) => serde_json::from_value(value.clone()).ok(),
+            TextBody::WikiMarkup(_) => None,
+        }
+    }
+}
+
+/// Represents a way in which a document breaks the rules of the ADF schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Where the problem is, like `"content[2].content[0]"`.
+    pub path: String,
+
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(path: &str, message: String) -> Self {
+        ValidationError {
+            path: path.to_owned(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+const INLINE_NODES: &[&str] = &["text", "hardBreak", "mention", "emoji", "inlineCard"];
+
+/// Returns the node types allowed as children of the parent node type, or `None` if it has no restrictions we check.
+fn allowed_children(parent: &str) -> Option<&'static [&'static str]> {
+    Some(match parent {
+        "doc" => &[
+            "paragraph",
+            "heading",
+            "bulletList",
+            "orderedList",
+            "codeBlock",
+            "blockquote",
+            "panel",
+            "rule",
+            "table",
+            "taskList",
+        ],
+        "paragraph" | "heading" | "taskItem" => INLINE_NODES,
+        "bulletList" | "orderedList" => &["listItem"],
+        "listItem" => &["paragraph", "bulletList", "orderedList", "codeBlock"],
+        "codeBlock" => &["text"],
+        "blockquote" => &["paragraph", "bulletList", "orderedList", "codeBlock"],
+        "panel" => &[
+            "paragraph",
+            "heading",
+            "bulletList",
+            "orderedList",
+            "codeBlock",
+            "taskList",
+        ],
+        "table" => &["tableRow"],
+        "tableRow" => &["tableHeader", "tableCell"],
+        "tableHeader" | "tableCell" => &[
+            "paragraph",
+            "heading",
+            "bulletList",
+            "orderedList",
+            "codeBlock",
+            "blockquote",
+            "panel",
+            "rule",
+            "taskList",
+        ],
+        // Nested task lists sit beside the item they belong under, rather than inside it.
+        "taskList" => &["taskItem", "taskList"],
+        _ => return None,
+    })
+}
+
+/// Returns whether the node type must have at least one child.
+fn requires_children(node_type: &str) -> bool {
+    matches!(
+        node_type,
+        "bulletList"
+            | "orderedList"
+            | "listItem"
+            | "blockquote"
+            | "panel"
+            | "table"
+            | "tableRow"
+            | "tableHeader"
+            | "tableCell"
+            | "taskList"
+    )
+}
+
+fn validate_children(
+    parent: &str,
+    children: &[Node],
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    if children.is_empty() && requires_children(parent) {
+        errors.push(ValidationError::new(
+            path,
+            format!("{} must have content", parent),
+        ));
+    }
+
+    for (i, child) in children.iter().enumerate() {
+        let child_path = format!("{}[{}]", path, i);
+
+        if let (Some(allowed), Some(child_type)) = (allowed_children(parent), child.type_name()) {
+            if KNOWN_NODE_TYPES.contains(&child_type) && !allowed.contains(&child_type) {
+                errors.push(ValidationError::new(
+                    &child_path,
+                    format!("{} is not allowed in {}", child_type, parent),
+                ));
+            }
+        }
+
+        validate_node(parent, i, child, &child_path, errors);
+    }
+}
+
+fn validate_node(
+    parent: &str,
+    index: usize,
+    node: &Node,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    match node {
+        Node::Heading { attrs, .. } if !(1..=6).contains(&attrs.level) => {
+            errors.push(ValidationError::new(
+                path,
+                format!("heading level must be from 1 to 6, not {}", attrs.level),
+            ));
+        }
+        Node::BulletList { .. } | Node::OrderedList { .. }
+            if parent == "listItem" && index == 0 =>
+        {
+            errors.push(ValidationError::new(
+                path,
+                "a list item cannot start with a nested list".to_owned(),
+            ));
+        }
+        Node::TaskList { .. } if parent == "taskList" && index == 0 => {
+            errors.push(ValidationError::new(
+                path,
+                "a task list cannot start with a nested task list".to_owned(),
+            ));
+        }
+        Node::Text { text, marks } => {
+            if text.is_empty() {
+                errors.push(ValidationError::new(
+                    path,
+                    "text must not be empty".to_owned(),
+                ));
+            }
+            if parent == "codeBlock" && !marks.is_empty() {
+                errors.push(ValidationError::new(
+                    path,
+                    "text in a code block cannot have marks".to_owned(),
+                ));
+            }
+            validate_marks(marks, path, errors);
+        }
+        Node::Mention { attrs } if attrs.id.is_empty() => {
+            errors.push(ValidationError::new(
+                path,
+                "mention must have an id".to_owned(),
+            ));
+        }
+        Node::InlineCard { attrs } if attrs.url.is_empty() => {
+            errors.push(ValidationError::new(
+                path,
+                "inline card must have a url".to_owned(),
+            ));
+        }
+        Node::Unknown(_) => {
+            if let Some(node_type) = node.type_name() {
+                if KNOWN_NODE_TYPES.contains(&node_type) {
+                    errors.push(ValidationError::new(
+                        path,
+                        format!("{} is missing required attributes or content", node_type),
+                    ));
+                }
+            }
+        }
+        _ => (),
+    }
+
+    if let Some(node_type) = node.type_name() {
+        if !matches!(node, Node::Unknown(_)) {
+            validate_children(
+                node_type,
+                node.content(),
+                &format!("{}.content", path),
+                errors,
+            );
+        }
+    }
+}
+
+fn validate_marks(marks: &[Mark], path: &str, errors: &mut Vec<ValidationError>) {
+    let mut seen = vec![];
+
+    for mark in marks {
+        let mark_type = mark.type_name().unwrap_or("unknown");
+
+        if seen.contains(&mark_type) {
+            errors.push(ValidationError::new(
+                path,
+                format!("{} mark is applied more than once", mark_type),
+            ));
+        }
+        seen.push(mark_type);
+
+        match mark {
+            Mark::Link { attrs } if attrs.href.is_empty() => {
+                errors.push(ValidationError::new(
+                    path,
+                    "link mark must have an href".to_owned(),
+                ));
+            }
+            Mark::Unknown(_) if Mark::is_known_type(mark_type) => {
+                errors.push(ValidationError::new(
+                    path,
+                    format!("{} mark is missing required attributes", mark_type),
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    // Code can only be combined with links; other formatting inside code is not supported by ADF.
+    if seen.contains(&"code")
+        && seen
+            .iter()
+            .any(|mark_type| *mark_type != "code" && *mark_type != "link")
+    {
+        errors.push(ValidationError::new(
+            path,
+            "code mark can only be combined with link".to_owned(),
+        ));
+    }
+}
+
+fn blocks_to_plain_text(blocks: &[Node]) -> String {
+    blocks
+        .iter()
+        .map(block_to_plain_text)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn block_to_plain_text(block: &Node) -> String {
+    match block {
+        Node::Paragraph { content, .. }
+        | Node::Heading { content, .. }
+        | Node::CodeBlock { content, .. }
+        | Node::TaskItem { content, .. } => inlines_to_plain_text(content),
+        Node::Table { content, .. } => content
+            .iter()
+            .map(|row| {
+                row.content()
+                    .iter()
+                    .map(|cell| blocks_to_plain_text(cell.content()))
+                    .collect::<Vec<String>>()
+                    .join("\t")
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Node::Rule { .. } => String::new(),
+        Node::Unknown(value) => unknown_to_plain_text(value),
+        node if node.is_inline() => inlines_to_plain_text(std::slice::from_ref(node)),
+        node => blocks_to_plain_text(node.content()),
+    }
+}
+
+fn inlines_to_plain_text(inlines: &[Node]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Node::Text { text, .. } => text.clone(),
+            Node::HardBreak { .. } => "\n".to_owned(),
+            Node::Mention { attrs } => attrs.text.clone().unwrap_or_else(|| attrs.id.clone()),
+            Node::Emoji { attrs } => attrs
+                .text
+                .clone()
+                .unwrap_or_else(|| attrs.short_name.clone()),
+            Node::InlineCard { attrs } => attrs.url.clone(),
+            Node::Unknown(value) => unknown_to_plain_text(value),
+            block => block_to_plain_text(block),
+        })
+        .collect()
+}
+
+/// Creates a list item from blocks, fitting them to what a list item allows and making sure it starts with a
+/// paragraph.
+fn list_item(blocks: Vec<Node>) -> Node {
+    let mut content = fit_content("listItem", blocks);
+    if !matches!(content.first(), Some(Node::Paragraph { .. })) {
+        content.insert(
+            0,
+            Node::Paragraph {
+                attrs: Map::new(),
+                content: vec![],
+                marks: vec![],
+            },
+        );
+    }
+
+    Node::ListItem {
+        attrs: Map::new(),
+        content,
+    }
+}
+
+/// Changes blocks which ADF does not allow in the parent into ones it does.
+///
+/// Headings become paragraphs, blockquotes are unwrapped, and anything else becomes paragraphs of its text.
+fn fit_content(parent: &str, blocks: Vec<Node>) -> Vec<Node> {
+    let allowed = match allowed_children(parent) {
+        Some(allowed) => allowed,
+        None => return blocks,
+    };
+    let mut content = vec![];
+
+    for block in blocks {
+        if block
+            .type_name()
+            .is_none_or(|block_type| allowed.contains(&block_type))
+        {
+            content.push(block);
+            continue;
+        }
+
+        match block {
+            Node::Heading {
+                content: inlines, ..
+            } if allowed.contains(&"paragraph") => content.push(Node::Paragraph {
+                attrs: Map::new(),
+                content: inlines,
+                marks: vec![],
+            }),
+            Node::Blockquote { content: inner, .. } => content.extend(fit_content(parent, inner)),
+            Node::TaskList { content: items, .. } if allowed.contains(&"bulletList") => {
+                content.push(checkbox_list(items))
+            }
+            Node::Rule { .. } => (),
+            block => content.extend(
+                blocks_to_plain_text(std::slice::from_ref(&block))
+                    .lines()
+                    .filter(|line| !line.is_empty())
+                    .map(Node::text_paragraph),
+            ),
+        }
+    }
+
+    content
+}
+
+/// Turns a task list into a bullet list whose items start with `[ ]` or `[x]`, for where task lists are not allowed.
+fn checkbox_list(items: Vec<Node>) -> Node {
+    let mut content: Vec<Node> = vec![];
+
+    for item in items {
+        match item {
+            Node::TaskItem {
+                attrs,
+                content: inlines,
+            } => {
+                let mut paragraph = inlines;
+                prepend_text(
+                    &mut paragraph,
+                    match attrs.state {
+                        TaskState::Done => "[x] ",
+                        TaskState::Todo => "[ ] ",
+                    },
+                );
+                content.push(Node::ListItem {
+                    attrs: Map::new(),
+                    content: vec![Node::Paragraph {
+                        attrs: Map::new(),
+                        content: paragraph,
+                        marks: vec![],
+                    }],
+                });
+            }
+            Node::TaskList {
+                content: nested, ..
+            } => {
+                let nested = checkbox_list(nested);
+                match content.last_mut() {
+                    Some(Node::ListItem { content, .. }) => content.push(nested),
+                    _ => content.push(Node::ListItem {
+                        attrs: Map::new(),
+                        content: vec![
+                            Node::Paragraph {
+                                attrs: Map::new(),
+                                content: vec![],
+                                marks: vec![],
+                            },
+                            nested,
+                        ],
+                    }),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Node::BulletList {
+        attrs: Map::new(),
+        content,
+    }
+}
+
+/// Adds text to the start of inline content, joining it onto the first text if that has no marks.
+fn prepend_text(inlines: &mut Vec<Node>, text: &str) {
+    match inlines.first_mut() {
+        Some(Node::Text { text: first, marks }) if marks.is_empty() => first.insert_str(0, text),
+        _ => inlines.insert(0, Node::text(text)),
+    }
+}
+
+/// Pulls the text out of a node we have no type for, by collecting every `text` string inside it.  Nodes like `status`
+/// keep their text in their attributes instead.
+fn unknown_to_plain_text(value: &JSONValue) -> String {
+    let mut text = String::new();
+
+    if let Some(s) = value
+        .get("text")
+        .or_else(|| value.get("attrs")?.get("text"))
+        .and_then(JSONValue::as_str)
+    {
+        text.push_str(s);
+    }
+    if let Some(JSONValue::Array(content)) = value.get("content") {
+        for child in content {
+            text.push_str(&unknown_to_plain_text(child));
+        }
+    }
+
+    text
+}
+
+/// Builds a list of block nodes, like the content of a document, list item or panel.
+///
+/// Each method adds a block to the end, and nested content is built with a closure.
+#[derive(Clone, Debug, Default)]
+pub struct BlockBuilder {
+    content: Vec<Node>,
+}
+
+impl BlockBuilder {
+    /// Adds any node, for nodes without a dedicated method.
+    pub fn node(mut self, node: Node) -> Self {
+        self.content.push(node);
+        self
+    }
+
+    pub fn paragraph(self, build: impl FnOnce(InlineBuilder) -> InlineBuilder) -> Self {
+        self.node(Node::Paragraph {
+            attrs: Map::new(),
+            content: build(InlineBuilder::default()).content,
+            marks: vec![],
+        })
+    }
+
+    /// Adds a heading, with a level from 1 to 6.
+    pub fn heading(self, level: u8, build: impl FnOnce(InlineBuilder) -> InlineBuilder) -> Self {
+        self.node(Node::Heading {
+            attrs: HeadingAttrs {
+                level,
+                extra: Map::new(),
+            },
+            content: build(InlineBuilder::default()).content,
+            marks: vec![],
+        })
+    }
+
+    pub fn bullet_list(self, build: impl FnOnce(ListBuilder) -> ListBuilder) -> Self {
+        self.node(Node::BulletList {
+            attrs: Map::new(),
+            content: build(ListBuilder::default()).items,
+        })
+    }
+
+    pub fn ordered_list(self, build: impl FnOnce(ListBuilder) -> ListBuilder) -> Self {
+        self.node(Node::OrderedList {
+            attrs: None,
+            content: build(ListBuilder::default()).items,
+        })
+    }
+
+    pub fn code_block(self, language: Option<&str>, code: &str) -> Self {
+        self.node(Node::CodeBlock {
+            attrs: language.map(|language| CodeBlockAttrs {
+                language: Some(language.to_owned()),
+                extra: Map::new(),
+            }),
+            // Empty text nodes are not allowed, so an empty code block has no content at all.
+            content: if code.is_empty() {
+                vec![]
+            } else {
+                vec![Node::text(code)]
+            },
+            marks: vec![],
+        })
+    }
+
+    pub fn blockquote(self, build: impl FnOnce(BlockBuilder) -> BlockBuilder) -> Self {
+        self.node(Node::Blockquote {
+            attrs: Map::new(),
+            content: build(BlockBuilder::default()).content,
+        })
+    }
+
+    pub fn panel(
+        self,
+        panel_type: PanelType,
+        build: impl FnOnce(BlockBuilder) -> BlockBuilder,
+    ) -> Self {
+        self.node(Node::Panel {
+            attrs: PanelAttrs {
+                panel_type,
+                extra: Map::new(),
+            },
+            content: build(BlockBuilder::default()).content,
+        })
+    }
+
+    pub fn rule(self) -> Self {
+        self.node(Node::Rule { attrs: Map::new() })
+    }
+
+    pub fn table(self, build: impl FnOnce(TableBuilder) -> TableBuilder) -> Self {
+        self.node(Node::Table {
+            attrs: None,
+            content: build(TableBuilder::default()).rows,
+        })
+    }
+
+    /// Finishes the document.
+    pub fn build(self) -> Document {
+        Document::new(self.content)
+    }
+
+    /// Returns the blocks built so far, for use as the content of a node built by hand.
+    pub fn into_content(self) -> Vec<Node> {
+        self.content
+    }
+}
+
+/// Builds the inline content of a paragraph or heading.
+#[derive(Clone, Debug, Default)]
+pub struct InlineBuilder {
+    content: Vec<Node>,
+}
+
+impl InlineBuilder {
+    /// Adds any node, for nodes without a dedicated method.
+    pub fn node(mut self, node: Node) -> Self {
+        self.content.push(node);
+        self
+    }
+
+    pub fn text(self, text: &str) -> Self {
+        self.marked(text, vec![])
+    }
+
+    /// Adds text with any combination of marks.  Empty text is left out, since JIRA rejects empty text nodes.
+    pub fn marked(self, text: &str, marks: Vec<Mark>) -> Self {
+        if text.is_empty() {
+            return self;
+        }
+
+        self.node(Node::Text {
+            text: text.to_owned(),
+            marks,
+        })
+    }
+
+    pub fn strong(self, text: &str) -> Self {
+        self.marked(text, vec![Mark::Strong])
+    }
+
+    pub fn em(self, text: &str) -> Self {
+        self.marked(text, vec![Mark::Em])
+    }
+
+    pub fn code(self, text: &str) -> Self {
+        self.marked(text, vec![Mark::Code])
+    }
+
+    pub fn strike(self, text: &str) -> Self {
+        self.marked(text, vec![Mark::Strike])
+    }
+
+    pub fn underline(self, text: &str) -> Self {
+        self.marked(text, vec![Mark::Underline])
+    }
+
+    pub fn link(self, text: &str, href: &str) -> Self {
+        self.marked(
+            text,
+            vec![Mark::Link {
+                attrs: LinkAttrs {
+                    href: href.to_owned(),
+                    title: None,
+                    extra: Map::new(),
+                },
+            }],
+        )
+    }
+
+    /// Mentions the user with the account ID, showing the text, like `"@Jane Smith"`.
+    pub fn mention(self, account_id: &str, text: &str) -> Self {
+        self.node(Node::Mention {
+            attrs: MentionAttrs {
+                id: account_id.to_owned(),
+                text: Some(text.to_owned()),
+                access_level: None,
+                extra: Map::new(),
+            },
+        })
+    }
+
+    /// Adds an emoji by its name, like `":grinning:"`.
+    pub fn emoji(self, short_name: &str) -> Self {
+        self.node(Node::Emoji {
+            attrs: EmojiAttrs {
+                short_name: short_name.to_owned(),
+                id: None,
+                text: None,
+                extra: Map::new(),
+            },
+        })
+    }
+
+    /// Adds a link which JIRA renders as a card, like a link to another issue.
+    pub fn inline_card(self, url: &str) -> Self {
+        self.node(Node::InlineCard {
+            attrs: CardAttrs {
+                url: url.to_owned(),
+                extra: Map::new(),
+            },
+        })
+    }
+
+    pub fn hard_break(self) -> Self {
+        self.node(Node::HardBreak { attrs: Map::new() })
+    }
+}
+
+/// Builds the items of a bullet or ordered list.
+#[derive(Clone, Debug, Default)]
+pub struct ListBuilder {
+    items: Vec<Node>,
+}
+
+impl ListBuilder {
+    /// Adds an item, whose content starts with a paragraph and may have nested lists after it.
+    pub fn item(mut self, build: impl FnOnce(BlockBuilder) -> BlockBuilder) -> Self {
+        self.items.push(Node::ListItem {
+            attrs: Map::new(),
+            content: build(BlockBuilder::default()).content,
+        });
+        self
+    }
+
+    /// Adds an item holding a single paragraph of text.
+    pub fn text_item(self, text: &str) -> Self {
+        self.item(|b| b.paragraph(|p| p.text(text)))
+    }
+}
+
+/// Builds the rows of a table.
+#[derive(Clone, Debug, Default)]
+pub struct TableBuilder {
+    rows: Vec<Node>,
+}
+
+impl TableBuilder {
+    pub fn row_with(mut self, build: impl FnOnce(RowBuilder) -> RowBuilder) -> Self {
+        self.rows.push(Node::TableRow {
+            attrs: Map::new(),
+            content: build(RowBuilder::default()).cells,
+        });
+        self
+    }
+
+    /// Adds a row of header cells, each holding a paragraph of text.
+    pub fn header_row(self, headers: &[&str]) -> Self {
+        self.row_with(|r| {
+            headers
+                .iter()
+                .fold(r, |r, header| r.header(|b| b.paragraph(|p| p.text(header))))
+        })
+    }
+
+    /// Adds a row of cells, each holding a paragraph of text.
+    pub fn row(self, cells: &[&str]) -> Self {
+        self.row_with(|r| {
+            cells
+                .iter()
+                .fold(r, |r, cell| r.cell(|b| b.paragraph(|p| p.text(cell))))
+        })
+    }
+}
+
+/// Builds the cells of a table row.
+#[derive(Clone, Debug, Default)]
+pub struct RowBuilder {
+    cells: Vec<Node>,
+}
+
+impl RowBuilder {
+    pub fn header(mut self, build: impl FnOnce(BlockBuilder) -> BlockBuilder) -> Self {
+        self.cells.push(Node::TableHeader {
+            attrs: None,
+            content: build(BlockBuilder::default()).content,
+        });
+        self
+    }
+
+    pub fn cell(mut self, build: impl FnOnce(BlockBuilder) -> BlockBuilder) -> Self {
+        self.cells.push(Node::TableCell {
+            attrs: None,
+            content: build(BlockBuilder::default()).content,
+        });
+        self
+    }
+}
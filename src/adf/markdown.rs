@@ -0,0 +1,981 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use serde_json::Map;
+
+use super::{
+    blocks_to_plain_text, fit_content, inlines_to_plain_text, list_item, prepend_text,
+    unknown_to_plain_text, Document, LinkAttrs, Mark, Node, OrderedListAttrs, TaskItemAttrs,
+    TaskListAttrs, TaskState,
+};
+
+impl Document {
+    /// Converts [CommonMark][1], with GitHub's [tables][2], [task lists][3] and strikethrough, into a document.
+    ///
+    /// Markdown can express things ADF cannot, so some of it is changed on the way in:
+    ///
+    /// - Images become links to the image, since ADF only holds media uploaded to Atlassian.
+    /// - Raw HTML is kept as literal text, except for `<br>`, which becomes a line break.
+    /// - Formatting inside code spans is dropped, since ADF only allows code to be a link as well.
+    /// - Blocks which ADF does not allow where they are, like a heading in a list item or a table in a blockquote,
+    ///   become paragraphs of their text.
+    /// - Task lists stay task lists only when every item in the list has a checkbox; otherwise they are bullet lists
+    ///   whose items start with `[ ]` or `[x]`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use jimberlage_jira_client::IssueCreateRequest;
+    /// use jimberlage_jira_client::adf::Document;
+    ///
+    /// let description = Document::from_markdown("Rotate the **staging** certificates.\n\n- [x] api\n- [ ] web\n");
+    ///
+    /// assert!(description.validate().is_ok());
+    /// assert_eq!(description.to_markdown(), "Rotate the **staging** certificates.\n\n- [x] api\n- [ ] web\n");
+    ///
+    /// let request = IssueCreateRequest::new("SRE", "Task", "Rotate certificates").description(description.into());
+    /// ```
+    ///
+    /// [1]: https://spec.commonmark.org/
+    /// [2]: https://github.github.com/gfm/#tables-extension-
+    /// [3]: https://github.github.com/gfm/#task-list-items-extension-
+    pub fn from_markdown(markdown: &str) -> Self {
+        let mut converter = Converter::default();
+
+        for event in Parser::new_ext(markdown, options()) {
+            converter.event(event);
+        }
+
+        converter.finish()
+    }
+
+    /// Converts the document into [CommonMark][1], using GitHub's [tables][2], [task lists][3] and strikethrough
+    /// where the document has them.
+    ///
+    /// ADF can express things Markdown cannot, so some of it is changed on the way out:
+    ///
+    /// - Underline, text color, subscript and superscript are dropped, keeping the text.
+    /// - Panels become blockquotes.
+    /// - Mentions become their display text (like `@Jane Smith`), emoji become the emoji itself or its short name,
+    ///   and inline cards become plain links.
+    /// - Table cells can only hold a line of text in Markdown, so blocks within a cell are separated by `<br>`, and
+    ///   the first row is always the header row.
+    /// - Nodes this module has no type for, like media, become their plain text, if they have any.
+    ///
+    /// [1]: https://spec.commonmark.org/
+    /// [2]: https://github.github.com/gfm/#tables-extension-
+    /// [3]: https://github.github.com/gfm/#task-list-items-extension-
+    pub fn to_markdown(&self) -> String {
+        let markdown = render_blocks(&self.content);
+
+        if markdown.is_empty() {
+            markdown
+        } else {
+            markdown + "\n"
+        }
+    }
+}
+
+fn options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH
+}
+
+/// A Markdown element which has been started but not yet ended.
+enum Container {
+    Document,
+    Paragraph,
+    Heading(u8),
+    BlockQuote,
+    CodeBlock(Option<String>),
+    HtmlBlock,
+    List(Option<u64>),
+    /// A list item, and whether it has a checked (`Some(true)`) or unchecked (`Some(false)`) checkbox.
+    Item(Option<bool>),
+    Table,
+    TableHead,
+    TableRow,
+    TableCell,
+    Image(String),
+    /// Any element we do not enable in the parser; its content is passed up to its parent.
+    Other,
+}
+
+struct Open {
+    container: Container,
+
+    blocks: Vec<Node>,
+
+    /// Inline content not yet wrapped in a block.  Tight list items and table cells have inline content without a
+    /// paragraph around it.
+    inlines: Vec<Node>,
+
+    /// The raw text of code and HTML blocks.
+    text: String,
+
+    /// The checkbox of each item, for lists.
+    tasks: Vec<Option<bool>>,
+}
+
+impl Open {
+    fn new(container: Container) -> Self {
+        Open {
+            container,
+            blocks: vec![],
+            inlines: vec![],
+            text: String::new(),
+            tasks: vec![],
+        }
+    }
+
+    /// Returns the blocks of the element, wrapping any loose inline content in a paragraph.
+    fn into_blocks(mut self) -> Vec<Node> {
+        self.flush_inlines();
+        self.blocks
+    }
+
+    fn flush_inlines(&mut self) {
+        if !self.inlines.is_empty() {
+            let content = std::mem::take(&mut self.inlines);
+            self.blocks.push(Node::Paragraph {
+                attrs: Map::new(),
+                content,
+                marks: vec![],
+            });
+        }
+    }
+}
+
+struct Converter {
+    stack: Vec<Open>,
+
+    /// The marks on the text being read, from the outermost to the innermost.
+    marks: Vec<Mark>,
+
+    last_local_id: u64,
+}
+
+impl Default for Converter {
+    fn default() -> Self {
+        Converter {
+            stack: vec![Open::new(Container::Document)],
+            marks: vec![],
+            last_local_id: 0,
+        }
+    }
+}
+
+impl Converter {
+    fn top(&mut self) -> &mut Open {
+        // Unwrap here is considered safe since the document is only popped off the stack in finish.
+        self.stack.last_mut().unwrap()
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match self.top().container {
+                Container::CodeBlock(_) | Container::HtmlBlock => self.top().text.push_str(&text),
+                _ => self.add_text(&text, self.marks.clone()),
+            },
+            Event::Code(code) => {
+                // Code can only be combined with links in ADF.
+                let mut marks: Vec<Mark> = self
+                    .marks
+                    .iter()
+                    .filter(|mark| matches!(mark, Mark::Link { .. }))
+                    .cloned()
+                    .collect();
+                marks.push(Mark::Code);
+                self.add_text(&code, marks);
+            }
+            Event::Html(html) => match self.top().container {
+                Container::HtmlBlock => self.top().text.push_str(&html),
+                _ => self.add_text(&html, self.marks.clone()),
+            },
+            Event::InlineHtml(html) => {
+                if is_line_break(&html) {
+                    self.add_inline(Node::HardBreak { attrs: Map::new() });
+                } else {
+                    self.add_text(&html, self.marks.clone());
+                }
+            }
+            Event::InlineMath(text) | Event::DisplayMath(text) => {
+                self.add_text(&text, self.marks.clone())
+            }
+            Event::FootnoteReference(label) => {
+                self.add_text(&format!("[^{}]", label), self.marks.clone())
+            }
+            Event::SoftBreak => self.add_text(" ", self.marks.clone()),
+            Event::HardBreak => self.add_inline(Node::HardBreak { attrs: Map::new() }),
+            Event::Rule => self.add_block(Node::Rule { attrs: Map::new() }),
+            Event::TaskListMarker(checked) => {
+                if let Some(Open {
+                    container: Container::Item(task),
+                    ..
+                }) = self
+                    .stack
+                    .iter_mut()
+                    .rev()
+                    .find(|open| matches!(open.container, Container::Item(_)))
+                {
+                    *task = Some(checked);
+                }
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        let container = match tag {
+            Tag::Paragraph => Container::Paragraph,
+            Tag::Heading { level, .. } => Container::Heading(heading_level(level)),
+            Tag::BlockQuote(_) => Container::BlockQuote,
+            Tag::CodeBlock(CodeBlockKind::Fenced(info)) => Container::CodeBlock(
+                info.split_whitespace()
+                    .next()
+                    .map(|language| language.to_owned()),
+            ),
+            Tag::CodeBlock(CodeBlockKind::Indented) => Container::CodeBlock(None),
+            Tag::HtmlBlock => Container::HtmlBlock,
+            Tag::List(start) => Container::List(start),
+            Tag::Item => Container::Item(None),
+            Tag::Table(_) => Container::Table,
+            Tag::TableHead => Container::TableHead,
+            Tag::TableRow => Container::TableRow,
+            Tag::TableCell => Container::TableCell,
+            Tag::Image { dest_url, .. } => Container::Image(dest_url.to_string()),
+            Tag::Emphasis => return self.marks.push(Mark::Em),
+            Tag::Strong => return self.marks.push(Mark::Strong),
+            Tag::Strikethrough => return self.marks.push(Mark::Strike),
+            Tag::Link {
+                dest_url, title, ..
+            } => {
+                return self.marks.push(Mark::Link {
+                    attrs: LinkAttrs {
+                        href: dest_url.to_string(),
+                        title: if title.is_empty() {
+                            None
+                        } else {
+                            Some(title.to_string())
+                        },
+                        extra: Map::new(),
+                    },
+                })
+            }
+            _ => Container::Other,
+        };
+
+        self.stack.push(Open::new(container));
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        if matches!(
+            tag,
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link
+        ) {
+            self.marks.pop();
+            return;
+        }
+
+        // Unwrap here is considered safe since every end event follows its start event.
+        let open = self.stack.pop().unwrap();
+
+        match open.container {
+            Container::Paragraph => self.add_block(Node::Paragraph {
+                attrs: Map::new(),
+                content: open.inlines,
+                marks: vec![],
+            }),
+            Container::Heading(level) => self.add_block(Node::Heading {
+                attrs: super::HeadingAttrs {
+                    level,
+                    extra: Map::new(),
+                },
+                content: open.inlines,
+                marks: vec![],
+            }),
+            Container::BlockQuote => {
+                let content = fit_content("blockquote", open.into_blocks());
+                if !content.is_empty() {
+                    self.add_block(Node::Blockquote {
+                        attrs: Map::new(),
+                        content,
+                    });
+                }
+            }
+            Container::CodeBlock(language) => {
+                let code = open.text.strip_suffix('\n').unwrap_or(&open.text);
+                self.add_block(Node::CodeBlock {
+                    attrs: language.map(|language| super::CodeBlockAttrs {
+                        language: Some(language),
+                        extra: Map::new(),
+                    }),
+                    content: if code.is_empty() {
+                        vec![]
+                    } else {
+                        vec![Node::text(code)]
+                    },
+                    marks: vec![],
+                });
+            }
+            Container::HtmlBlock => {
+                let html = open.text.trim_end();
+                if !html.is_empty() {
+                    self.add_block(Node::text_paragraph(html));
+                }
+            }
+            Container::Item(task) => {
+                // The content is fitted once the list knows whether it is a task list, which keeps nested task lists.
+                let content = open.into_blocks();
+                let list = self.top();
+                list.blocks.push(Node::ListItem {
+                    attrs: Map::new(),
+                    content,
+                });
+                list.tasks.push(task);
+            }
+            Container::List(start) => {
+                let list = self.list(start, open.blocks, &open.tasks);
+                self.add_block(list);
+            }
+            Container::Table => self.add_block(Node::Table {
+                attrs: None,
+                content: open.blocks,
+            }),
+            Container::TableHead | Container::TableRow => {
+                let content = open.blocks;
+                self.top().blocks.push(Node::TableRow {
+                    attrs: Map::new(),
+                    content,
+                });
+            }
+            Container::TableCell => {
+                let header = matches!(self.top().container, Container::TableHead);
+                let mut content = fit_content("tableCell", open.into_blocks());
+                if content.is_empty() {
+                    content.push(Node::Paragraph {
+                        attrs: Map::new(),
+                        content: vec![],
+                        marks: vec![],
+                    });
+                }
+
+                self.top().blocks.push(if header {
+                    Node::TableHeader {
+                        attrs: None,
+                        content,
+                    }
+                } else {
+                    Node::TableCell {
+                        attrs: None,
+                        content,
+                    }
+                });
+            }
+            Container::Image(url) => {
+                let alt = inlines_to_plain_text(&open.inlines);
+                let mut marks = self.marks.clone();
+                if !marks.iter().any(|mark| matches!(mark, Mark::Link { .. })) {
+                    marks.push(Mark::Link {
+                        attrs: LinkAttrs {
+                            href: url.clone(),
+                            title: None,
+                            extra: Map::new(),
+                        },
+                    });
+                }
+                self.add_text(if alt.is_empty() { &url } else { &alt }, marks);
+            }
+            Container::Other => {
+                let mut open = open;
+                let inlines = std::mem::take(&mut open.inlines);
+                for block in open.blocks {
+                    self.add_block(block);
+                }
+                for inline in inlines {
+                    self.add_inline(inline);
+                }
+            }
+            Container::Document => unreachable!("the document is never ended by the parser"),
+        }
+    }
+
+    fn finish(mut self) -> Document {
+        // Unwrap here is considered safe since only the document is left once the parser is done.
+        let open = self.stack.pop().unwrap();
+
+        Document::new(fit_content("doc", open.into_blocks()))
+    }
+
+    fn add_block(&mut self, node: Node) {
+        let top = self.top();
+        top.flush_inlines();
+        top.blocks.push(node);
+    }
+
+    fn add_inline(&mut self, node: Node) {
+        self.top().inlines.push(node);
+    }
+
+    /// Adds text, joining it onto the text before it if they have the same marks.
+    fn add_text(&mut self, text: &str, marks: Vec<Mark>) {
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(Node::Text {
+            text: last_text,
+            marks: last_marks,
+        }) = self.top().inlines.last_mut()
+        {
+            if *last_marks == marks {
+                last_text.push_str(text);
+                return;
+            }
+        }
+
+        self.add_inline(Node::Text {
+            text: text.to_owned(),
+            marks,
+        });
+    }
+
+    fn next_local_id(&mut self) -> String {
+        self.last_local_id += 1;
+        self.last_local_id.to_string()
+    }
+
+    fn list(&mut self, start: Option<u64>, items: Vec<Node>, tasks: &[Option<bool>]) -> Node {
+        if start.is_none() && !tasks.is_empty() && tasks.iter().all(Option::is_some) {
+            return self.task_list(items, tasks);
+        }
+
+        let content = items
+            .into_iter()
+            .zip(tasks)
+            .map(
+                |(item, task)| match (list_item(item.content().to_vec()), task) {
+                    (Node::ListItem { mut content, .. }, Some(checked)) => {
+                        if let Some(Node::Paragraph { content, .. }) = content.first_mut() {
+                            prepend_text(content, if *checked { "[x] " } else { "[ ] " });
+                        }
+                        Node::ListItem {
+                            attrs: Map::new(),
+                            content,
+                        }
+                    }
+                    (item, _) => item,
+                },
+            )
+            .collect();
+
+        match start {
+            None => Node::BulletList {
+                attrs: Map::new(),
+                content,
+            },
+            Some(order) => Node::OrderedList {
+                attrs: if order == 1 {
+                    None
+                } else {
+                    Some(OrderedListAttrs {
+                        order: order as u32,
+                        extra: Map::new(),
+                    })
+                },
+                content,
+            },
+        }
+    }
+
+    /// Turns list items into task items, whose content can only be inline.  Nested task lists go beside the item
+    /// they belong to, and any other blocks are flattened into lines of text.
+    fn task_list(&mut self, items: Vec<Node>, tasks: &[Option<bool>]) -> Node {
+        let mut content = vec![];
+
+        for (item, task) in items.into_iter().zip(tasks) {
+            let mut inlines = vec![];
+            let mut nested = vec![];
+
+            for block in item.content() {
+                let block_inlines = match block {
+                    Node::Paragraph { content, .. } | Node::Heading { content, .. } => {
+                        content.clone()
+                    }
+                    Node::TaskList { .. } => {
+                        nested.push(block.clone());
+                        continue;
+                    }
+                    block => blocks_to_plain_text(std::slice::from_ref(block))
+                        .lines()
+                        .map(Node::text)
+                        .flat_map(|line| [Node::HardBreak { attrs: Map::new() }, line])
+                        .skip(1)
+                        .collect(),
+                };
+                if block_inlines.is_empty() {
+                    continue;
+                }
+                if !inlines.is_empty() {
+                    inlines.push(Node::HardBreak { attrs: Map::new() });
+                }
+                inlines.extend(block_inlines);
+            }
+
+            content.push(Node::TaskItem {
+                attrs: TaskItemAttrs {
+                    local_id: self.next_local_id(),
+                    state: if *task == Some(true) {
+                        TaskState::Done
+                    } else {
+                        TaskState::Todo
+                    },
+                    extra: Map::new(),
+                },
+                content: inlines,
+            });
+            content.extend(nested);
+        }
+
+        Node::TaskList {
+            attrs: TaskListAttrs {
+                local_id: self.next_local_id(),
+                extra: Map::new(),
+            },
+            content,
+        }
+    }
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+fn is_line_break(html: &str) -> bool {
+    matches!(
+        html.trim().to_ascii_lowercase().as_str(),
+        "<br>" | "<br/>" | "<br />"
+    )
+}
+
+fn render_blocks(blocks: &[Node]) -> String {
+    blocks
+        .iter()
+        .map(render_block)
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+fn render_block(block: &Node) -> String {
+    match block {
+        Node::Paragraph { content, .. } => render_inlines(content, false),
+        Node::Heading { attrs, content, .. } => format!(
+            "{} {}",
+            "#".repeat(attrs.level.clamp(1, 6) as usize),
+            render_inlines(content, false)
+        ),
+        Node::BulletList { content, .. } => content
+            .iter()
+            .map(|item| render_list_item("- ", item.content()))
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Node::OrderedList { attrs, content } => {
+            let start = attrs.as_ref().map_or(1, |attrs| attrs.order);
+            content
+                .iter()
+                .zip(start..)
+                .map(|(item, number)| render_list_item(&format!("{}. ", number), item.content()))
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+        Node::TaskList { content, .. } => render_task_list(content),
+        Node::CodeBlock { attrs, content, .. } => {
+            let code = inlines_to_plain_text(content);
+            let fence = "`".repeat(longest_run(&code, '`').max(2) + 1);
+            let language = attrs
+                .as_ref()
+                .and_then(|attrs| attrs.language.as_deref())
+                .unwrap_or("");
+            format!("{}{}\n{}\n{}", fence, language, code, fence)
+        }
+        Node::Blockquote { content, .. } | Node::Panel { content, .. } => {
+            prefix_lines(&render_blocks(content), "> ", ">")
+        }
+        Node::Rule { .. } => "---".to_owned(),
+        Node::Table { content, .. } => render_table(content),
+        Node::Unknown(value) => escape(&unknown_to_plain_text(value), false),
+        block if block.is_inline() => render_inlines(std::slice::from_ref(block), false),
+        block => render_blocks(block.content()),
+    }
+}
+
+fn render_list_item(marker: &str, content: &[Node]) -> String {
+    let mut body = String::new();
+
+    for (i, block) in content.iter().enumerate() {
+        if i > 0 {
+            // Lists and code can follow a paragraph directly, but two paragraphs need a blank line between them.
+            body.push_str(match block {
+                Node::BulletList { .. }
+                | Node::OrderedList { .. }
+                | Node::TaskList { .. }
+                | Node::CodeBlock { .. } => "\n",
+                _ => "\n\n",
+            });
+        }
+        body.push_str(&render_block(block));
+    }
+
+    format!("{}{}", marker, indent_following_lines(&body, marker.len()))
+        .trim_end()
+        .to_owned()
+}
+
+fn render_task_list(content: &[Node]) -> String {
+    let mut lines = vec![];
+
+    for node in content {
+        match node {
+            Node::TaskItem { attrs, content } => lines.push(
+                format!(
+                    "- [{}] {}",
+                    if attrs.state == TaskState::Done {
+                        "x"
+                    } else {
+                        " "
+                    },
+                    indent_following_lines(&render_inlines(content, false), 6)
+                )
+                .trim_end()
+                .to_owned(),
+            ),
+            Node::TaskList { content, .. } => {
+                lines.push(prefix_lines(&render_task_list(content), "  ", ""))
+            }
+            node => lines.push(render_block(node)),
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn render_table(rows: &[Node]) -> String {
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.content()
+                .iter()
+                .map(|cell| {
+                    cell.content()
+                        .iter()
+                        .map(|block| match block {
+                            Node::Paragraph { content, .. } | Node::Heading { content, .. } => {
+                                render_inlines(content, true)
+                            }
+                            block => {
+                                escape(&blocks_to_plain_text(std::slice::from_ref(block)), true)
+                                    .replace('\n', "<br>")
+                            }
+                        })
+                        .filter(|text| !text.is_empty())
+                        .collect::<Vec<String>>()
+                        .join("<br>")
+                })
+                .collect()
+        })
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let line = |cells: &[String]| {
+        let mut line = String::from("|");
+        for i in 0..columns {
+            line.push(' ');
+            line.push_str(cells.get(i).map(String::as_str).unwrap_or(""));
+            line.push_str(" |");
+        }
+        line
+    };
+
+    let mut lines = vec![line(&rows[0]), line(&vec!["---".to_owned(); columns])];
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+/// Writes inline nodes as Markdown, opening and closing emphasis only where the marks change.
+fn render_inlines(inlines: &[Node], in_table: bool) -> String {
+    let mut writer = InlineWriter {
+        out: String::new(),
+        open: vec![],
+        pending_space: String::new(),
+        at_line_start: true,
+        in_table,
+    };
+
+    for inline in inlines {
+        match inline {
+            Node::Text { text, marks } => writer.text(text, marks),
+            Node::HardBreak { .. } => writer.hard_break(),
+            Node::Mention { attrs } => writer.text(
+                &attrs
+                    .text
+                    .clone()
+                    .unwrap_or_else(|| format!("@{}", attrs.id)),
+                &[],
+            ),
+            Node::Emoji { attrs } => {
+                writer.text(attrs.text.as_deref().unwrap_or(&attrs.short_name), &[])
+            }
+            Node::InlineCard { attrs } => writer.raw(&format!("<{}>", attrs.url)),
+            Node::Unknown(value) => writer.text(&unknown_to_plain_text(value), &[]),
+            block => writer.text(&blocks_to_plain_text(std::slice::from_ref(block)), &[]),
+        }
+    }
+
+    writer.close_all();
+    writer.out
+}
+
+struct InlineWriter {
+    out: String,
+
+    /// The marks which have been opened and not yet closed, from the outermost to the innermost.
+    open: Vec<Mark>,
+
+    /// Whitespace at the end of the last text, held back so that marks are closed before it.
+    pending_space: String,
+
+    at_line_start: bool,
+
+    in_table: bool,
+}
+
+impl InlineWriter {
+    fn text(&mut self, text: &str, marks: &[Mark]) {
+        let core = text.trim();
+        if core.is_empty() {
+            if !self.at_line_start {
+                self.pending_space.push_str(text);
+            }
+            return;
+        }
+        let leading = &text[..text.len() - text.trim_start().len()];
+        let trailing = &text[text.trim_end().len()..];
+
+        let wanted = markdown_marks(marks);
+        let common = self
+            .open
+            .iter()
+            .zip(&wanted)
+            .take_while(|(open, wanted)| open == wanted)
+            .count();
+        self.close_to(common);
+
+        if !self.at_line_start {
+            self.out.push_str(&std::mem::take(&mut self.pending_space));
+            self.out.push_str(leading);
+        }
+        for mark in &wanted[common..] {
+            self.out.push_str(match mark {
+                Mark::Link { .. } => "[",
+                Mark::Strike => "~~",
+                Mark::Strong => "**",
+                _ => "*",
+            });
+        }
+        self.open = wanted;
+
+        if marks.contains(&Mark::Code) {
+            let mut code = core.to_owned();
+            if self.in_table {
+                code = code.replace('|', "\\|");
+            }
+            let fence = "`".repeat(longest_run(&code, '`') + 1);
+            let padding = if code.starts_with('`') || code.ends_with('`') {
+                " "
+            } else {
+                ""
+            };
+            self.out
+                .push_str(&format!("{}{}{}{}{}", fence, padding, code, padding, fence));
+        } else {
+            let mut escaped = escape(core, self.in_table);
+            if self.at_line_start && !self.in_table {
+                escaped = escape_line_start(&escaped);
+            }
+            self.out.push_str(&escaped);
+        }
+
+        self.at_line_start = false;
+        self.pending_space = trailing.to_owned();
+    }
+
+    fn raw(&mut self, text: &str) {
+        self.close_all();
+        if !self.at_line_start {
+            self.out.push_str(&std::mem::take(&mut self.pending_space));
+        }
+        self.out.push_str(text);
+        self.at_line_start = false;
+    }
+
+    fn hard_break(&mut self) {
+        self.close_all();
+        self.pending_space.clear();
+        self.out
+            .push_str(if self.in_table { "<br>" } else { "\\\n" });
+        self.at_line_start = !self.in_table;
+    }
+
+    fn close_all(&mut self) {
+        self.close_to(0);
+    }
+
+    fn close_to(&mut self, depth: usize) {
+        while self.open.len() > depth {
+            // Unwrap here is considered safe since the loop only runs while there are open marks.
+            match self.open.pop().unwrap() {
+                Mark::Link { attrs } => {
+                    self.out.push_str("](");
+                    self.out.push_str(&link_destination(&attrs.href));
+                    if let Some(title) = attrs.title {
+                        self.out
+                            .push_str(&format!(" \"{}\"", title.replace('"', "\\\"")));
+                    }
+                    self.out.push(')');
+                }
+                Mark::Strike => self.out.push_str("~~"),
+                Mark::Strong => self.out.push_str("**"),
+                _ => self.out.push('*'),
+            }
+        }
+    }
+}
+
+/// Returns the marks Markdown can express, from the outermost to the innermost.  Code is left out since it is
+/// written as a code span around the text instead.
+fn markdown_marks(marks: &[Mark]) -> Vec<Mark> {
+    let rank = |mark: &Mark| match mark {
+        Mark::Link { .. } => Some(0),
+        Mark::Strike => Some(1),
+        Mark::Strong => Some(2),
+        Mark::Em => Some(3),
+        _ => None,
+    };
+    let mut marks: Vec<Mark> = marks
+        .iter()
+        .filter(|mark| rank(mark).is_some())
+        .cloned()
+        .collect();
+    marks.sort_by_key(rank);
+    marks
+}
+
+/// Escapes the characters which would otherwise start Markdown formatting.  Underscores within words are left alone,
+/// since they never start emphasis there and are common in identifiers.
+fn escape(text: &str, in_table: bool) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut escaped = String::with_capacity(text.len());
+
+    for (i, c) in chars.iter().enumerate() {
+        let needs_escape = match c {
+            '\\' | '`' | '*' | '[' | ']' | '<' | '~' => true,
+            '|' => in_table,
+            '_' => {
+                let before = i.checked_sub(1).and_then(|i| chars.get(i));
+                let after = chars.get(i + 1);
+                !(before.is_some_and(|c| c.is_alphanumeric())
+                    && after.is_some_and(|c| c.is_alphanumeric()))
+            }
+            _ => false,
+        };
+        if needs_escape {
+            escaped.push('\\');
+        }
+        escaped.push(*c);
+    }
+
+    escaped
+}
+
+/// Escapes text at the start of a line which would otherwise start a heading, blockquote, list or thematic break.
+fn escape_line_start(text: &str) -> String {
+    let starts_block = |marker_len: usize| {
+        text[marker_len..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace)
+    };
+
+    if text.starts_with('>')
+        || text.starts_with(['-', '+', '=']) && starts_block(1)
+        || text.chars().all(|c| c == '-' || c == '=')
+    {
+        return format!("\\{}", text);
+    }
+
+    let hashes = text.chars().take_while(|c| *c == '#').count();
+    if hashes > 0 && starts_block(hashes) {
+        return format!("\\{}", text);
+    }
+
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && text[digits..].starts_with(['.', ')']) && starts_block(digits + 1) {
+        return format!("{}\\{}", &text[..digits], &text[digits..]);
+    }
+
+    text.to_owned()
+}
+
+/// Writes a link destination, wrapping it in angle brackets if it has characters which would otherwise end it.
+fn link_destination(url: &str) -> String {
+    if url.contains([' ', '(', ')', '<', '>']) {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_owned()
+    }
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    text.split(|other| other != c)
+        .map(str::len)
+        .max()
+        .unwrap_or(0)
+}
+
+fn indent_following_lines(text: &str, width: usize) -> String {
+    let indent = " ".repeat(width);
+
+    text.split('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 || line.is_empty() {
+                line.to_owned()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn prefix_lines(text: &str, prefix: &str, empty_prefix: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                empty_prefix.to_owned()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,1345 @@
+use std::collections::VecDeque;
+
+use serde_json::Map;
+
+use super::{
+    blocks_to_plain_text, checkbox_list, fit_content, inlines_to_plain_text, list_item,
+    unknown_to_plain_text, CodeBlockAttrs, Document, EmojiAttrs, HeadingAttrs, LinkAttrs, Mark,
+    MentionAttrs, Node, PanelAttrs, PanelType, SubsupAttrs, SubsupType, TextColorAttrs,
+};
+use crate::{ApiVersion, TextBody};
+
+impl Document {
+    /// Converts [JIRA wiki markup][1], the rich text format of V2 of the API, into a document.
+    ///
+    /// Wiki markup can express things ADF cannot, so some of it is changed on the way in:
+    ///
+    /// - Images (`!diagram.png!`) and attachment links (`[^log.txt]`) are kept as literal text, since ADF only holds
+    ///   media uploaded to Atlassian.
+    /// - Links to things other than URLs, like `[SRE-1]` or `[Some page]`, are kept as literal text.
+    /// - Citations (`??text??`) become emphasis.
+    /// - Panel titles become a bold first line, and panel colors other than those of ADF's panel types become info
+    ///   panels.
+    /// - Text colors other than hex codes and a few basic names are dropped, keeping the text.
+    /// - `[~name]` mentions, as JIRA Server writes them, become mentions with the name as the ID.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use jimberlage_jira_client::adf::Document;
+    ///
+    /// let doc = Document::from_wiki_markup("h2. Rollout\n\n* *api* is done (/)\n* see [the runbook|https://example.com/runbook]");
+    ///
+    /// assert!(doc.validate().is_ok());
+    /// assert_eq!(doc.to_plain_text(), "Rollout\napi is done ✅\nsee the runbook");
+    /// ```
+    ///
+    /// [1]: https://jira.atlassian.com/secure/WikiRendererHelpAction.jspa?section=all
+    pub fn from_wiki_markup(markup: &str) -> Self {
+        Document::new(fit_content("doc", parse_blocks(markup)))
+    }
+
+    /// Converts the document into JIRA wiki markup.
+    ///
+    /// ADF can express things wiki markup cannot, so some of it is changed on the way out:
+    ///
+    /// - Task lists become bullet lists whose items start with `[ ]` or `[x]`.
+    /// - List items and table cells can only hold a line of text, so blocks after the first are joined onto it with
+    ///   line breaks, and blocks other than paragraphs become their plain text.
+    /// - Mentions of account IDs are written as `[~accountid:...]`, as JIRA Cloud does, and other mentions as
+    ///   `[~name]`, as JIRA Server does.  ADF does not say which a mention is, so account IDs are told apart by their
+    ///   shape.
+    /// - Nodes this module has no type for, like media, become their plain text, if they have any.
+    pub fn to_wiki_markup(&self) -> String {
+        render_blocks(&self.content)
+    }
+}
+
+impl TextBody {
+    /// Converts the body into the rich text format of the `ApiVersion`, for moving text between JIRA Server or Data
+    /// Center and JIRA Cloud.  Bodies already in that format are returned as they are.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use jimberlage_jira_client::{ApiVersion, TextBody};
+    ///
+    /// let body = TextBody::WikiMarkup("Deployed *v1.2.0*".to_owned()).into_api_version(ApiVersion::V3);
+    ///
+    /// assert_eq!(body.to_adf().unwrap().to_plain_text(), "Deployed v1.2.0");
+    /// assert_eq!(body.into_api_version(ApiVersion::V2), TextBody::WikiMarkup("Deployed *v1.2.0*".to_owned()));
+    /// ```
+    pub fn into_api_version(self, api_version: ApiVersion) -> TextBody {
+        match (self, api_version) {
+            (TextBody::WikiMarkup(markup), ApiVersion::V3) => {
+                Document::from_wiki_markup(&markup).into()
+            }
+            (TextBody::Adf(value), ApiVersion::V2) => {
+                TextBody::WikiMarkup(match serde_json::from_value::<Document>(value.clone()) {
+                    Ok(document) => document.to_wiki_markup(),
+                    Err(_) => escape(&unknown_to_plain_text(&value), false),
+                })
+            }
+            (body, _) => body,
+        }
+    }
+}
+
+/// The macros which hold blocks, like `{code}...{code}`.
+const BLOCK_MACROS: &[&str] = &[
+    "code", "noformat", "quote", "panel", "info", "note", "warning", "tip",
+];
+
+/// Emoticons, with the short name and text of the emoji JIRA Cloud turns them into.
+const EMOTICONS: &[(&str, &str, &str)] = &[
+    ("(/)", ":check_mark:", "✅"),
+    ("(x)", ":cross_mark:", "❌"),
+    ("(!)", ":warning:", "⚠️"),
+    ("(i)", ":info:", "ℹ️"),
+    ("(y)", ":thumbsup:", "👍"),
+    ("(n)", ":thumbsdown:", "👎"),
+    (":)", ":slight_smile:", "🙂"),
+    (":(", ":disappointed:", "😞"),
+    (":P", ":stuck_out_tongue:", "😛"),
+    (":D", ":smiley:", "😃"),
+    (";)", ":wink:", "😉"),
+];
+
+/// The background colors JIRA uses for each type of panel.
+const PANEL_COLORS: &[(PanelType, &str)] = &[
+    (PanelType::Info, "#deebff"),
+    (PanelType::Note, "#eae6ff"),
+    (PanelType::Success, "#e3fcef"),
+    (PanelType::Warning, "#fffae6"),
+    (PanelType::Error, "#ffebe6"),
+];
+
+const NAMED_COLORS: &[(&str, &str)] = &[
+    ("black", "#000000"),
+    ("blue", "#0000ff"),
+    ("gray", "#808080"),
+    ("green", "#008000"),
+    ("grey", "#808080"),
+    ("orange", "#ffa500"),
+    ("purple", "#800080"),
+    ("red", "#ff0000"),
+    ("white", "#ffffff"),
+    ("yellow", "#ffff00"),
+];
+
+fn parse_blocks(markup: &str) -> Vec<Node> {
+    let mut lines: VecDeque<String> = markup
+        .replace("\r\n", "\n")
+        .split('\n')
+        .map(str::to_owned)
+        .collect();
+    let mut blocks = vec![];
+
+    while let Some(line) = lines.pop_front() {
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some((name, params, rest)) = block_macro(line) {
+            let (inner, after) = take_macro_body(name, rest, &mut lines);
+            if !after.trim().is_empty() {
+                lines.push_front(after);
+            }
+            blocks.extend(macro_block(name, params, &inner));
+        } else if let Some((level, text)) = heading(line) {
+            blocks.push(Node::Heading {
+                attrs: HeadingAttrs {
+                    level,
+                    extra: Map::new(),
+                },
+                content: parse_inlines(text),
+                marks: vec![],
+            });
+        } else if let Some(text) = line.strip_prefix("bq.") {
+            blocks.push(Node::Blockquote {
+                attrs: Map::new(),
+                content: vec![Node::Paragraph {
+                    attrs: Map::new(),
+                    content: parse_inlines(text.trim()),
+                    marks: vec![],
+                }],
+            });
+        } else if is_rule(line) {
+            blocks.push(Node::Rule { attrs: Map::new() });
+        } else if list_marker(line).is_some() {
+            let mut items = vec![line.to_owned()];
+            while lines
+                .front()
+                .is_some_and(|line| list_marker(line.trim()).is_some())
+            {
+                // Unwrap here is considered safe since the loop only runs while there is a line.
+                items.push(lines.pop_front().unwrap().trim().to_owned());
+            }
+            let items: Vec<(&str, &str)> =
+                items.iter().filter_map(|item| list_marker(item)).collect();
+            blocks.extend(build_lists(&items, 0));
+        } else if line.starts_with('|') {
+            let mut rows: Vec<Node> = table_row(line).into_iter().collect();
+            while lines
+                .front()
+                .is_some_and(|line| line.trim().starts_with('|'))
+            {
+                // Unwrap here is considered safe since the loop only runs while there is a line.
+                rows.extend(table_row(lines.pop_front().unwrap().trim()));
+            }
+            // ADF does not allow tables without rows, which is all lines like `||` would make.
+            if !rows.is_empty() {
+                blocks.push(Node::Table {
+                    attrs: None,
+                    content: rows,
+                });
+            }
+        } else {
+            let mut paragraph = vec![line.to_owned()];
+            while lines.front().is_some_and(|line| {
+                let line = line.trim();
+                !line.is_empty() && !starts_block(line)
+            }) {
+                // Unwrap here is considered safe since the loop only runs while there is a line.
+                paragraph.push(lines.pop_front().unwrap().trim().to_owned());
+            }
+            blocks.push(Node::Paragraph {
+                attrs: Map::new(),
+                content: parse_inlines(&paragraph.join("\n")),
+                marks: vec![],
+            });
+        }
+    }
+
+    blocks
+}
+
+fn starts_block(line: &str) -> bool {
+    block_macro(line).is_some()
+        || heading(line).is_some()
+        || line.starts_with("bq.")
+        || is_rule(line)
+        || list_marker(line).is_some()
+        || line.starts_with('|')
+}
+
+/// Returns the name, parameters and the rest of the line, if the line starts with a block macro like `{code:java}`.
+fn block_macro(line: &str) -> Option<(&'static str, &str, &str)> {
+    let tag_end = line.find('}')?;
+    let tag = line.strip_prefix('{')?.get(..tag_end - 1)?;
+    let (name, params) = tag.split_once(':').unwrap_or((tag, ""));
+    let name = BLOCK_MACROS
+        .iter()
+        .find(|macro_name| **macro_name == name)?;
+
+    Some((name, params, &line[tag_end + 1..]))
+}
+
+/// Reads the body of a block macro up to its closing tag, returning the body and whatever follows the closing tag on
+/// its line.  A macro which is never closed runs to the end of the markup, as it does in JIRA.
+fn take_macro_body(name: &str, rest: &str, lines: &mut VecDeque<String>) -> (String, String) {
+    let closing = format!("{{{}}}", name);
+    let mut body = vec![];
+    let mut line = rest.to_owned();
+
+    loop {
+        if let Some(i) = line.find(&closing) {
+            body.push(line[..i].to_owned());
+            let after = line[i + closing.len()..].to_owned();
+            return (trim_blank_lines(&body), after);
+        }
+        body.push(line);
+        match lines.pop_front() {
+            Some(next) => line = next,
+            None => return (trim_blank_lines(&body), String::new()),
+        }
+    }
+}
+
+/// Joins lines, dropping the empty line left by a tag on a line of its own at either end.
+fn trim_blank_lines(lines: &[String]) -> String {
+    let mut lines = lines;
+    if lines.first().is_some_and(|line| line.trim().is_empty()) {
+        lines = &lines[1..];
+    }
+    if lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines = &lines[..lines.len() - 1];
+    }
+
+    lines.join("\n")
+}
+
+fn macro_block(name: &str, params: &str, body: &str) -> Vec<Node> {
+    let params: Vec<(&str, Option<&str>)> = params
+        .split('|')
+        .filter(|param| !param.is_empty())
+        .map(|param| match param.split_once('=') {
+            Some((key, value)) => (key.trim(), Some(value.trim())),
+            None => (param.trim(), None),
+        })
+        .collect();
+    let param = |key: &str| {
+        params
+            .iter()
+            .find(|(k, v)| *k == key && v.is_some())
+            .and_then(|(_, v)| *v)
+    };
+
+    match name {
+        "code" | "noformat" => {
+            let language = if name == "code" {
+                param("language").or_else(|| {
+                    params
+                        .iter()
+                        .find(|(_, value)| value.is_none())
+                        .map(|(key, _)| *key)
+                })
+            } else {
+                None
+            };
+            vec![Node::CodeBlock {
+                // `{code}` without a language keeps empty attrs, so that it is not written back as `{noformat}`.
+                attrs: (name == "code").then(|| CodeBlockAttrs {
+                    language: language.map(str::to_owned),
+                    extra: Map::new(),
+                }),
+                content: if body.is_empty() {
+                    vec![]
+                } else {
+                    vec![Node::text(body)]
+                },
+                marks: vec![],
+            }]
+        }
+        "quote" => {
+            let content = fit_content("blockquote", parse_blocks(body));
+            if content.is_empty() {
+                vec![]
+            } else {
+                vec![Node::Blockquote {
+                    attrs: Map::new(),
+                    content,
+                }]
+            }
+        }
+        _ => {
+            let panel_type = match name {
+                "info" => PanelType::Info,
+                "note" => PanelType::Warning,
+                "warning" => PanelType::Error,
+                "tip" => PanelType::Success,
+                _ => param("bgColor")
+                    .and_then(|color| {
+                        PANEL_COLORS
+                            .iter()
+                            .find(|(_, panel_color)| panel_color.eq_ignore_ascii_case(color))
+                    })
+                    .map_or(PanelType::Info, |(panel_type, _)| *panel_type),
+            };
+            let mut content = vec![];
+            if let Some(title) = param("title") {
+                content.push(Node::Paragraph {
+                    attrs: Map::new(),
+                    content: vec![Node::Text {
+                        text: title.to_owned(),
+                        marks: vec![Mark::Strong],
+                    }],
+                    marks: vec![],
+                });
+            }
+            content.extend(fit_content("panel", parse_blocks(body)));
+            if content.is_empty() {
+                content.push(Node::Paragraph {
+                    attrs: Map::new(),
+                    content: vec![],
+                    marks: vec![],
+                });
+            }
+            vec![Node::Panel {
+                attrs: PanelAttrs {
+                    panel_type,
+                    extra: Map::new(),
+                },
+                content,
+            }]
+        }
+    }
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let rest = line.strip_prefix('h')?;
+    let level = rest.chars().next()?.to_digit(10)?;
+    let text = rest[1..].strip_prefix('.')?;
+
+    if (1..=6).contains(&level) && (text.is_empty() || text.starts_with(char::is_whitespace)) {
+        Some((level as u8, text.trim()))
+    } else {
+        None
+    }
+}
+
+fn is_rule(line: &str) -> bool {
+    line.len() >= 4 && line.chars().all(|c| c == '-')
+}
+
+/// Returns the marker and text of a list item, like `("*#", "text")` for `*# text`.
+fn list_marker(line: &str) -> Option<(&str, &str)> {
+    let marker_len = if line.starts_with('-') {
+        1
+    } else {
+        line.chars().take_while(|c| *c == '*' || *c == '#').count()
+    };
+    let text = line[marker_len..].strip_prefix(char::is_whitespace)?;
+
+    if marker_len == 0 {
+        None
+    } else {
+        Some((&line[..marker_len], text.trim()))
+    }
+}
+
+/// Builds the lists at the depth, with deeper items nested inside the items before them.
+fn build_lists(items: &[(&str, &str)], depth: usize) -> Vec<Node> {
+    let kind = |marker: &str| marker.as_bytes().get(depth).copied().unwrap_or(b'*') == b'#';
+    let mut lists = vec![];
+    let mut i = 0;
+
+    while i < items.len() {
+        let ordered = kind(items[i].0);
+        let mut list_items = vec![];
+
+        while i < items.len() && kind(items[i].0) == ordered {
+            let mut content = vec![];
+            if items[i].0.len() == depth + 1 {
+                content.push(Node::Paragraph {
+                    attrs: Map::new(),
+                    content: parse_inlines(items[i].1),
+                    marks: vec![],
+                });
+                i += 1;
+            }
+
+            let nested_start = i;
+            while i < items.len() && items[i].0.len() > depth + 1 {
+                i += 1;
+            }
+            content.extend(build_lists(&items[nested_start..i], depth + 1));
+
+            list_items.push(list_item(content));
+        }
+
+        lists.push(if ordered {
+            Node::OrderedList {
+                attrs: None,
+                content: list_items,
+            }
+        } else {
+            Node::BulletList {
+                attrs: Map::new(),
+                content: list_items,
+            }
+        });
+    }
+
+    lists
+}
+
+/// Parses a table row, where cells after `||` are headers and cells after `|` are not.  Returns `None` if the row has
+/// no cells, since ADF does not allow empty rows.
+fn table_row(line: &str) -> Option<Node> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut cells = vec![];
+    let mut current: Option<(bool, String)> = None;
+    let mut link_depth = 0;
+    let mut in_code = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c == '\\' && next.is_some() {
+            if let Some((_, text)) = current.as_mut() {
+                text.push(c);
+                text.push(chars[i + 1]);
+            }
+            i += 2;
+            continue;
+        }
+        match (c, next) {
+            ('[', _) if !in_code => link_depth += 1,
+            (']', _) if !in_code && link_depth > 0 => link_depth -= 1,
+            ('{', Some('{')) => in_code = true,
+            ('}', Some('}')) => in_code = false,
+            ('|', _) if link_depth == 0 && !in_code => {
+                cells.extend(current.take());
+                let header = next == Some('|');
+                current = Some((header, String::new()));
+                i += if header { 2 } else { 1 };
+                continue;
+            }
+            _ => (),
+        }
+        if let Some((_, text)) = current.as_mut() {
+            text.push(c);
+        }
+        i += 1;
+    }
+    // The delimiter at the end of the row starts a cell with nothing in it.
+    if let Some((header, text)) = current {
+        if !text.trim().is_empty() {
+            cells.push((header, text));
+        }
+    }
+
+    if cells.is_empty() {
+        return None;
+    }
+
+    Some(Node::TableRow {
+        attrs: Map::new(),
+        content: cells
+            .into_iter()
+            .map(|(header, text)| {
+                let content = vec![Node::Paragraph {
+                    attrs: Map::new(),
+                    content: parse_inlines(text.trim()),
+                    marks: vec![],
+                }];
+                if header {
+                    Node::TableHeader {
+                        attrs: None,
+                        content,
+                    }
+                } else {
+                    Node::TableCell {
+                        attrs: None,
+                        content,
+                    }
+                }
+            })
+            .collect(),
+    })
+}
+
+fn parse_inlines(text: &str) -> Vec<Node> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut nodes = vec![];
+
+    InlineParser { nodes: &mut nodes }.parse(&chars, &[]);
+    nodes
+}
+
+/// Returns the mark a single character delimiter like `*` applies.
+fn delimiter_mark(c: char) -> Option<Mark> {
+    Some(match c {
+        '*' => Mark::Strong,
+        '_' => Mark::Em,
+        '-' => Mark::Strike,
+        '+' => Mark::Underline,
+        '^' => Mark::Subsup {
+            attrs: SubsupAttrs {
+                subsup_type: SubsupType::Sup,
+                extra: Map::new(),
+            },
+        },
+        '~' => Mark::Subsup {
+            attrs: SubsupAttrs {
+                subsup_type: SubsupType::Sub,
+                extra: Map::new(),
+            },
+        },
+        _ => return None,
+    })
+}
+
+fn is_special(c: char) -> bool {
+    c.is_ascii_punctuation()
+}
+
+fn find(chars: &[char], from: usize, pattern: &str) -> Option<usize> {
+    let pattern: Vec<char> = pattern.chars().collect();
+
+    (from..chars.len()).find(|i| chars[*i..].starts_with(&pattern))
+}
+
+struct InlineParser<'a> {
+    nodes: &'a mut Vec<Node>,
+}
+
+impl InlineParser<'_> {
+    fn parse(&mut self, chars: &[char], marks: &[Mark]) {
+        let mut text = String::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let at_boundary = prev.is_none_or(|prev| !prev.is_alphanumeric());
+
+            if c == '\\' {
+                match chars.get(i + 1) {
+                    Some('\\') => {
+                        i = self.hard_break(chars, i + 2, &mut text, marks);
+                    }
+                    Some(next) if is_special(*next) => {
+                        text.push(*next);
+                        i += 2;
+                    }
+                    _ => {
+                        text.push(c);
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+
+            if c == '\n' {
+                i = self.hard_break(chars, i + 1, &mut text, marks);
+                continue;
+            }
+
+            if let Some(consumed) = self.special(chars, i, at_boundary, &mut text, marks) {
+                i += consumed;
+                continue;
+            }
+
+            text.push(c);
+            i += 1;
+        }
+
+        self.text(&mut text, marks);
+    }
+
+    /// Adds a line break, dropping the whitespace around it, and returns where the text after it starts.
+    fn hard_break(
+        &mut self,
+        chars: &[char],
+        mut i: usize,
+        text: &mut String,
+        marks: &[Mark],
+    ) -> usize {
+        text.truncate(text.trim_end().len());
+        self.text(text, marks);
+        self.nodes.push(Node::HardBreak { attrs: Map::new() });
+
+        while chars.get(i).is_some_and(|c| *c == ' ' || *c == '\t') {
+            i += 1;
+        }
+        i
+    }
+
+    /// Parses the markup starting at `i`, if there is any, returning how many characters it took up.
+    fn special(
+        &mut self,
+        chars: &[char],
+        i: usize,
+        at_boundary: bool,
+        text: &mut String,
+        marks: &[Mark],
+    ) -> Option<usize> {
+        let rest = &chars[i..];
+        let starts_with = |pattern: &str| {
+            rest.iter()
+                .copied()
+                .take(pattern.chars().count())
+                .eq(pattern.chars())
+        };
+
+        if starts_with("{{") {
+            let end = find(chars, i + 2, "}}")?;
+            let code: String = chars[i + 2..end].iter().collect();
+            if code.is_empty() {
+                return None;
+            }
+            self.text(text, marks);
+            let mut code_marks: Vec<Mark> = marks
+                .iter()
+                .filter(|mark| matches!(mark, Mark::Link { .. }))
+                .cloned()
+                .collect();
+            code_marks.push(Mark::Code);
+            self.push_text(&code, &code_marks);
+            return Some(end + 2 - i);
+        }
+
+        if starts_with("{color") {
+            let tag_end = find(chars, i, "}")?;
+            let end = find(chars, tag_end, "{color}")?;
+            let tag: String = chars[i + 1..tag_end].iter().collect();
+            self.text(text, marks);
+            let mut inner_marks = marks.to_vec();
+            if let Some(color) = tag.strip_prefix("color:").and_then(hex_color) {
+                inner_marks.push(Mark::TextColor {
+                    attrs: TextColorAttrs {
+                        color,
+                        extra: Map::new(),
+                    },
+                });
+            }
+            self.parse(&chars[tag_end + 1..end], &inner_marks);
+            return Some(end + "{color}".len() - i);
+        }
+
+        // A delimiter in braces, like `{*}`, applies inside words too.
+        if chars[i] == '{' && chars.get(i + 2) == Some(&'}') {
+            let delimiter = chars[i + 1];
+            let mark = delimiter_mark(delimiter)?;
+            let end = find(chars, i + 3, &format!("{{{}}}", delimiter))?;
+            self.text(text, marks);
+            self.parse(&chars[i + 3..end], &with_mark(marks, mark));
+            return Some(end + 3 - i);
+        }
+
+        if chars[i] == '[' {
+            let end = find(chars, i + 1, "]")?;
+            let inner: String = chars[i + 1..end].iter().collect();
+            return self.link(&inner, text, marks).map(|_| end + 1 - i);
+        }
+
+        if !at_boundary {
+            return None;
+        }
+
+        if let Some((emoticon, short_name, emoji)) = EMOTICONS
+            .iter()
+            .find(|(emoticon, _, _)| starts_with(emoticon))
+        {
+            self.text(text, marks);
+            self.nodes.push(Node::Emoji {
+                attrs: EmojiAttrs {
+                    short_name: (*short_name).to_owned(),
+                    id: None,
+                    text: Some((*emoji).to_owned()),
+                    extra: Map::new(),
+                },
+            });
+            return Some(emoticon.chars().count());
+        }
+
+        let in_link = marks.iter().any(|mark| matches!(mark, Mark::Link { .. }));
+        if !in_link && (starts_with("http://") || starts_with("https://")) {
+            let mut end = (i..chars.len())
+                .find(|j| chars[*j].is_whitespace() || "|[]{}".contains(chars[*j]))
+                .unwrap_or(chars.len());
+            while end > i && ".,;:!?)'\"".contains(chars[end - 1]) {
+                end -= 1;
+            }
+            let url: String = chars[i..end].iter().collect();
+            self.text(text, marks);
+            self.push_text(&url, &with_mark(marks, link_mark(&url)));
+            return Some(end - i);
+        }
+
+        if starts_with("??") {
+            let end = self.closing(chars, i + 2, "??")?;
+            self.text(text, marks);
+            self.parse(&chars[i + 2..end], &with_mark(marks, Mark::Em));
+            return Some(end + 2 - i);
+        }
+
+        let mark = delimiter_mark(chars[i])?;
+        let end = self.closing(chars, i + 1, &chars[i].to_string())?;
+        self.text(text, marks);
+        self.parse(&chars[i + 1..end], &with_mark(marks, mark));
+        Some(end + 1 - i)
+    }
+
+    /// Finds the closing delimiter for one opened just before `from`, on the same line.  Delimiters only open before
+    /// text and only close after it, so `a - b` and `well-known` have no strikethrough.
+    fn closing(&self, chars: &[char], from: usize, delimiter: &str) -> Option<usize> {
+        let delimiter: Vec<char> = delimiter.chars().collect();
+        let first = chars.get(from)?;
+        if first.is_whitespace() || *first == delimiter[0] {
+            return None;
+        }
+
+        let mut j = from + 1;
+        while j < chars.len() && chars[j] != '\n' {
+            if chars[j] == '\\' {
+                j += 2;
+                continue;
+            }
+            if chars[j..].starts_with(&delimiter)
+                && !chars[j - 1].is_whitespace()
+                && chars
+                    .get(j + delimiter.len())
+                    .is_none_or(|next| !next.is_alphanumeric())
+            {
+                return Some(j);
+            }
+            j += 1;
+        }
+
+        None
+    }
+
+    /// Parses the inside of square brackets, returning `None` if it is not a link or mention.
+    fn link(&mut self, inner: &str, text: &mut String, marks: &[Mark]) -> Option<()> {
+        if let Some(user) = inner.strip_prefix('~') {
+            let id = user.strip_prefix("accountid:").unwrap_or(user);
+            if id.is_empty() {
+                return None;
+            }
+            self.text(text, marks);
+            self.nodes.push(Node::Mention {
+                attrs: MentionAttrs {
+                    id: id.to_owned(),
+                    text: None,
+                    access_level: None,
+                    extra: Map::new(),
+                },
+            });
+            return Some(());
+        }
+
+        let parts: Vec<&str> = inner.split('|').collect();
+        let (label, url) = match parts.as_slice() {
+            [url] => (*url, *url),
+            [label, url, ..] => (*label, *url),
+            [] => return None,
+        };
+        let url = url.trim();
+        if !is_url(url) {
+            return None;
+        }
+
+        self.text(text, marks);
+        if parts.get(2) == Some(&"smart-link") {
+            self.nodes.push(Node::InlineCard {
+                attrs: super::CardAttrs {
+                    url: url.to_owned(),
+                    extra: Map::new(),
+                },
+            });
+        } else {
+            let label: Vec<char> = label.chars().collect();
+            self.parse(&label, &with_mark(marks, link_mark(url)));
+        }
+        Some(())
+    }
+
+    /// Adds the text collected so far, if there is any.
+    fn text(&mut self, text: &mut String, marks: &[Mark]) {
+        if !text.is_empty() {
+            self.push_text(&std::mem::take(text), marks);
+        }
+    }
+
+    /// Adds text, joining it onto the text before it if they have the same marks.
+    fn push_text(&mut self, text: &str, marks: &[Mark]) {
+        if let Some(Node::Text {
+            text: last_text,
+            marks: last_marks,
+        }) = self.nodes.last_mut()
+        {
+            if last_marks == marks {
+                last_text.push_str(text);
+                return;
+            }
+        }
+
+        self.nodes.push(Node::Text {
+            text: text.to_owned(),
+            marks: marks.to_vec(),
+        });
+    }
+}
+
+fn with_mark(marks: &[Mark], mark: Mark) -> Vec<Mark> {
+    let mut marks = marks.to_vec();
+    if !marks.contains(&mark) {
+        marks.push(mark);
+    }
+    marks
+}
+
+fn link_mark(url: &str) -> Mark {
+    Mark::Link {
+        attrs: LinkAttrs {
+            href: url.to_owned(),
+            title: None,
+            extra: Map::new(),
+        },
+    }
+}
+
+/// Returns whether a mention ID is a JIRA Cloud account ID, like `5b10a2844c20165700ede21g` or
+/// `557058:f58131cb-b67d-43c7-b30d-6b58d40bd077`, rather than a JIRA Server user name.
+fn is_account_id(id: &str) -> bool {
+    match id.split_once(':') {
+        Some((prefix, uuid)) => {
+            !prefix.is_empty()
+                && prefix.chars().all(|c| c.is_ascii_alphanumeric())
+                && uuid.len() == 36
+                && uuid.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+        }
+        None => {
+            id.len() == 24
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        }
+    }
+}
+
+fn is_url(url: &str) -> bool {
+    url.contains("://") || url.starts_with("mailto:")
+}
+
+fn hex_color(color: &str) -> Option<String> {
+    let color = color.trim();
+
+    if color.starts_with('#')
+        && color.len() == 7
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+    {
+        Some(color.to_ascii_lowercase())
+    } else {
+        NAMED_COLORS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(color))
+            .map(|(_, hex)| (*hex).to_owned())
+    }
+}
+
+fn render_blocks(blocks: &[Node]) -> String {
+    blocks
+        .iter()
+        .map(render_block)
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+fn render_block(block: &Node) -> String {
+    match block {
+        Node::Paragraph { content, .. } => render_inlines(content, Context::Paragraph),
+        Node::Heading { attrs, content, .. } => format!(
+            "h{}. {}",
+            attrs.level.clamp(1, 6),
+            render_inlines(content, Context::Line)
+        ),
+        Node::BulletList { .. } | Node::OrderedList { .. } => render_list(block, ""),
+        Node::TaskList { content, .. } => render_list(&checkbox_list(content.clone()), ""),
+        Node::CodeBlock { attrs, content, .. } => {
+            let code = inlines_to_plain_text(content);
+            match attrs.as_ref().map(|attrs| attrs.language.as_deref()) {
+                Some(Some(language)) => format!("{{code:{}}}\n{}\n{{code}}", language, code),
+                Some(None) => format!("{{code}}\n{}\n{{code}}", code),
+                None => format!("{{noformat}}\n{}\n{{noformat}}", code),
+            }
+        }
+        Node::Blockquote { content, .. } => {
+            format!("{{quote}}\n{}\n{{quote}}", render_blocks(content))
+        }
+        Node::Panel { attrs, content } => {
+            let (open, close) = match attrs.panel_type {
+                PanelType::Info => ("{info}".to_owned(), "{info}"),
+                PanelType::Success => ("{tip}".to_owned(), "{tip}"),
+                PanelType::Warning => ("{note}".to_owned(), "{note}"),
+                PanelType::Error => ("{warning}".to_owned(), "{warning}"),
+                PanelType::Note => (
+                    format!("{{panel:bgColor={}}}", PANEL_COLORS[1].1),
+                    "{panel}",
+                ),
+                PanelType::Custom => ("{panel}".to_owned(), "{panel}"),
+            };
+            format!("{}\n{}\n{}", open, render_blocks(content), close)
+        }
+        Node::Rule { .. } => "----".to_owned(),
+        Node::Table { content, .. } => content
+            .iter()
+            .map(render_table_row)
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Node::Unknown(value) => escape(&unknown_to_plain_text(value), false),
+        block if block.is_inline() => {
+            render_inlines(std::slice::from_ref(block), Context::Paragraph)
+        }
+        block => render_blocks(block.content()),
+    }
+}
+
+/// Writes a list, with each item on its own line after its marker, like `*#` for an item in an ordered list inside
+/// a bullet list.
+fn render_list(list: &Node, outer_marker: &str) -> String {
+    let marker = format!(
+        "{}{}",
+        outer_marker,
+        if matches!(list, Node::OrderedList { .. }) {
+            "#"
+        } else {
+            "*"
+        }
+    );
+    let mut lines = vec![];
+
+    for item in list.content() {
+        let mut line = vec![];
+        let mut nested = vec![];
+
+        for block in item.content() {
+            match block {
+                Node::BulletList { .. } | Node::OrderedList { .. } => {
+                    nested.push(render_list(block, &marker))
+                }
+                Node::TaskList { content, .. } => {
+                    nested.push(render_list(&checkbox_list(content.clone()), &marker))
+                }
+                block => line.push(render_line(block)),
+            }
+        }
+
+        lines.push(
+            format!("{} {}", marker, line.join("\\\\"))
+                .trim_end()
+                .to_owned(),
+        );
+        lines.extend(nested);
+    }
+
+    lines.join("\n")
+}
+
+fn render_table_row(row: &Node) -> String {
+    let mut line = String::new();
+    let mut last_delimiter = "|";
+
+    for cell in row.content() {
+        last_delimiter = if matches!(cell, Node::TableHeader { .. }) {
+            "||"
+        } else {
+            "|"
+        };
+        let text = cell
+            .content()
+            .iter()
+            .map(render_cell_block)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<String>>()
+            .join("\\\\");
+
+        line.push_str(last_delimiter);
+        line.push_str(if text.is_empty() { " " } else { &text });
+    }
+    line.push_str(last_delimiter);
+
+    line
+}
+
+/// Writes a block on a single line, for list items.
+fn render_line(block: &Node) -> String {
+    match block {
+        Node::Paragraph { content, .. } | Node::Heading { content, .. } => {
+            render_inlines(content, Context::Line)
+        }
+        block => {
+            escape(&blocks_to_plain_text(std::slice::from_ref(block)), false).replace('\n', "\\\\")
+        }
+    }
+}
+
+fn render_cell_block(block: &Node) -> String {
+    match block {
+        Node::Paragraph { content, .. } | Node::Heading { content, .. } => {
+            render_inlines(content, Context::Cell)
+        }
+        block => {
+            escape(&blocks_to_plain_text(std::slice::from_ref(block)), true).replace('\n', "\\\\")
+        }
+    }
+}
+
+/// Where inline content is written, which decides how line breaks are written and what needs escaping.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Context {
+    /// A paragraph, where a new line is a line break.
+    Paragraph,
+    /// A heading or list item, which end at the end of the line.
+    Line,
+    /// A table cell, which also ends at a `|`.
+    Cell,
+}
+
+fn render_inlines(inlines: &[Node], context: Context) -> String {
+    let mut writer = InlineWriter {
+        inlines,
+        out: String::new(),
+        open: vec![],
+        pending_space: String::new(),
+        at_line_start: true,
+        context,
+    };
+
+    for (i, inline) in inlines.iter().enumerate() {
+        match inline {
+            Node::Text { text, marks } => writer.text(i, text, marks),
+            Node::HardBreak { .. } => writer.hard_break(),
+            Node::Mention { attrs } if is_account_id(&attrs.id) => {
+                writer.raw(&format!("[~accountid:{}]", attrs.id))
+            }
+            Node::Mention { attrs } => writer.raw(&format!("[~{}]", attrs.id)),
+            Node::Emoji { attrs } => writer.raw(
+                EMOTICONS
+                    .iter()
+                    .find(|(_, short_name, _)| *short_name == attrs.short_name)
+                    .map_or(&attrs.short_name, |(emoticon, _, _)| emoticon),
+            ),
+            Node::InlineCard { attrs } => {
+                writer.raw(&format!("[{}|{}|smart-link]", attrs.url, attrs.url))
+            }
+            Node::Unknown(value) => writer.text(i, &unknown_to_plain_text(value), &[]),
+            block => writer.text(i, &blocks_to_plain_text(std::slice::from_ref(block)), &[]),
+        }
+    }
+
+    writer.close_to(0);
+    writer.out
+}
+
+struct InlineWriter<'a> {
+    inlines: &'a [Node],
+
+    out: String,
+
+    /// The marks which have been opened and not yet closed, from the outermost to the innermost, and whether each
+    /// was opened with a delimiter in braces.
+    open: Vec<(Mark, bool)>,
+
+    /// Whitespace at the end of the last text, held back so that marks are closed before it.
+    pending_space: String,
+
+    at_line_start: bool,
+
+    context: Context,
+}
+
+impl InlineWriter<'_> {
+    fn text(&mut self, index: usize, text: &str, marks: &[Mark]) {
+        let core = text.trim();
+        if core.is_empty() {
+            if !self.at_line_start {
+                self.pending_space.push_str(text);
+            }
+            return;
+        }
+        let leading = &text[..text.len() - text.trim_start().len()];
+        let trailing = &text[text.trim_end().len()..];
+
+        let wanted = wiki_marks(marks);
+        // A link whose text is its URL is written as `[url]`.
+        if let [Mark::Link { attrs }] = marks {
+            if attrs.href == core {
+                self.pending_space.push_str(leading);
+                self.raw(&format!("[{}]", core));
+                self.pending_space = trailing.to_owned();
+                return;
+            }
+        }
+        let common = self
+            .open
+            .iter()
+            .zip(&wanted)
+            .take_while(|((open, _), wanted)| open == *wanted)
+            .count();
+        self.close_to(common);
+
+        if !self.at_line_start {
+            self.out.push_str(&std::mem::take(&mut self.pending_space));
+            self.out.push_str(leading);
+        }
+        for depth in common..wanted.len() {
+            // Plain delimiters only work at the edges of words, so a mark touching a letter on either side is
+            // written in braces, like `{*}`, at both ends.
+            let braced = self.out.chars().last().is_some_and(char::is_alphanumeric)
+                || self
+                    .char_after_span(index, &wanted[..=depth])
+                    .is_some_and(char::is_alphanumeric);
+            let mark = wanted[depth].clone();
+            self.out.push_str(&open_delimiter(&mark, braced));
+            self.open.push((mark, braced));
+        }
+
+        if marks.contains(&Mark::Code) {
+            self.out.push_str(&format!("{{{{{}}}}}", core));
+        } else {
+            let mut escaped = escape(core, self.context == Context::Cell);
+            if self.at_line_start && self.context == Context::Paragraph {
+                escaped = escape_line_start(&escaped);
+            }
+            self.out.push_str(&escaped);
+        }
+
+        self.at_line_start = false;
+        self.pending_space = trailing.to_owned();
+    }
+
+    /// Returns the character which will follow the marks, from the text at the index onwards, or `None` if they run
+    /// to a line break or the end.
+    fn char_after_span(&self, index: usize, marks: &[Mark]) -> Option<char> {
+        let mut space_before = false;
+
+        for inline in &self.inlines[index..] {
+            match inline {
+                Node::Text { text, .. } if text.trim().is_empty() => space_before = true,
+                Node::Text {
+                    text,
+                    marks: node_marks,
+                } => {
+                    let node_marks = wiki_marks(node_marks);
+                    if node_marks.len() >= marks.len() && node_marks[..marks.len()] == *marks {
+                        space_before = text.ends_with(char::is_whitespace);
+                        continue;
+                    }
+                    return if space_before || text.starts_with(char::is_whitespace) {
+                        Some(' ')
+                    } else {
+                        text.chars().next()
+                    };
+                }
+                Node::HardBreak { .. } => return None,
+                // Mentions, emoji and cards all start with punctuation.
+                _ => return Some(if space_before { ' ' } else { '[' }),
+            }
+        }
+
+        None
+    }
+
+    fn raw(&mut self, text: &str) {
+        self.close_to(0);
+        if !self.at_line_start {
+            self.out.push_str(&std::mem::take(&mut self.pending_space));
+        }
+        self.out.push_str(text);
+        self.at_line_start = false;
+    }
+
+    fn hard_break(&mut self) {
+        self.close_to(0);
+        self.pending_space.clear();
+        if self.context == Context::Paragraph {
+            self.out.push('\n');
+            self.at_line_start = true;
+        } else {
+            self.out.push_str("\\\\");
+        }
+    }
+
+    fn close_to(&mut self, depth: usize) {
+        while self.open.len() > depth {
+            // Unwrap here is considered safe since the loop only runs while there are open marks.
+            let (mark, braced) = self.open.pop().unwrap();
+            self.out.push_str(&close_delimiter(&mark, braced));
+        }
+    }
+}
+
+/// Returns the marks wiki markup can express, from the outermost to the innermost.  Code is left out since it is
+/// written as `{{...}}` around the text instead.
+fn wiki_marks(marks: &[Mark]) -> Vec<Mark> {
+    let rank = |mark: &Mark| match mark {
+        Mark::Link { .. } => Some(0),
+        Mark::TextColor { .. } => Some(1),
+        Mark::Strike => Some(2),
+        Mark::Underline => Some(3),
+        Mark::Strong => Some(4),
+        Mark::Em => Some(5),
+        Mark::Subsup { .. } => Some(6),
+        _ => None,
+    };
+    let mut marks: Vec<Mark> = marks
+        .iter()
+        .filter(|mark| rank(mark).is_some())
+        .cloned()
+        .collect();
+    marks.sort_by_key(rank);
+    marks
+}
+
+fn delimiter(mark: &Mark) -> char {
+    match mark {
+        Mark::Strike => '-',
+        Mark::Underline => '+',
+        Mark::Strong => '*',
+        Mark::Subsup { attrs } if attrs.subsup_type == SubsupType::Sup => '^',
+        Mark::Subsup { .. } => '~',
+        _ => '_',
+    }
+}
+
+fn open_delimiter(mark: &Mark, braced: bool) -> String {
+    match mark {
+        Mark::Link { .. } => "[".to_owned(),
+        Mark::TextColor { attrs } => format!("{{color:{}}}", attrs.color),
+        mark if braced => format!("{{{}}}", delimiter(mark)),
+        mark => delimiter(mark).to_string(),
+    }
+}
+
+fn close_delimiter(mark: &Mark, braced: bool) -> String {
+    match mark {
+        Mark::Link { attrs } => format!("|{}]", attrs.href),
+        Mark::TextColor { .. } => "{color}".to_owned(),
+        mark if braced => format!("{{{}}}", delimiter(mark)),
+        mark => delimiter(mark).to_string(),
+    }
+}
+
+/// Escapes the characters which would otherwise start wiki markup.  Delimiters like `*` are only escaped where they
+/// could open or close a mark, so that `a - b` and `well-known` stay as they are.
+fn escape(text: &str, in_cell: bool) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut escaped = String::with_capacity(text.len());
+
+    for (i, c) in chars.iter().enumerate() {
+        let before = i.checked_sub(1).map(|i| chars[i]);
+        let after = chars.get(i + 1).copied();
+        let at_boundary = before.is_none_or(|before| !before.is_alphanumeric());
+
+        let needs_escape = match c {
+            '[' | ']' | '{' | '}' => true,
+            // There is no way to escape a backslash, since two of them are a line break.
+            '\\' => false,
+            '|' => in_cell,
+            '?' => after == Some('?'),
+            '*' | '_' | '-' | '+' | '^' | '~' => {
+                let opens = at_boundary && after.is_some_and(|after| !after.is_whitespace());
+                let closes = before.is_some_and(|before| !before.is_whitespace())
+                    && after.is_none_or(|after| !after.is_alphanumeric());
+                opens || closes
+            }
+            _ => {
+                at_boundary
+                    && EMOTICONS.iter().any(|(emoticon, _, _)| {
+                        chars[i..]
+                            .iter()
+                            .copied()
+                            .take(emoticon.chars().count())
+                            .eq(emoticon.chars())
+                    })
+            }
+        };
+        if needs_escape {
+            escaped.push('\\');
+        }
+        escaped.push(*c);
+    }
+
+    escaped
+}
+
+/// Escapes text at the start of a line which would otherwise start a heading, blockquote, list, table or rule.
+fn escape_line_start(text: &str) -> String {
+    if heading(text).is_some() || text.starts_with("bq.") {
+        return format!("{}\\{}", &text[..2], &text[2..]);
+    }
+    if list_marker(text).is_some() || text.starts_with('|') || is_rule(text) {
+        return format!("\\{}", text);
+    }
+
+    text.to_owned()
+}
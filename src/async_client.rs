@@ -0,0 +1,807 @@
+use chrono::{DateTime, TimeZone};
+use reqwest::{
+    header::LOCATION,
+    multipart::{Form, Part},
+    Client, RequestBuilder, Response, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{value::Value as JSONValue, Map};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::attachment::{Attachment, AttachmentArchive};
+use crate::auth::{Auth, Authenticator};
+use crate::changelog::{
+    self, BulkFetchRequest, BulkFetchResponse, ChangelogEntry, BULK_FETCH_MAX_ISSUES,
+};
+use crate::comment::{Comment, CommentRequest};
+use crate::error::{self, Error, RawResponse};
+use crate::fields::FieldCatalog;
+use crate::jql::JQLStatement;
+use crate::link::{
+    self, IssueLink, IssueLinkRequest, IssueLinkType, IssueLinkTypesResponse, RemoteLink,
+    RemoteLinkIdentifier, RemoteLinkRequest,
+};
+use crate::mapping::{JiraIssue, ResolvedFields};
+use crate::retry::RetryPolicy;
+use crate::time_tracking::TimeTrackingSettings;
+use crate::transition::{self, Transition, TransitionRequest, TransitionsResponse};
+use crate::version::ApiVersion;
+use crate::worklog::{
+    UpdatedWorklogsPage, Worklog, WorklogIdsRequest, WorklogOptions, WorklogRequest,
+    WORKLOG_LIST_MAX_IDS,
+};
+use crate::{
+    get_issue_query, BulkCreateResult, CreatedIssue, Field, Issue, IssueBulkCreateRequest,
+    IssueCreateRequest, IssueEditOptions, IssueEditRequest, IssueEditUpdate, IssueExpand, Page,
+    RestClientBuilder, SearchIssue, SearchRequest, SearchResponse, SearchUserResponse, TextBody,
+    ATLASSIAN_TOKEN, PAGE_SIZE, SEARCH_PAGE_SIZE,
+};
+
+/// Provides a reusable async HTTP client for using parts of JIRA's [V3 REST API][1].
+///
+/// This mirrors `RestClient` method for method, but is built on reqwest's async client so that it can be used from
+/// within an async runtime like tokio without blocking the executor.  It is only available with the `async` feature.
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/intro/
+pub struct AsyncRestClient {
+    pub(crate) api_version: ApiVersion,
+    pub(crate) base_url: String,
+    pub(crate) client: Client,
+    pub(crate) auth: Authenticator,
+    pub(crate) retry_policy: RetryPolicy,
+
+    /// The catalog `get_field_catalog` returns, once it has been fetched.
+    pub(crate) field_catalog: Mutex<Option<Arc<FieldCatalog>>>,
+}
+
+impl AsyncRestClient {
+    /// Initialize an AsyncRestClient for the URL, with the given username and token.
+    ///
+    /// This may fail if the TLS backend cannot be initialized, or if the resolver cannot load the system
+    /// configuration.  Use `RestClientBuilder` for other auth schemes and connection settings.
+    pub fn new(url: &str, username: &str, token: &str) -> Result<Self, Error> {
+        RestClientBuilder::new(url)
+            .auth(Auth::basic(username, token))
+            .build_async()
+    }
+
+    /// Returns the version of JIRA's REST API this client talks to.
+    pub fn api_version(&self) -> ApiVersion {
+        self.api_version
+    }
+
+    /// Sets the policy for retrying requests that JIRA could not serve right now.
+    ///
+    /// Clients use `RetryPolicy::default()` unless this is called, or `RestClientBuilder::retry_policy` is used.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sends the request, retrying according to the client's retry policy, and turns unsuccessful responses into the
+    /// matching `Error`.
+    ///
+    /// Returns the raw response, which callers can check with `RawResponse::into_body` if they have no special
+    /// handling for unsuccessful responses.  `idempotent` marks requests that are safe to retry even though their
+    /// method is not, like POSTs that only read.
+    async fn execute(
+        &self,
+        request: RequestBuilder,
+        idempotent: bool,
+    ) -> Result<RawResponse, Error> {
+        Self::read_response(self.execute_streaming(request, idempotent).await?).await
+    }
+
+    /// Sends the request like `execute`, but leaves the body of the response unread, so that it can be streamed.
+    ///
+    /// The response is returned whatever its status; callers have to check it.
+    async fn execute_streaming(
+        &self,
+        request: RequestBuilder,
+        idempotent: bool,
+    ) -> Result<Response, Error> {
+        let mut request = request.build()?;
+        let may_retry = self.retry_policy.may_retry(request.method(), idempotent);
+        let mut attempt = 1;
+        let mut refreshed_auth = false;
+
+        loop {
+            // Requests with streaming bodies cannot be cloned, and so can only be sent once.
+            let mut attempt_request = match request.try_clone() {
+                Some(attempt_request) => attempt_request,
+                None => break,
+            };
+            let sent_token = self
+                .auth
+                .authorize_async(attempt_request.headers_mut())
+                .await?;
+
+            let outcome = self.client.execute(attempt_request).await;
+
+            // An expired OAuth 2.0 token is refreshed and the request sent again once, regardless of the retry policy.
+            if let (Ok(response), Some(sent_token)) = (&outcome, &sent_token) {
+                if response.status() == StatusCode::UNAUTHORIZED && !refreshed_auth {
+                    self.auth.refresh_async(sent_token).await?;
+                    refreshed_auth = true;
+                    continue;
+                }
+            }
+
+            let delay = self.retry_policy.delay_before_retry(
+                attempt,
+                may_retry,
+                outcome
+                    .as_ref()
+                    .map(|response| (response.status(), response.headers())),
+            );
+
+            match delay {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Ok(outcome?),
+            }
+
+            attempt += 1;
+        }
+
+        self.auth.authorize_async(request.headers_mut()).await?;
+        Ok(self.client.execute(request).await?)
+    }
+
+    /// Reads the status, headers and body out of a response.
+    async fn read_response(response: Response) -> Result<RawResponse, Error> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+
+        Ok(RawResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    /// Sends the request, turning unsuccessful responses into the matching `Error`.
+    ///
+    /// Only idempotent methods are retried; see `RetryPolicy`.
+    async fn send(&self, request: RequestBuilder) -> Result<String, Error> {
+        self.execute(request, false).await?.into_body()
+    }
+
+    /// Sends the request, and parses the JSON body of a successful response.
+    async fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, Error> {
+        error::parse_body(self.send(request).await?)
+    }
+
+    /// Make a GET request to the specified path, using the URL, username, & token configured for the client.
+    ///
+    /// Returns a `reqwest::RequestBuilder` so that you can use any method available in the reqwest library.
+    fn get(&self, path: &str) -> RequestBuilder {
+        self.client.get(format!("{}{}", self.base_url, path))
+    }
+
+    /// Make a POST request to the specified path, using the URL, username, & token configured for the client.
+    ///
+    /// Returns a `reqwest::RequestBuilder` so that you can use any method available in the reqwest library.
+    fn post(&self, path: &str) -> RequestBuilder {
+        self.client.post(format!("{}{}", self.base_url, path))
+    }
+
+    /// Make a PUT request to the specified path, using the URL, username, & token configured for the client.
+    ///
+    /// Returns a `reqwest::RequestBuilder` so that you can use any method available in the reqwest library.
+    fn put(&self, path: &str) -> RequestBuilder {
+        self.client.put(format!("{}{}", self.base_url, path))
+    }
+
+    /// Make a DELETE request to the specified path, using the URL, username, & token configured for the client.
+    ///
+    /// Returns a `reqwest::RequestBuilder` so that you can use any method available in the reqwest library.
+    fn delete(&self, path: &str) -> RequestBuilder {
+        self.client.delete(format!("{}{}", self.base_url, path))
+    }
+
+    /// Gets every result from an endpoint which pages with `startAt` and `maxResults`, visiting each page.
+    async fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, Error> {
+        let mut start_at = 0u64;
+        let mut result = vec![];
+
+        loop {
+            let page: Page<T> = self
+                .send_json(
+                    self.get(path)
+                        .query(query)
+                        .query(&[("startAt", start_at), ("maxResults", PAGE_SIZE)]),
+                )
+                .await?;
+            let next_start_at = page.next_start_at();
+            result.extend(page.values);
+
+            match next_start_at {
+                Some(next_start_at) => start_at = next_start_at,
+                None => break,
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Gets all configured fields for your JIRA instance.
+    ///
+    /// See `RestClient::get_fields`.
+    pub async fn get_fields(&self) -> Result<Vec<Field>, Error> {
+        self.send_json(self.get("/field")).await
+    }
+
+    /// Gets all configured fields for your JIRA instance, to look up by name.
+    ///
+    /// See `RestClient::get_field_catalog`.
+    pub async fn get_field_catalog(&self) -> Result<Arc<FieldCatalog>, Error> {
+        if let Some(catalog) = crate::cached_field_catalog(&self.field_catalog) {
+            return Ok(catalog);
+        }
+
+        self.refresh_field_catalog().await
+    }
+
+    /// Gets all configured fields for your JIRA instance again, replacing the catalog the client keeps.
+    ///
+    /// See `RestClient::refresh_field_catalog`.
+    pub async fn refresh_field_catalog(&self) -> Result<Arc<FieldCatalog>, Error> {
+        let catalog = Arc::new(FieldCatalog::new(self.get_fields().await?));
+        crate::cache_field_catalog(&self.field_catalog, catalog.clone());
+
+        Ok(catalog)
+    }
+
+    /// Search JIRA for issues matching the given JQL statement, for a single page of results.
+    ///
+    /// See `RestClient::search`.
+    async fn search(
+        &self,
+        fields: &[String],
+        jql: &JQLStatement,
+        start_at: u64,
+        max_results: u64,
+    ) -> Result<SearchResponse, Error> {
+        // Searching only reads issues, so it is safe to retry despite being a POST.
+        let request = self.post("/search").json(&SearchRequest {
+            fields: fields.to_vec(),
+            jql: jql.clone(),
+            start_at,
+            max_results,
+        });
+        error::parse_body(self.execute(request, true).await?.into_body()?)
+    }
+
+    /// Search JIRA for issues matching the given JQL statement, visiting each page of results.
+    ///
+    /// See `RestClient::search_all`.
+    pub async fn search_all(
+        &self,
+        fields: &[String],
+        jql: &JQLStatement,
+    ) -> Result<Vec<SearchIssue>, Error> {
+        let mut start_at = 0u64;
+        let mut result = vec![];
+
+        loop {
+            let mut response = self.search(fields, jql, start_at, SEARCH_PAGE_SIZE).await?;
+            let num_responses = response.issues.len() as u64;
+            result.append(&mut response.issues);
+
+            if num_responses < SEARCH_PAGE_SIZE {
+                break;
+            }
+
+            start_at += num_responses
+        }
+
+        Ok(result)
+    }
+
+    /// Finds the IDs of the fields a `JiraIssue` type reads.
+    ///
+    /// See `RestClient::resolve_fields`.
+    pub async fn resolve_fields<T: JiraIssue>(&self) -> Result<ResolvedFields<T>, Error> {
+        if ResolvedFields::<T>::needs_names() {
+            ResolvedFields::new(&*self.get_field_catalog().await?)
+        } else {
+            ResolvedFields::new(&FieldCatalog::default())
+        }
+    }
+
+    /// Search JIRA for issues matching the given JQL statement, reading each into a `JiraIssue` type.
+    ///
+    /// See `RestClient::search_all_as`.
+    pub async fn search_all_as<T: JiraIssue>(&self, jql: &JQLStatement) -> Result<Vec<T>, Error> {
+        let fields = self.resolve_fields::<T>().await?;
+
+        self.search_all(fields.ids(), jql)
+            .await?
+            .iter()
+            .map(|issue| T::from_issue(issue, &fields))
+            .collect()
+    }
+
+    /// Gets a single issue by its key or ID.
+    ///
+    /// See `RestClient::get_issue`.
+    pub async fn get_issue(
+        &self,
+        key: &str,
+        fields: &[String],
+        expand: &[IssueExpand],
+    ) -> Result<Issue, Error> {
+        let path = format!("/issue/{}", key);
+        self.send_json(self.get(&path).query(&get_issue_query(fields, expand)))
+            .await
+    }
+
+    /// Gets the transitions the user can make from the issue's current status.
+    ///
+    /// See `RestClient::get_transitions`.
+    pub async fn get_transitions(&self, key: &str) -> Result<Vec<Transition>, Error> {
+        let path = format!("/issue/{}/transitions", key);
+        let response: TransitionsResponse = self.send_json(self.get(&path)).await?;
+
+        Ok(response.transitions)
+    }
+
+    /// Gets the transition which moves the issue into the status with the name, ignoring case.
+    ///
+    /// See `RestClient::get_transition_to_status`.
+    pub async fn get_transition_to_status(
+        &self,
+        key: &str,
+        status_name: &str,
+    ) -> Result<Option<Transition>, Error> {
+        let transitions = self.get_transitions(key).await?;
+
+        Ok(transition::find_by_status(&transitions, status_name).cloned())
+    }
+
+    /// Moves an issue through its workflow with the transition, by ID.
+    ///
+    /// See `RestClient::transition_issue`.
+    pub async fn transition_issue(
+        &self,
+        key: &str,
+        transition_id: &str,
+        fields: &Map<String, JSONValue>,
+        update: &IssueEditUpdate,
+        comment: Option<&TextBody>,
+    ) -> Result<(), Error> {
+        let path = format!("/issue/{}/transitions", key);
+        self.send(self.post(&path).json(&TransitionRequest::new(
+            transition_id,
+            fields,
+            update,
+            comment,
+        )))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gets every change made to an issue, oldest first, visiting each page for you.
+    ///
+    /// See `RestClient::get_changelog`.
+    pub async fn get_changelog(&self, key: &str) -> Result<Vec<ChangelogEntry>, Error> {
+        let path = format!("/issue/{}/changelog", key);
+        self.get_all_pages(&path, &[]).await
+    }
+
+    /// Gets every change made to each of the issues, by key or ID, visiting each page for you.
+    ///
+    /// See `RestClient::bulk_fetch_changelogs`.
+    pub async fn bulk_fetch_changelogs(
+        &self,
+        keys: &[String],
+    ) -> Result<HashMap<String, Vec<ChangelogEntry>>, Error> {
+        let mut result = HashMap::new();
+
+        for chunk in keys.chunks(BULK_FETCH_MAX_ISSUES) {
+            let mut next_page_token = None;
+
+            loop {
+                // Fetching changelogs only reads them, so it is safe to retry despite being a POST.
+                let request = self.post("/changelog/bulkfetch").json(&BulkFetchRequest {
+                    issue_ids_or_keys: chunk,
+                    next_page_token,
+                });
+                let mut response: BulkFetchResponse =
+                    error::parse_body(self.execute(request, true).await?.into_body()?)?;
+                next_page_token = response.next_page_token.take();
+                changelog::merge_bulk_fetch(&mut result, response);
+
+                if next_page_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Gets every comment on an issue, oldest first, visiting each page for you.
+    ///
+    /// Comment properties are included.
+    ///
+    /// See `RestClient::get_comments`.
+    pub async fn get_comments(&self, key: &str) -> Result<Vec<Comment>, Error> {
+        let path = format!("/issue/{}/comment", key);
+        self.get_all_pages(&path, &[("expand", "properties")]).await
+    }
+
+    /// Adds a comment to an issue.
+    ///
+    /// See `RestClient::add_comment`.
+    pub async fn add_comment(&self, key: &str, request: &CommentRequest) -> Result<Comment, Error> {
+        let path = format!("/issue/{}/comment", key);
+        self.send_json(self.post(&path).json(request)).await
+    }
+
+    /// Replaces the body, visibility and properties of a comment.
+    ///
+    /// See `RestClient::update_comment`.
+    pub async fn update_comment(
+        &self,
+        key: &str,
+        comment_id: &str,
+        request: &CommentRequest,
+    ) -> Result<Comment, Error> {
+        let path = format!("/issue/{}/comment/{}", key, comment_id);
+        self.send_json(self.put(&path).json(request)).await
+    }
+
+    /// Deletes a comment.
+    ///
+    /// See `RestClient::delete_comment`.
+    pub async fn delete_comment(&self, key: &str, comment_id: &str) -> Result<(), Error> {
+        let path = format!("/issue/{}/comment/{}", key, comment_id);
+        self.send(self.delete(&path)).await?;
+
+        Ok(())
+    }
+
+    /// Gets every worklog on an issue, oldest first, visiting each page for you.
+    ///
+    /// Worklog properties are included.
+    ///
+    /// See `RestClient::get_worklogs`.
+    pub async fn get_worklogs(&self, key: &str) -> Result<Vec<Worklog>, Error> {
+        let path = format!("/issue/{}/worklog", key);
+        self.get_all_pages(&path, &[("expand", "properties")]).await
+    }
+
+    /// Gets a single worklog on an issue.
+    ///
+    /// See `RestClient::get_worklog`.
+    pub async fn get_worklog(&self, key: &str, worklog_id: &str) -> Result<Worklog, Error> {
+        let path = format!("/issue/{}/worklog/{}", key, worklog_id);
+        self.send_json(self.get(&path).query(&[("expand", "properties")]))
+            .await
+    }
+
+    /// Logs time against an issue.
+    ///
+    /// See `RestClient::add_worklog`.
+    pub async fn add_worklog(
+        &self,
+        key: &str,
+        request: &WorklogRequest,
+        options: &WorklogOptions,
+    ) -> Result<Worklog, Error> {
+        let path = format!("/issue/{}/worklog", key);
+        self.send_json(
+            self.post(&path)
+                .query(&options.query(Some("reduceBy"))?)
+                .json(request),
+        )
+        .await
+    }
+
+    /// Replaces the time spent, start, comment, visibility and properties of a worklog.
+    ///
+    /// See `RestClient::update_worklog`.
+    pub async fn update_worklog(
+        &self,
+        key: &str,
+        worklog_id: &str,
+        request: &WorklogRequest,
+        options: &WorklogOptions,
+    ) -> Result<Worklog, Error> {
+        let path = format!("/issue/{}/worklog/{}", key, worklog_id);
+        self.send_json(self.put(&path).query(&options.query(None)?).json(request))
+            .await
+    }
+
+    /// Deletes a worklog.
+    ///
+    /// See `RestClient::delete_worklog`.
+    pub async fn delete_worklog(
+        &self,
+        key: &str,
+        worklog_id: &str,
+        options: &WorklogOptions,
+    ) -> Result<(), Error> {
+        let path = format!("/issue/{}/worklog/{}", key, worklog_id);
+        self.send(
+            self.delete(&path)
+                .query(&options.query(Some("increaseBy"))?),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gets every worklog, on any issue, created or updated since the time, visiting each page for you.
+    ///
+    /// See `RestClient::get_worklogs_updated_since`.
+    pub async fn get_worklogs_updated_since<Tz: TimeZone>(
+        &self,
+        since: &DateTime<Tz>,
+    ) -> Result<Vec<Worklog>, Error> {
+        let mut ids = vec![];
+        let mut since = since.timestamp_millis();
+
+        loop {
+            let page: UpdatedWorklogsPage = self
+                .send_json(self.get("/worklog/updated").query(&[("since", since)]))
+                .await?;
+            ids.extend(page.values.iter().map(|worklog| worklog.worklog_id));
+
+            if page.last_page {
+                break;
+            }
+            since = page.until;
+        }
+
+        let mut result = vec![];
+        for chunk in ids.chunks(WORKLOG_LIST_MAX_IDS) {
+            // Listing worklogs only reads them, so it is safe to retry despite being a POST.
+            let request = self
+                .post("/worklog/list")
+                .query(&[("expand", "properties")])
+                .json(&WorklogIdsRequest { ids: chunk });
+            let mut worklogs: Vec<Worklog> =
+                error::parse_body(self.execute(request, true).await?.into_body()?)?;
+            result.append(&mut worklogs);
+        }
+
+        Ok(result)
+    }
+
+    /// Gets how the instance turns days and weeks into hours, for parsing and formatting durations.
+    ///
+    /// See `RestClient::get_time_tracking_settings`.
+    pub async fn get_time_tracking_settings(&self) -> Result<TimeTrackingSettings, Error> {
+        self.send_json(self.get("/configuration/timetracking/options"))
+            .await
+    }
+
+    /// Attaches a file to an issue.
+    ///
+    /// See `RestClient::add_attachment`.
+    pub async fn add_attachment(
+        &self,
+        key: &str,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> Result<Attachment, Error> {
+        let path = format!("/issue/{}/attachments", key);
+        let form = Form::new().part("file", Part::bytes(content).file_name(file_name.to_owned()));
+        // JIRA rejects uploads without this header, as protection against cross-site request forgery.
+        let [attachment]: [Attachment; 1] = self
+            .send_json(
+                self.post(&path)
+                    .header(ATLASSIAN_TOKEN, "no-check")
+                    .multipart(form),
+            )
+            .await?;
+
+        Ok(attachment)
+    }
+
+    /// Gets the metadata of an attachment, like its file name and size.
+    ///
+    /// See `RestClient::get_attachment_meta`.
+    pub async fn get_attachment_meta(&self, attachment_id: &str) -> Result<Attachment, Error> {
+        let path = format!("/attachment/{}", attachment_id);
+        self.send_json(self.get(&path)).await
+    }
+
+    /// Writes the content of an attachment to `out` as it downloads, so that large files are not held in memory.
+    ///
+    /// See `RestClient::download_attachment`.
+    pub async fn download_attachment<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        attachment_id: &str,
+        out: &mut W,
+    ) -> Result<u64, Error> {
+        let path = format!("/attachment/content/{}", attachment_id);
+        let mut response = self.execute_streaming(self.get(&path), false).await?;
+
+        if !response.status().is_success() {
+            return Err(Self::read_response(response).await?.into_error());
+        }
+
+        let mut written = 0;
+        while let Some(chunk) = response.chunk().await? {
+            out.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        out.flush().await?;
+
+        Ok(written)
+    }
+
+    /// Lists the files in an archive attached to an issue, like a zip file.
+    ///
+    /// See `RestClient::expand_attachment`.
+    pub async fn expand_attachment(&self, attachment_id: &str) -> Result<AttachmentArchive, Error> {
+        let path = format!("/attachment/{}/expand/human", attachment_id);
+        self.send_json(self.get(&path)).await
+    }
+
+    /// Deletes an attachment.
+    ///
+    /// See `RestClient::delete_attachment`.
+    pub async fn delete_attachment(&self, attachment_id: &str) -> Result<(), Error> {
+        let path = format!("/attachment/{}", attachment_id);
+        self.send(self.delete(&path)).await?;
+
+        Ok(())
+    }
+
+    /// Links two issues.  Start the request with `IssueLinkType::outward_link` or `IssueLinkType::inward_link`.
+    ///
+    /// Returns the ID of the new link, if JIRA said what it was.
+    ///
+    /// See `RestClient::create_issue_link`.
+    pub async fn create_issue_link(
+        &self,
+        request: &IssueLinkRequest,
+    ) -> Result<Option<String>, Error> {
+        let response = self
+            .execute(self.post("/issueLink").json(request), false)
+            .await?;
+        // JIRA responds with 201 Created and no body, so the ID is only in the URL of the new link.
+        let id = response
+            .headers
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(link::id_from_location);
+        response.into_body()?;
+
+        Ok(id)
+    }
+
+    /// Gets a link between two issues.
+    ///
+    /// See `RestClient::get_issue_link`.
+    pub async fn get_issue_link(&self, link_id: &str) -> Result<IssueLink, Error> {
+        let path = format!("/issueLink/{}", link_id);
+        self.send_json(self.get(&path)).await
+    }
+
+    /// Deletes a link between two issues.
+    ///
+    /// See `RestClient::delete_issue_link`.
+    pub async fn delete_issue_link(&self, link_id: &str) -> Result<(), Error> {
+        let path = format!("/issueLink/{}", link_id);
+        self.send(self.delete(&path)).await?;
+
+        Ok(())
+    }
+
+    /// Gets every kind of link issues can have between them, like "Blocks".
+    ///
+    /// See `RestClient::get_issue_link_types`.
+    pub async fn get_issue_link_types(&self) -> Result<Vec<IssueLinkType>, Error> {
+        let response: IssueLinkTypesResponse = self.send_json(self.get("/issueLinkType")).await?;
+
+        Ok(response.issue_link_types)
+    }
+
+    /// Gets the links from an issue to things outside of JIRA, like pull requests.
+    ///
+    /// See `RestClient::get_remote_links`.
+    pub async fn get_remote_links(&self, key: &str) -> Result<Vec<RemoteLink>, Error> {
+        let path = format!("/issue/{}/remotelink", key);
+        self.send_json(self.get(&path)).await
+    }
+
+    /// Links an issue to something outside of JIRA, or updates the link with the same `global_id` if the issue
+    /// already has one.
+    ///
+    /// See `RestClient::upsert_remote_link`.
+    pub async fn upsert_remote_link(
+        &self,
+        key: &str,
+        request: &RemoteLinkRequest,
+    ) -> Result<RemoteLinkIdentifier, Error> {
+        let path = format!("/issue/{}/remotelink", key);
+        self.send_json(self.post(&path).json(request)).await
+    }
+
+    /// Deletes a remote link by its ID.
+    ///
+    /// See `RestClient::delete_remote_link`.
+    pub async fn delete_remote_link(&self, key: &str, link_id: &str) -> Result<(), Error> {
+        let path = format!("/issue/{}/remotelink/{}", key, link_id);
+        self.send(self.delete(&path)).await?;
+
+        Ok(())
+    }
+
+    /// Deletes the remote link with the global ID.
+    ///
+    /// See `RestClient::delete_remote_link_by_global_id`.
+    pub async fn delete_remote_link_by_global_id(
+        &self,
+        key: &str,
+        global_id: &str,
+    ) -> Result<(), Error> {
+        let path = format!("/issue/{}/remotelink", key);
+        self.send(self.delete(&path).query(&[("globalId", global_id)]))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Searches for users in JIRA by key or email.
+    ///
+    /// See `RestClient::search_users`.
+    pub async fn search_users(&self, query: &str) -> Result<SearchUserResponse, Error> {
+        self.send_json(self.get("/user/picker").query(&[("query", query)]))
+            .await
+    }
+
+    /// Creates an issue.
+    ///
+    /// See `RestClient::create_issue`.
+    pub async fn create_issue(&self, request: &IssueCreateRequest) -> Result<CreatedIssue, Error> {
+        self.send_json(self.post("/issue").json(request)).await
+    }
+
+    /// Creates up to 50 issues at once.
+    ///
+    /// See `RestClient::create_issues_bulk`.
+    pub async fn create_issues_bulk(
+        &self,
+        requests: &[IssueCreateRequest],
+    ) -> Result<BulkCreateResult, Error> {
+        let request = self.post("/issue/bulk").json(&IssueBulkCreateRequest {
+            issue_updates: requests,
+        });
+        BulkCreateResult::from_response(self.execute(request, false).await?)
+    }
+
+    /// Edits an issue.
+    ///
+    /// See `RestClient::edit_issue`.
+    pub async fn edit_issue(
+        &self,
+        key: &str,
+        request: &IssueEditRequest,
+        options: &IssueEditOptions,
+    ) -> Result<Option<Issue>, Error> {
+        let path = format!("/issue/{}", key);
+        let body = self
+            .send(self.put(&path).query(&options.query()).json(request))
+            .await?;
+
+        // Unless the issue is asked for, JIRA responds with 204 No Content, so there is no body to parse.
+        error::parse_optional_body(body)
+    }
+}
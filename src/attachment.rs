@@ -0,0 +1,95 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+
+use crate::{util, User};
+
+/// Represents a file attached to an issue, as returned by a [get attachment metadata request][1].
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::attachment::Attachment;
+///
+/// let attachment: Attachment = serde_json::from_str(r#"{
+///   "id": 10000,
+///   "filename": "build.log",
+///   "created": "2023-05-10T14:23:01.123+0000",
+///   "size": 23123,
+///   "mimeType": "text/plain",
+///   "content": "https://your-domain.atlassian.net/rest/api/3/attachment/content/10000"
+/// }"#).unwrap();
+///
+/// assert_eq!(attachment.id, "10000");
+/// assert_eq!(attachment.size, 23123);
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-attachments/#api-rest-api-3-attachment-id-get
+#[derive(Clone, Debug, Deserialize)]
+pub struct Attachment {
+    /// JIRA sends this as a number from some endpoints and a string from others; it is always a string here.
+    #[serde(deserialize_with = "util::deserialize_id")]
+    pub id: String,
+
+    pub filename: String,
+
+    #[serde(default)]
+    pub author: Option<User>,
+
+    #[serde(deserialize_with = "util::deserialize_datetime")]
+    pub created: DateTime<FixedOffset>,
+
+    /// The size of the file, in bytes.
+    pub size: u64,
+
+    #[serde(default, rename(deserialize = "mimeType"))]
+    pub mime_type: Option<String>,
+
+    /// The URL of the file itself.
+    #[serde(default)]
+    pub content: Option<String>,
+
+    /// The URL of a thumbnail, for images.
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+}
+
+/// Represents the contents of an archive attached to an issue, like a zip file, as returned by a [get all metadata
+/// for an expanded attachment request][1].
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-attachments/#api-rest-api-3-attachment-id-expand-human-get
+#[derive(Clone, Debug, Deserialize)]
+pub struct AttachmentArchive {
+    #[serde(deserialize_with = "util::deserialize_id")]
+    pub id: String,
+
+    /// The file name of the archive.
+    pub name: String,
+
+    /// The files in the archive.  JIRA only lists the first few of a large archive; see `total_entry_count`.
+    pub entries: Vec<ArchiveEntry>,
+
+    #[serde(rename(deserialize = "totalEntryCount"))]
+    pub total_entry_count: u64,
+
+    #[serde(default, rename(deserialize = "mediaType"))]
+    pub media_type: Option<String>,
+}
+
+/// Represents one file within an `AttachmentArchive`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ArchiveEntry {
+    /// Where the file is within the archive, like `"logs/build.log"`.
+    pub path: String,
+
+    /// The position of the file within the archive, starting from 0.
+    pub index: u64,
+
+    /// The size of the file, formatted for people to read, like `"119 kB"`.
+    pub size: String,
+
+    #[serde(default, rename(deserialize = "mediaType"))]
+    pub media_type: Option<String>,
+
+    /// The name of the file, without the directories it is in.
+    pub label: String,
+}
@@ -0,0 +1,255 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+
+use crate::error::Error;
+
+/// How long before an OAuth 2.0 access token expires that it is refreshed, so that it does not expire in flight.
+const OAUTH2_EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// Represents an OAuth 2.0 access token, as handed out by Atlassian's [authorization server][1].
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/oauth-2-3lo-apps/
+#[derive(Clone, Debug)]
+pub struct OAuth2Token {
+    pub access_token: String,
+
+    /// When the token stops being accepted.  `None` means the token is only refreshed after JIRA rejects it.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Fetches a fresh OAuth 2.0 access token, typically by exchanging a refresh token with Atlassian's authorization
+/// server.
+///
+/// Requests rejected at the same time share a single refresh, so rotating refresh tokens are only exchanged once.  The
+/// async client runs the callback on tokio's blocking thread pool, so it may block, e.g. by using `reqwest::blocking`.
+pub type OAuth2RefreshCallback =
+    dyn Fn() -> Result<OAuth2Token, Box<dyn std::error::Error + Send + Sync>> + Send + Sync;
+
+/// Represents the ways a client can authenticate with JIRA.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::{Auth, OAuth2Token};
+///
+/// let cloud = Auth::basic("me@example.com", "api-token");
+/// let data_center = Auth::bearer("personal-access-token");
+/// let connect = Auth::oauth2(
+///     OAuth2Token { access_token: "initial".to_owned(), expires_at: None },
+///     || Ok(OAuth2Token { access_token: "refreshed".to_owned(), expires_at: None }),
+/// );
+/// ```
+#[derive(Clone)]
+pub enum Auth {
+    /// [Basic auth][1] with an email address and API token, as used by JIRA Cloud.
+    ///
+    /// [1]: https://developer.atlassian.com/cloud/jira/platform/basic-auth-for-rest-apis/
+    Basic { username: String, token: String },
+
+    /// A [personal access token][1], as used by JIRA Server and Data Center.
+    ///
+    /// [1]: https://confluence.atlassian.com/enterprise/using-personal-access-tokens-1026032365.html
+    Bearer(String),
+
+    /// An [OAuth 2.0 (3LO)][1] access token, which is refreshed with the callback when it expires or JIRA rejects it.
+    ///
+    /// OAuth 2.0 requests go through Atlassian's API gateway, so the client URL should be
+    /// `https://api.atlassian.com/ex/jira/{cloudid}` rather than the URL of your site.
+    ///
+    /// [1]: https://developer.atlassian.com/cloud/jira/platform/oauth-2-3lo-apps/
+    OAuth2 {
+        token: OAuth2Token,
+        refresh: Arc<OAuth2RefreshCallback>,
+    },
+
+    /// No authentication, for instances which allow anonymous access.
+    Anonymous,
+}
+
+impl Auth {
+    pub fn basic(username: &str, token: &str) -> Self {
+        Auth::Basic {
+            username: username.to_owned(),
+            token: token.to_owned(),
+        }
+    }
+
+    pub fn bearer(token: &str) -> Self {
+        Auth::Bearer(token.to_owned())
+    }
+
+    pub fn oauth2<F>(token: OAuth2Token, refresh: F) -> Self
+    where
+        F: Fn() -> Result<OAuth2Token, Box<dyn std::error::Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Auth::OAuth2 {
+            token,
+            refresh: Arc::new(refresh),
+        }
+    }
+}
+
+impl fmt::Debug for Auth {
+    /// Formats the auth scheme without any of its secrets.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .finish_non_exhaustive(),
+            Auth::Bearer(_) => f.debug_tuple("Bearer").finish_non_exhaustive(),
+            Auth::OAuth2 { token, .. } => f
+                .debug_struct("OAuth2")
+                .field("expires_at", &token.expires_at)
+                .finish_non_exhaustive(),
+            Auth::Anonymous => f.write_str("Anonymous"),
+        }
+    }
+}
+
+/// Produces the `Authorization` header for each request a client makes, refreshing OAuth 2.0 tokens as needed.
+pub(crate) struct Authenticator {
+    /// Only locked briefly, so that requests are not held up while a token is refreshed.
+    auth: Mutex<Auth>,
+
+    /// Held by the blocking client while it refreshes, so that concurrent refreshes of one token wait on the first.
+    refreshing: Mutex<()>,
+
+    /// Does the job of `refreshing` for the async client, without blocking the executor while waiting.
+    #[cfg(feature = "async")]
+    refreshing_async: tokio::sync::Mutex<()>,
+}
+
+impl Authenticator {
+    pub(crate) fn new(auth: Auth) -> Self {
+        Authenticator {
+            auth: Mutex::new(auth),
+            refreshing: Mutex::new(()),
+            #[cfg(feature = "async")]
+            refreshing_async: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Sets the `Authorization` header on a request, unless access is anonymous, refreshing an OAuth 2.0 token which is
+    /// about to expire first.
+    ///
+    /// Returns the OAuth 2.0 access token the request was authorized with, to hand to `refresh` if JIRA rejects it.
+    pub(crate) fn authorize(&self, headers: &mut HeaderMap) -> Result<Option<String>, Error> {
+        if let Some(expiring) = self.expiring_token() {
+            self.refresh(&expiring)?;
+        }
+
+        self.set_header(headers)
+    }
+
+    /// Does the job of `authorize` for the async client.
+    #[cfg(feature = "async")]
+    pub(crate) async fn authorize_async(
+        &self,
+        headers: &mut HeaderMap,
+    ) -> Result<Option<String>, Error> {
+        if let Some(expiring) = self.expiring_token() {
+            self.refresh_async(&expiring).await?;
+        }
+
+        self.set_header(headers)
+    }
+
+    /// Replaces an OAuth 2.0 access token which JIRA rejected, unless another request has replaced it already.
+    pub(crate) fn refresh(&self, stale: &str) -> Result<(), Error> {
+        let _refreshing = self.refreshing.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(refresh) = self.refresh_callback(stale) {
+            let token = refresh().map_err(Error::TokenRefresh)?;
+            self.replace_token(token);
+        }
+
+        Ok(())
+    }
+
+    /// Does the job of `refresh` for the async client.
+    #[cfg(feature = "async")]
+    pub(crate) async fn refresh_async(&self, stale: &str) -> Result<(), Error> {
+        let _refreshing = self.refreshing_async.lock().await;
+
+        if let Some(refresh) = self.refresh_callback(stale) {
+            let token = tokio::task::spawn_blocking(move || refresh())
+                .await
+                .map_err(|e| Error::TokenRefresh(Box::new(e)))?
+                .map_err(Error::TokenRefresh)?;
+            self.replace_token(token);
+        }
+
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Auth> {
+        self.auth.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the OAuth 2.0 access token in use if it is about to expire.
+    fn expiring_token(&self) -> Option<String> {
+        match &*self.lock() {
+            Auth::OAuth2 { token, .. }
+                if token.expires_at.is_some_and(|expires_at| {
+                    expires_at - chrono::Duration::from_std(OAUTH2_EXPIRY_MARGIN).unwrap()
+                        <= Utc::now()
+                }) =>
+            {
+                Some(token.access_token.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the callback which replaces `stale`, or `None` if the access token in use is no longer `stale`.
+    fn refresh_callback(&self, stale: &str) -> Option<Arc<OAuth2RefreshCallback>> {
+        match &*self.lock() {
+            Auth::OAuth2 { token, refresh } if token.access_token == stale => Some(refresh.clone()),
+            _ => None,
+        }
+    }
+
+    fn replace_token(&self, refreshed: OAuth2Token) {
+        if let Auth::OAuth2 { token, .. } = &mut *self.lock() {
+            *token = refreshed;
+        }
+    }
+
+    fn set_header(&self, headers: &mut HeaderMap) -> Result<Option<String>, Error> {
+        let (value, access_token) = match &*self.lock() {
+            Auth::Basic { username, token } => (
+                format!(
+                    "Basic {}",
+                    STANDARD.encode(format!("{}:{}", username, token))
+                ),
+                None,
+            ),
+            Auth::Bearer(token) => (format!("Bearer {}", token), None),
+            Auth::OAuth2 { token, .. } => (
+                format!("Bearer {}", token.access_token),
+                Some(token.access_token.clone()),
+            ),
+            Auth::Anonymous => return Ok(None),
+        };
+
+        // Tokens are handed to us by the caller, so unlike the base64 output they may contain characters that are not
+        // allowed in a header.
+        let mut header_value =
+            HeaderValue::from_str(&value).map_err(|_| Error::InvalidCredentials)?;
+        header_value.set_sensitive(true);
+        headers.insert(AUTHORIZATION, header_value);
+
+        Ok(access_token)
+    }
+}
@@ -0,0 +1,206 @@
+use std::{sync::Mutex, time::Duration};
+
+use reqwest::{
+    blocking,
+    header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE},
+    Certificate, Proxy,
+};
+
+use crate::auth::{Auth, Authenticator};
+use crate::error::Error;
+use crate::retry::RetryPolicy;
+use crate::version::ApiVersion;
+use crate::{base_url, RestClient};
+
+/// Configures and builds a `RestClient`, or an `AsyncRestClient` with the `async` feature.
+///
+/// `RestClient::new` covers the common case of JIRA Cloud with an API token; use this when you need a different
+/// auth scheme or more control over the connection.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use jimberlage_jira_client::{Auth, RestClientBuilder};
+///
+/// let client = RestClientBuilder::new("https://jira.example.com")
+///     .auth(Auth::bearer("personal-access-token"))
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("release-bot/1.0")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct RestClientBuilder {
+    url: String,
+    api_version: ApiVersion,
+    auth: Auth,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    root_certificates: Vec<Vec<u8>>,
+    user_agent: Option<String>,
+    retry_policy: RetryPolicy,
+}
+
+impl RestClientBuilder {
+    /// Starts configuring a client for the JIRA instance at the URL, with anonymous access.
+    pub fn new(url: &str) -> Self {
+        RestClientBuilder {
+            url: url.to_owned(),
+            api_version: ApiVersion::default(),
+            auth: Auth::Anonymous,
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            root_certificates: vec![],
+            user_agent: None,
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets the version of JIRA's REST API to talk to.  This defaults to V3; JIRA Server and Data Center need V2.
+    pub fn api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Sets how the client authenticates with JIRA.
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Sets the timeout for each request, from connecting until the response body has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for connecting to JIRA.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sends all requests through the proxy at the URL.  An invalid URL fails when the client is built.
+    pub fn proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_owned());
+        self
+    }
+
+    /// Trusts a PEM-encoded CA certificate in addition to the system's, for instances with an internal CA.  An
+    /// invalid certificate fails when the client is built.
+    pub fn add_root_certificate(mut self, pem: &[u8]) -> Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_owned());
+        self
+    }
+
+    /// Sets the policy for retrying requests that JIRA could not serve right now.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Builds a blocking client.
+    ///
+    /// This may fail if the proxy URL or a certificate is invalid, if the TLS backend cannot be initialized, or if
+    /// the resolver cannot load the system configuration.
+    pub fn build(self) -> Result<RestClient, Error> {
+        let mut builder = blocking::ClientBuilder::new().default_headers(default_headers());
+
+        // The blocking client has a default timeout of 30 seconds, which is kept unless a timeout was configured.
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = self.build_proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        for certificate in self.build_root_certificates()? {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        Ok(RestClient {
+            api_version: self.api_version,
+            base_url: base_url(&self.url, self.api_version),
+            client: builder.build()?,
+            auth: Authenticator::new(self.auth),
+            retry_policy: self.retry_policy,
+            field_catalog: Mutex::new(None),
+        })
+    }
+
+    /// Builds an async client.
+    ///
+    /// This may fail if the proxy URL or a certificate is invalid, if the TLS backend cannot be initialized, or if
+    /// the resolver cannot load the system configuration.
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> Result<crate::AsyncRestClient, Error> {
+        let mut builder = reqwest::ClientBuilder::new().default_headers(default_headers());
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = self.build_proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        for certificate in self.build_root_certificates()? {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+
+        Ok(crate::AsyncRestClient {
+            api_version: self.api_version,
+            base_url: base_url(&self.url, self.api_version),
+            client: builder.build()?,
+            auth: Authenticator::new(self.auth),
+            retry_policy: self.retry_policy,
+            field_catalog: Mutex::new(None),
+        })
+    }
+
+    fn build_proxy(&self) -> Result<Option<Proxy>, Error> {
+        Ok(match &self.proxy {
+            Some(url) => Some(Proxy::all(url)?),
+            None => None,
+        })
+    }
+
+    fn build_root_certificates(&self) -> Result<Vec<Certificate>, Error> {
+        let mut certificates = vec![];
+
+        for pem in &self.root_certificates {
+            certificates.push(Certificate::from_pem(pem)?);
+        }
+
+        Ok(certificates)
+    }
+}
+
+/// Builds the headers sent with every request, for both the blocking and async clients.
+///
+/// The `Authorization` header is not part of these, since OAuth 2.0 tokens can change over the life of a client.
+fn default_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+
+    headers
+}
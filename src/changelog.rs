@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::{util, User};
+
+/// Represents one change to an issue, made by one user at one time, as returned by a [get changelogs request][1].
+///
+/// A single change can touch several fields at once, like a transition that also sets the resolution.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::changelog::ChangelogEntry;
+///
+/// let entry: ChangelogEntry = serde_json::from_str(r#"{
+///   "id": "10042",
+///   "author": {"accountId": "5b10a2844c20165700ede21g", "displayName": "Jane Smith"},
+///   "created": "2023-05-10T14:23:01.123+0000",
+///   "items": [
+///     {"field": "status", "fieldtype": "jira", "from": "3", "fromString": "In Progress", "to": "10001", "toString": "Done"}
+///   ]
+/// }"#).unwrap();
+///
+/// assert_eq!(entry.status_change(), Some(("In Progress", "Done")));
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-changelog-get
+#[derive(Clone, Debug, Deserialize)]
+pub struct ChangelogEntry {
+    pub id: String,
+
+    /// The user who made the change.  This is missing for changes made by JIRA itself, like automation.
+    #[serde(default)]
+    pub author: Option<User>,
+
+    #[serde(deserialize_with = "util::deserialize_datetime")]
+    pub created: DateTime<FixedOffset>,
+
+    pub items: Vec<ChangeItem>,
+}
+
+impl ChangelogEntry {
+    /// Returns the names of the statuses the issue moved from and to, if this change was a status change.
+    pub fn status_change(&self) -> Option<(&str, &str)> {
+        self.items
+            .iter()
+            .find(|item| item.field == "status")
+            .and_then(|item| Some((item.from_string.as_deref()?, item.to_string.as_deref()?)))
+    }
+}
+
+/// Represents the change to a single field within a `ChangelogEntry`.
+///
+/// `from` and `to` hold the raw values, like status IDs, while `from_string` and `to_string` hold what a user would
+/// see, like status names.  Any of them can be missing, like when a field goes from empty to set.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ChangeItem {
+    /// The name of the field.  For custom fields, this is the display name rather than the ID.
+    pub field: String,
+
+    #[serde(default, rename(deserialize = "fieldId"))]
+    pub field_id: Option<String>,
+
+    /// Whether the field is a system field (`"jira"`) or a custom field (`"custom"`).
+    #[serde(default, rename(deserialize = "fieldtype"))]
+    pub field_type: Option<String>,
+
+    #[serde(default)]
+    pub from: Option<String>,
+
+    #[serde(default, rename(deserialize = "fromString"))]
+    pub from_string: Option<String>,
+
+    #[serde(default)]
+    pub to: Option<String>,
+
+    #[serde(default, rename(deserialize = "toString"))]
+    pub to_string: Option<String>,
+}
+
+/// Represents the changelog section of an issue, as returned with `IssueExpand::Changelog`.
+///
+/// JIRA only includes the most recent changes here; use `get_changelog` on the client to get all of them.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Changelog {
+    #[serde(rename(deserialize = "startAt"))]
+    pub start_at: u64,
+
+    #[serde(rename(deserialize = "maxResults"))]
+    pub max_results: u64,
+
+    pub total: u64,
+
+    pub histories: Vec<ChangelogEntry>,
+}
+
+/// The most issues JIRA accepts in a single bulk fetch request.
+pub(crate) const BULK_FETCH_MAX_ISSUES: usize = 1000;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BulkFetchRequest<'a> {
+    #[serde(rename(serialize = "issueIdsOrKeys"))]
+    pub(crate) issue_ids_or_keys: &'a [String],
+
+    #[serde(
+        rename(serialize = "nextPageToken"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BulkFetchResponse {
+    #[serde(rename(deserialize = "issueChangeLogs"))]
+    pub(crate) issue_changelogs: Vec<IssueChangelogs>,
+
+    #[serde(default, rename(deserialize = "nextPageToken"))]
+    pub(crate) next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct IssueChangelogs {
+    #[serde(rename(deserialize = "issueId"))]
+    pub(crate) issue_id: String,
+
+    #[serde(rename(deserialize = "changeHistories"))]
+    pub(crate) change_histories: Vec<ChangelogEntry>,
+}
+
+/// Adds a page of bulk fetch results to the changelogs collected so far, keyed by issue ID.
+///
+/// An issue's changelog can be split across pages, so entries for the same issue are appended rather than replaced.
+pub(crate) fn merge_bulk_fetch(
+    changelogs: &mut HashMap<String, Vec<ChangelogEntry>>,
+    response: BulkFetchResponse,
+) {
+    for issue in response.issue_changelogs {
+        changelogs
+            .entry(issue.issue_id)
+            .or_default()
+            .extend(issue.change_histories);
+    }
+}
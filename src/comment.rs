@@ -0,0 +1,135 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::value::Value as JSONValue;
+
+use crate::{util, TextBody, User};
+
+/// Represents a comment on an issue, as returned by a [get comments request][1].
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-comments/#api-rest-api-3-issue-issueidorkey-comment-get
+#[derive(Clone, Debug, Deserialize)]
+pub struct Comment {
+    pub id: String,
+
+    #[serde(default)]
+    pub author: Option<User>,
+
+    #[serde(default, rename(deserialize = "updateAuthor"))]
+    pub update_author: Option<User>,
+
+    /// The comment itself, in the format of the client's `ApiVersion`.
+    pub body: TextBody,
+
+    #[serde(deserialize_with = "util::deserialize_datetime")]
+    pub created: DateTime<FixedOffset>,
+
+    #[serde(deserialize_with = "util::deserialize_datetime")]
+    pub updated: DateTime<FixedOffset>,
+
+    /// Who can see the comment.  `None` means anyone who can see the issue.
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+
+    #[serde(default)]
+    pub properties: Vec<EntityProperty>,
+}
+
+/// Represents who can see a comment or worklog, beyond the permissions on the issue itself.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::comment::Visibility;
+///
+/// assert_eq!(
+///     serde_json::to_string(&Visibility::role("Administrators")).unwrap(),
+///     r#"{"type":"role","value":"Administrators"}"#
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Visibility {
+    #[serde(rename = "type")]
+    pub visibility_type: VisibilityType,
+
+    /// The name of the role or group.
+    pub value: String,
+}
+
+impl Visibility {
+    /// Restricts visibility to members of the project role with the name.
+    pub fn role(name: &str) -> Self {
+        Visibility {
+            visibility_type: VisibilityType::Role,
+            value: name.to_owned(),
+        }
+    }
+
+    /// Restricts visibility to members of the group with the name.
+    pub fn group(name: &str) -> Self {
+        Visibility {
+            visibility_type: VisibilityType::Group,
+            value: name.to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VisibilityType {
+    Role,
+    Group,
+}
+
+/// Represents an [entity property][1], which apps use to store their own data on JIRA objects like comments.
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/jira-entity-properties/
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct EntityProperty {
+    pub key: String,
+
+    pub value: JSONValue,
+}
+
+/// Represents the body of an [add comment request][1] or an [update comment request][2].
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::ApiVersion;
+/// use jimberlage_jira_client::comment::{CommentRequest, Visibility};
+///
+/// let request = CommentRequest {
+///     visibility: Some(Visibility::group("sre")),
+///     ..CommentRequest::new(ApiVersion::V2.plain_text("Deployed to production."))
+/// };
+///
+/// assert_eq!(
+///     serde_json::to_string(&request).unwrap(),
+///     r#"{"body":"Deployed to production.","visibility":{"type":"group","value":"sre"}}"#
+/// );
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-comments/#api-rest-api-3-issue-issueidorkey-comment-post
+/// [2]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-comments/#api-rest-api-3-issue-issueidorkey-comment-id-put
+#[derive(Clone, Debug, Serialize)]
+pub struct CommentRequest {
+    /// The comment itself, in the format of the client's `ApiVersion`.
+    pub body: TextBody,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<EntityProperty>,
+}
+
+impl CommentRequest {
+    /// Starts a request for a comment visible to anyone who can see the issue.
+    pub fn new(body: TextBody) -> Self {
+        CommentRequest {
+            body,
+            visibility: None,
+            properties: vec![],
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use std::{collections::HashMap, fmt, io, time::Duration};
+
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// Represents the structured error body JIRA sends back with most failed requests.
+///
+/// See ["Status codes"][1] for the shape of this body.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::JiraErrors;
+///
+/// let body = r#"{
+///   "errorMessages": ["Issue does not exist or you do not have permission to see it."],
+///   "errors": {"summary": "You must specify a summary of the issue."}
+/// }"#;
+/// let errors: JiraErrors = serde_json::from_str(body).unwrap();
+///
+/// assert_eq!(errors.errors.get("summary").map(String::as_str), Some("You must specify a summary of the issue."));
+/// assert_eq!(
+///     errors.to_string(),
+///     "Issue does not exist or you do not have permission to see it.; summary: You must specify a summary of the issue.".to_owned()
+/// );
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/intro/#status-codes
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct JiraErrors {
+    /// Errors which are not tied to a particular field.
+    #[serde(default, rename(deserialize = "errorMessages"))]
+    pub error_messages: Vec<String>,
+
+    /// Errors keyed by the ID of the field they apply to.
+    #[serde(default)]
+    pub errors: HashMap<String, String>,
+}
+
+impl JiraErrors {
+    /// Parses a JIRA error body, falling back to an empty set of errors if the body is not in the expected shape.
+    ///
+    /// Proxies and load balancers in front of JIRA will happily return HTML error pages, so this cannot assume the
+    /// body is JSON.
+    fn from_body(body: &str) -> Self {
+        serde_json::from_str(body).unwrap_or_default()
+    }
+
+    /// Returns true if JIRA did not report any errors.
+    pub fn is_empty(&self) -> bool {
+        self.error_messages.is_empty() && self.errors.is_empty()
+    }
+}
+
+impl fmt::Display for JiraErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Sort the field errors so that the message is stable, since they come out of a HashMap.
+        let mut field_errors = self.errors.iter().collect::<Vec<(&String, &String)>>();
+        field_errors.sort();
+
+        let messages = self
+            .error_messages
+            .iter()
+            .cloned()
+            .chain(
+                field_errors
+                    .into_iter()
+                    .map(|(field, message)| format!("{}: {}", field, message)),
+            )
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+/// Represents anything that can go wrong when talking to JIRA.
+#[derive(Debug)]
+pub enum Error {
+    /// The client could not be built, the request could not be sent, or the response could not be read.
+    Transport(reqwest::Error),
+
+    /// JIRA rejected the credentials (401), or the user is not allowed to do this (403).
+    Auth {
+        status: StatusCode,
+        errors: JiraErrors,
+    },
+
+    /// The resource does not exist, or the user does not have permission to see it (404).
+    NotFound(JiraErrors),
+
+    /// JIRA rejected the request body or parameters (400).  Field-level problems are in `errors.errors`.
+    Validation(JiraErrors),
+
+    /// JIRA is rate limiting this client (429).  `retry_after` is parsed from the `Retry-After` header, if present.
+    RateLimited { retry_after: Option<Duration> },
+
+    /// Any other unsuccessful status code.  The raw body is kept, since it may not be in JIRA's error format.
+    Status {
+        status: StatusCode,
+        errors: JiraErrors,
+        body: String,
+    },
+
+    /// The callback for refreshing an OAuth 2.0 token failed.
+    TokenRefresh(Box<dyn std::error::Error + Send + Sync>),
+
+    /// The credentials contain characters that cannot be sent in an HTTP header.
+    InvalidCredentials,
+
+    /// The response was successful, but the body was not in the shape we expected.  The raw body is kept for
+    /// debugging.
+    Deserialization {
+        source: serde_json::Error,
+        body: String,
+    },
+
+    /// A download could not be written out, or was cut off partway through.
+    Io(io::Error),
+
+    /// No field has the name.
+    UnknownField(String),
+
+    /// More than one field has the name, so it cannot be told which is meant.  The IDs of each are kept.
+    AmbiguousField { name: String, ids: Vec<String> },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(e) => write!(f, "error communicating with JIRA: {}", e),
+            Error::Auth { status, errors } if errors.is_empty() => {
+                write!(f, "JIRA denied access ({})", status)
+            }
+            Error::Auth { status, errors } => {
+                write!(f, "JIRA denied access ({}): {}", status, errors)
+            }
+            Error::NotFound(errors) if errors.is_empty() => write!(f, "not found in JIRA"),
+            Error::NotFound(errors) => write!(f, "not found in JIRA: {}", errors),
+            Error::Validation(errors) => write!(f, "JIRA rejected the request: {}", errors),
+            Error::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(
+                f,
+                "rate limited by JIRA; retry after {} seconds",
+                retry_after.as_secs()
+            ),
+            Error::RateLimited { retry_after: None } => write!(f, "rate limited by JIRA"),
+            Error::Status { status, errors, .. } if errors.is_empty() => {
+                write!(f, "JIRA responded with {}", status)
+            }
+            Error::Status { status, errors, .. } => {
+                write!(f, "JIRA responded with {}: {}", status, errors)
+            }
+            Error::TokenRefresh(e) => write!(f, "could not refresh the OAuth 2.0 token: {}", e),
+            Error::InvalidCredentials => {
+                write!(f, "credentials contain characters not allowed in a header")
+            }
+            Error::Deserialization { source, .. } => {
+                write!(f, "could not parse the response from JIRA: {}", source)
+            }
+            Error::Io(e) => write!(f, "could not download from JIRA: {}", e),
+            Error::UnknownField(name) => write!(f, "no field is named \"{}\"", name),
+            Error::AmbiguousField { name, ids } => write!(
+                f,
+                "more than one field is named \"{}\": {}",
+                name,
+                ids.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(e) => Some(e),
+            Error::TokenRefresh(e) => Some(e.as_ref()),
+            Error::Deserialization { source, .. } => Some(source),
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Transport(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Reads the `Retry-After` header, which JIRA may send as either a number of seconds or an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    // A date in the past means we can retry immediately.
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Represents a response from JIRA which has been read, but not yet checked for errors.
+pub(crate) struct RawResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: String,
+}
+
+impl RawResponse {
+    /// Turns an unsuccessful response into the matching `Error`, returning the body of a successful one.
+    pub(crate) fn into_body(self) -> Result<String, Error> {
+        check_response(self.status, &self.headers, self.body)
+    }
+
+    /// Turns the response into the matching `Error`, for callers which already know it was unsuccessful.
+    pub(crate) fn into_error(self) -> Error {
+        status_error(self.status, &self.headers, self.body)
+    }
+}
+
+/// Turns an unsuccessful response into the matching `Error`, passing the body through if the response succeeded.
+pub(crate) fn check_response(
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: String,
+) -> Result<String, Error> {
+    if status.is_success() {
+        return Ok(body);
+    }
+
+    Err(status_error(status, headers, body))
+}
+
+/// Turns an unsuccessful response into the matching `Error`.
+pub(crate) fn status_error(status: StatusCode, headers: &HeaderMap, body: String) -> Error {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Error::Auth {
+            status,
+            errors: JiraErrors::from_body(&body),
+        },
+        StatusCode::NOT_FOUND => Error::NotFound(JiraErrors::from_body(&body)),
+        StatusCode::BAD_REQUEST => Error::Validation(JiraErrors::from_body(&body)),
+        StatusCode::TOO_MANY_REQUESTS => Error::RateLimited {
+            retry_after: retry_after(headers),
+        },
+        _ => Error::Status {
+            status,
+            errors: JiraErrors::from_body(&body),
+            body,
+        },
+    }
+}
+
+/// Parses a successful response body, keeping the body around if it is not in the shape we expected.
+pub(crate) fn parse_body<T: DeserializeOwned>(body: String) -> Result<T, Error> {
+    serde_json::from_str(&body).map_err(|source| Error::Deserialization { source, body })
+}
+
+/// Parses a successful response body which may be empty, like from a request which only sometimes responds with
+/// content.
+pub(crate) fn parse_optional_body<T: DeserializeOwned>(body: String) -> Result<Option<T>, Error> {
+    if body.trim().is_empty() {
+        return Ok(None);
+    }
+
+    parse_body(body).map(Some)
+}
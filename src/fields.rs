@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+
+use crate::{util, Error, Field};
+
+/// Represents the value of a field on an issue, as read by accessors like `SearchIssue::summary`.
+///
+/// JIRA leaves fields out of an issue unless they were asked for, and sends `null` for fields which were asked for
+/// but have no value, so the two are told apart here.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::SearchIssue;
+/// use jimberlage_jira_client::fields::FieldValue;
+///
+/// let issue: SearchIssue = serde_json::from_str(r#"{
+///   "id": "10001",
+///   "key": "SRE-1",
+///   "fields": {"summary": "Rotate certificates", "assignee": null}
+/// }"#).unwrap();
+///
+/// assert_eq!(issue.summary().unwrap(), FieldValue::Value("Rotate certificates".to_owned()));
+/// assert!(matches!(issue.assignee().unwrap(), FieldValue::Null));
+/// assert!(!issue.reporter().unwrap().is_requested());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldValue<T> {
+    /// The field is not on the issue, because it was not asked for or does not exist.
+    NotRequested,
+
+    /// The field was asked for, and has no value.
+    Null,
+
+    Value(T),
+}
+
+impl<T> FieldValue<T> {
+    /// Returns whether JIRA sent the field at all, even if it has no value.
+    pub fn is_requested(&self) -> bool {
+        !matches!(self, FieldValue::NotRequested)
+    }
+
+    /// Returns the value, if there is one, for when it does not matter why there is not.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            FieldValue::Value(value) => Some(value),
+            FieldValue::NotRequested | FieldValue::Null => None,
+        }
+    }
+
+    pub fn as_ref(&self) -> FieldValue<&T> {
+        match self {
+            FieldValue::NotRequested => FieldValue::NotRequested,
+            FieldValue::Null => FieldValue::Null,
+            FieldValue::Value(value) => FieldValue::Value(value),
+        }
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> FieldValue<U> {
+        match self {
+            FieldValue::NotRequested => FieldValue::NotRequested,
+            FieldValue::Null => FieldValue::Null,
+            FieldValue::Value(value) => FieldValue::Value(f(value)),
+        }
+    }
+}
+
+/// Describes the value of a `Field`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct FieldSchema {
+    /// The type of the value, like `"string"`, `"number"`, `"user"` or `"array"`.
+    #[serde(rename(deserialize = "type"))]
+    pub field_type: String,
+
+    /// The type of each item, for arrays.
+    #[serde(default)]
+    pub items: Option<String>,
+
+    /// The ID of a built-in field, like `"summary"`.
+    #[serde(default)]
+    pub system: Option<String>,
+
+    /// The key of the plugin which provides a custom field, like
+    /// `"com.atlassian.jira.plugin.system.customfieldtypes:float"`.
+    #[serde(default)]
+    pub custom: Option<String>,
+
+    /// The number in the ID of a custom field, like 10016 for `customfield_10016`.
+    #[serde(default, rename(deserialize = "customId"))]
+    pub custom_id: Option<u64>,
+}
+
+/// Holds the fields of a JIRA site, as returned by `RestClient::get_field_catalog`, to find them by ID, JQL clause
+/// name or display name.
+///
+/// Custom fields have IDs like `customfield_10016`, which differ between sites, so code usually knows them by name.
+/// Names are not unique, though, so finding one which more than one field has is an error, rather than a guess.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::{Error, Field};
+/// use jimberlage_jira_client::fields::FieldCatalog;
+///
+/// let fields: Vec<Field> = serde_json::from_str(r#"[
+///   {"id": "summary", "name": "Summary", "clauseNames": ["summary"]},
+///   {"id": "customfield_10016", "name": "Story Points", "custom": true, "clauseNames": ["cf[10016]", "Story Points"]},
+///   {"id": "customfield_10020", "name": "Team", "custom": true, "clauseNames": ["cf[10020]", "Team"]},
+///   {"id": "customfield_10021", "name": "Team", "custom": true, "clauseNames": ["cf[10021]", "Team"]}
+/// ]"#).unwrap();
+/// let catalog = FieldCatalog::new(fields);
+///
+/// assert_eq!(catalog.resolve("story points").unwrap().id, "customfield_10016");
+/// assert_eq!(catalog.resolve("cf[10020]").unwrap().id, "customfield_10020");
+/// assert_eq!(catalog.resolve("summary").unwrap().name, "Summary");
+/// assert!(matches!(catalog.resolve("Team"), Err(Error::AmbiguousField { .. })));
+/// assert!(matches!(catalog.resolve("Sprint"), Err(Error::UnknownField(_))));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FieldCatalog {
+    fields: Vec<Field>,
+
+    /// The index of each field in `fields`, by ID.
+    ids: HashMap<String, usize>,
+
+    /// The indices of the fields in `fields` with each name, in lowercase.
+    names: HashMap<String, Vec<usize>>,
+}
+
+impl FieldCatalog {
+    pub fn new(fields: Vec<Field>) -> Self {
+        let mut ids = HashMap::new();
+        let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (index, field) in fields.iter().enumerate() {
+            ids.insert(field.id.clone(), index);
+            names
+                .entry(field.name.to_lowercase())
+                .or_default()
+                .push(index);
+        }
+
+        FieldCatalog { fields, ids, names }
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Finds the field with the ID, like `"summary"` or `"customfield_10016"`.
+    pub fn get(&self, id: &str) -> Option<&Field> {
+        self.ids.get(id).map(|index| &self.fields[*index])
+    }
+
+    /// Finds the field with the display name, ignoring case.
+    pub fn find_by_name(&self, name: &str) -> Result<&Field, Error> {
+        let indices = self
+            .names
+            .get(&name.to_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        self.only_field(name, indices.iter().map(|index| &self.fields[*index]))
+    }
+
+    /// Finds the field with the JQL clause name, like `"cf[10016]"` or `"Story Points"`, ignoring case.
+    pub fn find_by_clause_name(&self, clause_name: &str) -> Result<&Field, Error> {
+        if let Some(field) = custom_field_id(clause_name).and_then(|id| self.get(&id)) {
+            return Ok(field);
+        }
+
+        self.only_field(
+            clause_name,
+            self.fields.iter().filter(|field| {
+                field
+                    .clause_names
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(clause_name))
+            }),
+        )
+    }
+
+    /// Finds a field however it is referred to: by ID, by `cf[N]`, by display name, or by another JQL clause name,
+    /// trying each in that order.
+    pub fn resolve(&self, field: &str) -> Result<&Field, Error> {
+        if let Some(found) = self.get(field) {
+            return Ok(found);
+        }
+
+        match self.find_by_name(field) {
+            Err(Error::UnknownField(_)) => self.find_by_clause_name(field),
+            result => result,
+        }
+    }
+
+    fn only_field<'a, I>(&'a self, name: &str, mut fields: I) -> Result<&'a Field, Error>
+    where
+        I: Iterator<Item = &'a Field>,
+    {
+        let first = fields
+            .next()
+            .ok_or_else(|| Error::UnknownField(name.to_owned()))?;
+        let rest = fields.collect::<Vec<&Field>>();
+
+        if rest.is_empty() {
+            Ok(first)
+        } else {
+            Err(Error::AmbiguousField {
+                name: name.to_owned(),
+                ids: std::iter::once(first)
+                    .chain(rest)
+                    .map(|field| field.id.clone())
+                    .collect(),
+            })
+        }
+    }
+}
+
+/// Turns a clause name like `cf[10016]` into the ID of the custom field, `customfield_10016`.
+fn custom_field_id(clause_name: &str) -> Option<String> {
+    let number = clause_name
+        .strip_prefix("cf[")
+        .or_else(|| clause_name.strip_prefix("CF["))?
+        .strip_suffix(']')?;
+
+    if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+        Some(format!("customfield_{}", number))
+    } else {
+        None
+    }
+}
+
+/// Represents how urgent an issue is, like "High".
+#[derive(Clone, Debug, Deserialize)]
+pub struct Priority {
+    pub id: String,
+
+    pub name: String,
+
+    #[serde(default, rename(deserialize = "iconUrl"))]
+    pub icon_url: Option<String>,
+}
+
+/// Represents the type of an issue, like "Bug" or "Sub-task".
+#[derive(Clone, Debug, Deserialize)]
+pub struct IssueType {
+    pub id: String,
+
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub subtask: bool,
+
+    /// Where the type sits in the issue hierarchy: 0 for standard issues, -1 for sub-tasks and 1 for epics.  JIRA
+    /// Server and Data Center do not send this.
+    #[serde(default, rename(deserialize = "hierarchyLevel"))]
+    pub hierarchy_level: Option<i32>,
+}
+
+/// Represents a component of a project, like "Backend".
+#[derive(Clone, Debug, Deserialize)]
+pub struct Component {
+    pub id: String,
+
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Represents a version of a project, as found in the `fixVersions` and `versions` fields.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Version {
+    pub id: String,
+
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub released: bool,
+
+    #[serde(default)]
+    pub archived: bool,
+
+    /// The day the version is released, like `"2023-05-10"`.
+    #[serde(default, rename(deserialize = "releaseDate"))]
+    pub release_date: Option<String>,
+}
+
+/// Represents the estimates and time logged on an issue, as found in the `timetracking` field.
+///
+/// The strings are formatted by JIRA, like `"1w 2d"`; use `TimeTrackingSettings` to work with them, or the seconds
+/// alongside them.  Each part is missing if it has not been set.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TimeTracking {
+    #[serde(default, rename(deserialize = "originalEstimate"))]
+    pub original_estimate: Option<String>,
+
+    #[serde(default, rename(deserialize = "remainingEstimate"))]
+    pub remaining_estimate: Option<String>,
+
+    #[serde(default, rename(deserialize = "timeSpent"))]
+    pub time_spent: Option<String>,
+
+    #[serde(default, rename(deserialize = "originalEstimateSeconds"))]
+    pub original_estimate_seconds: Option<u64>,
+
+    #[serde(default, rename(deserialize = "remainingEstimateSeconds"))]
+    pub remaining_estimate_seconds: Option<u64>,
+
+    #[serde(default, rename(deserialize = "timeSpentSeconds"))]
+    pub time_spent_seconds: Option<u64>,
+}
+
+/// Wraps a timestamp field, so that it can be read with JIRA's format.
+#[derive(Deserialize)]
+pub(crate) struct Timestamp(
+    #[serde(deserialize_with = "util::deserialize_datetime")] pub(crate) DateTime<FixedOffset>,
+);
@@ -1,40 +1,153 @@
-use std::collections::HashMap;
-
-use base64::{
-    self,
-    engine::{GeneralPurpose, GeneralPurposeConfig},
-    Engine,
+use std::{
+    collections::HashMap,
+    io,
+    sync::{Arc, Mutex},
+    thread,
 };
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+
 use reqwest::{
     self,
-    blocking::{Client, ClientBuilder, RequestBuilder},
-    header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE},
+    blocking::{
+        multipart::{Form, Part},
+        Client, RequestBuilder, Response,
+    },
+    header::LOCATION,
+    StatusCode,
 };
-use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
-use serde_json::value::Value as JSONValue;
+use serde::{
+    de::DeserializeOwned,
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Serialize, Serializer,
+};
+use serde_json::{json, value::Value as JSONValue, Map};
 
+use self::attachment::{Attachment, AttachmentArchive};
+use self::auth::Authenticator;
+use self::changelog::{
+    BulkFetchRequest, BulkFetchResponse, Changelog, ChangelogEntry, BULK_FETCH_MAX_ISSUES,
+};
+use self::comment::{Comment, CommentRequest};
+use self::error::RawResponse;
+use self::fields::{
+    Component, FieldCatalog, FieldSchema, FieldValue, IssueType, Priority, TimeTracking, Timestamp,
+    Version,
+};
 use self::jql::JQLStatement;
+use self::link::{
+    IssueLink, IssueLinkRequest, IssueLinkType, IssueLinkTypesResponse, LinkedIssue, RemoteLink,
+    RemoteLinkIdentifier, RemoteLinkRequest,
+};
+use self::mapping::{JiraIssue, ResolvedFields};
+use self::time_tracking::TimeTrackingSettings;
+use self::transition::{Transition, TransitionRequest, TransitionsResponse};
+use self::worklog::{
+    UpdatedWorklogsPage, Worklog, WorklogIdsRequest, WorklogOptions, WorklogRequest,
+    WORKLOG_LIST_MAX_IDS,
+};
 
+pub mod adf;
+#[cfg(feature = "async")]
+mod async_client;
+pub mod attachment;
+mod auth;
+mod builder;
+pub mod changelog;
+pub mod comment;
+mod error;
+pub mod fields;
 pub mod jql;
+pub mod link;
+pub mod mapping;
+mod retry;
+pub mod time_tracking;
+pub mod transition;
 pub mod util;
+mod version;
+pub mod worklog;
+
+#[cfg(feature = "async")]
+pub use self::async_client::AsyncRestClient;
+pub use self::auth::{Auth, OAuth2RefreshCallback, OAuth2Token};
+pub use self::builder::RestClientBuilder;
+pub use self::error::{Error, JiraErrors};
+pub use self::retry::RetryPolicy;
+pub use self::version::{ApiVersion, TextBody, UserRef};
 
 /// Represents a field in JIRA, as returned by a [get fields request][1].
 ///
+/// Look fields up by name with a `FieldCatalog`.
+///
 /// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-fields/#api-rest-api-3-field-get
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Field {
     pub id: String,
 
+    /// The name JIRA shows for the field.  More than one custom field may have the same name.
     pub name: String,
+
+    /// Whether the field was added to the site, rather than being built into JIRA.
+    #[serde(default)]
+    pub custom: bool,
+
+    /// The type of the field's value.  A few built-in fields, like `thumbnail`, have none.
+    #[serde(default)]
+    pub schema: Option<FieldSchema>,
+
+    /// The names the field can be searched by in JQL, like `"cf[10016]"` and `"Story Points"`.
+    #[serde(default, rename(deserialize = "clauseNames"))]
+    pub clause_names: Vec<String>,
+
+    /// Whether the field can be searched by in JQL.
+    #[serde(default)]
+    pub searchable: bool,
+
+    /// Whether issues can be ordered by the field in JQL.
+    #[serde(default)]
+    pub orderable: bool,
+}
+
+/// Represents a user in JIRA, as returned in user fields, changelogs, comments and so on.
+///
+/// V3 of the API identifies users by `account_id`, while V2 on JIRA Server and Data Center identifies them by `name`
+/// and `key`; only the fields for the `ApiVersion` in use will be set.  Other fields may be hidden by the user's
+/// privacy settings.
+#[derive(Clone, Debug, Deserialize)]
+pub struct User {
+    #[serde(default, rename(deserialize = "accountId"))]
+    pub account_id: Option<String>,
+
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub key: Option<String>,
+
+    #[serde(default, rename(deserialize = "displayName"))]
+    pub display_name: Option<String>,
+
+    #[serde(default, rename(deserialize = "emailAddress"))]
+    pub email_address: Option<String>,
+
+    #[serde(default)]
+    pub active: Option<bool>,
 }
 
 /// Represents a user in JIRA, as returned by a [user search request][1].
 ///
 /// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-user-search/#api-rest-api-3-user-picker-get
-#[derive(Debug, Deserialize)]
-pub struct SearchUser {
-    #[serde(rename(deserialize = "accountId"))]
-    pub account_id: String,
+pub type SearchUser = User;
+
+impl User {
+    /// Returns a reference to this user for use in requests, preferring the account ID when JIRA sent one.
+    pub fn user_ref(&self) -> Option<UserRef> {
+        match (&self.account_id, &self.name) {
+            (Some(account_id), _) => Some(UserRef::AccountId(account_id.clone())),
+            (None, Some(name)) => Some(UserRef::Name(name.clone())),
+            (None, None) => None,
+        }
+    }
 }
 
 /// Represents a response from a [user search request][1].
@@ -45,9 +158,12 @@ pub struct SearchUserResponse {
     pub users: Vec<SearchUser>,
 }
 
-/// Represents an issue in JIRA, as returned by a [search request][1].
+/// Represents an issue in JIRA, as returned by a [search request][1] or a [get issue request][2].
+///
+/// The sections after `fields` are only present when they are requested with `IssueExpand`.
 ///
 /// [1]: https://docs.atlassian.com/software/jira/docs/api/REST/9.6.0/#api/2/search-searchUsingSearchRequest
+/// [2]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-get
 #[derive(Debug, Deserialize)]
 pub struct SearchIssue {
     pub id: String,
@@ -55,6 +171,105 @@ pub struct SearchIssue {
     pub key: String,
 
     pub fields: HashMap<String, JSONValue>,
+
+    /// The fields rendered as HTML, keyed by field ID.
+    #[serde(default, rename(deserialize = "renderedFields"))]
+    pub rendered_fields: Option<HashMap<String, JSONValue>>,
+
+    /// The display name of each field, keyed by field ID.
+    #[serde(default)]
+    pub names: Option<HashMap<String, String>>,
+
+    /// The schema of each field, keyed by field ID.
+    #[serde(default)]
+    pub schema: Option<HashMap<String, JSONValue>>,
+
+    /// The transitions the user can make from the issue's current status.
+    #[serde(default)]
+    pub transitions: Option<Vec<Transition>>,
+
+    /// The most recent changes to the issue.
+    #[serde(default)]
+    pub changelog: Option<Changelog>,
+
+    /// The fields that can be edited, and how.
+    #[serde(default, rename(deserialize = "editmeta"))]
+    pub edit_meta: Option<JSONValue>,
+}
+
+/// Represents a status in a workflow, like "In Progress".
+#[derive(Clone, Debug, Deserialize)]
+pub struct Status {
+    pub id: String,
+
+    pub name: String,
+
+    #[serde(default, rename(deserialize = "statusCategory"))]
+    pub status_category: Option<StatusCategory>,
+}
+
+/// Represents one of the categories JIRA groups statuses into: "To Do", "In Progress" or "Done".
+#[derive(Clone, Debug, Deserialize)]
+pub struct StatusCategory {
+    pub id: u64,
+
+    /// The stable identifier of the category, like `"indeterminate"` for "In Progress".
+    pub key: String,
+
+    pub name: String,
+}
+
+/// Represents a single issue, as returned by a [get issue request][1].
+///
+/// This is the same shape as an issue returned by search, so the two can be handled by the same code.
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-get
+pub type Issue = SearchIssue;
+
+/// Represents an optional section of an issue, which JIRA only includes in the response when asked to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IssueExpand {
+    RenderedFields,
+    Names,
+    Schema,
+    Transitions,
+    Changelog,
+    EditMeta,
+}
+
+impl IssueExpand {
+    /// Returns the value JIRA expects in the `expand` parameter for this section.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueExpand::RenderedFields => "renderedFields",
+            IssueExpand::Names => "names",
+            IssueExpand::Schema => "schema",
+            IssueExpand::Transitions => "transitions",
+            IssueExpand::Changelog => "changelog",
+            IssueExpand::EditMeta => "editmeta",
+        }
+    }
+}
+
+/// Builds the query string for a get issue request.
+///
+/// Empty lists are left out entirely, since JIRA treats an empty `fields` parameter as asking for no fields.
+fn get_issue_query(fields: &[String], expand: &[IssueExpand]) -> Vec<(&'static str, String)> {
+    let mut query = vec![];
+
+    if !fields.is_empty() {
+        query.push(("fields", fields.join(",")));
+    }
+    if !expand.is_empty() {
+        let expand = expand
+            .iter()
+            .map(|section| section.as_str())
+            .collect::<Vec<&str>>()
+            .join(",");
+        query.push(("expand", expand));
+    }
+
+    query
 }
 
 impl SearchIssue {
@@ -86,15 +301,154 @@ impl SearchIssue {
 
         None
     }
+
+    /// Reads a field by ID into `T`, like a custom field with a known shape.
+    ///
+    /// Returns `FieldValue::NotRequested` if the field is not on the issue, and `Error::Deserialization` if it is not
+    /// in the shape of `T`.
+    pub fn field<T: DeserializeOwned>(&self, field_id: &str) -> Result<FieldValue<T>, Error> {
+        match self.fields.get(field_id) {
+            None => Ok(FieldValue::NotRequested),
+            Some(JSONValue::Null) => Ok(FieldValue::Null),
+            Some(value) => T::deserialize(value)
+                .map(FieldValue::Value)
+                .map_err(|source| Error::Deserialization {
+                    source,
+                    body: value.to_string(),
+                }),
+        }
+    }
+
+    /// Reads a timestamp field by ID, in JIRA's format.
+    fn datetime_field(&self, field_id: &str) -> Result<FieldValue<DateTime<FixedOffset>>, Error> {
+        Ok(self
+            .field::<Timestamp>(field_id)?
+            .map(|timestamp| timestamp.0))
+    }
+
+    /// Reads the `summary` field.
+    pub fn summary(&self) -> Result<FieldValue<String>, Error> {
+        self.field("summary")
+    }
+
+    /// Reads the `status` field.
+    pub fn status(&self) -> Result<FieldValue<Status>, Error> {
+        self.field("status")
+    }
+
+    /// Reads the `assignee` field.
+    pub fn assignee(&self) -> Result<FieldValue<User>, Error> {
+        self.field("assignee")
+    }
+
+    /// Reads the `reporter` field.
+    pub fn reporter(&self) -> Result<FieldValue<User>, Error> {
+        self.field("reporter")
+    }
+
+    /// Reads the `priority` field.
+    pub fn priority(&self) -> Result<FieldValue<Priority>, Error> {
+        self.field("priority")
+    }
+
+    /// Reads the `issuetype` field.
+    pub fn issue_type(&self) -> Result<FieldValue<IssueType>, Error> {
+        self.field("issuetype")
+    }
+
+    /// Reads the `labels` field.
+    pub fn labels(&self) -> Result<FieldValue<Vec<String>>, Error> {
+        self.field("labels")
+    }
+
+    /// Reads the `components` field.
+    pub fn components(&self) -> Result<FieldValue<Vec<Component>>, Error> {
+        self.field("components")
+    }
+
+    /// Reads the `fixVersions` field.
+    pub fn fix_versions(&self) -> Result<FieldValue<Vec<Version>>, Error> {
+        self.field("fixVersions")
+    }
+
+    /// Reads the `created` field.
+    pub fn created(&self) -> Result<FieldValue<DateTime<FixedOffset>>, Error> {
+        self.datetime_field("created")
+    }
+
+    /// Reads the `updated` field.
+    pub fn updated(&self) -> Result<FieldValue<DateTime<FixedOffset>>, Error> {
+        self.datetime_field("updated")
+    }
+
+    /// Reads the `resolutiondate` field, which is `FieldValue::Null` for unresolved issues.
+    pub fn resolution_date(&self) -> Result<FieldValue<DateTime<FixedOffset>>, Error> {
+        self.datetime_field("resolutiondate")
+    }
+
+    /// Reads the `parent` field: the parent of a sub-task, or the epic or other issue above a standard issue.
+    pub fn parent(&self) -> Result<FieldValue<LinkedIssue>, Error> {
+        self.field("parent")
+    }
+
+    /// Reads the `subtasks` field.
+    pub fn subtasks(&self) -> Result<FieldValue<Vec<LinkedIssue>>, Error> {
+        self.field("subtasks")
+    }
+
+    /// Reads the `issuelinks` field.  Only the issue at the other end of each link is set.
+    pub fn issue_links(&self) -> Result<FieldValue<Vec<IssueLink>>, Error> {
+        self.field("issuelinks")
+    }
+
+    /// Reads the `timetracking` field.
+    pub fn time_tracking(&self) -> Result<FieldValue<TimeTracking>, Error> {
+        self.field("timetracking")
+    }
 }
 
 #[derive(Debug, Deserialize)]
-struct SearchResponse {
+pub(crate) struct SearchResponse {
     issues: Vec<SearchIssue>,
 }
 
+/// Represents a page of results from an endpoint which pages with `startAt` and `maxResults`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Page<T> {
+    #[serde(rename(deserialize = "startAt"))]
+    pub(crate) start_at: u64,
+
+    #[serde(default, rename(deserialize = "isLast"))]
+    pub(crate) is_last: Option<bool>,
+
+    #[serde(default)]
+    pub(crate) total: Option<u64>,
+
+    /// The results.  Some endpoints name this after what they return instead.
+    #[serde(alias = "comments", alias = "worklogs")]
+    pub(crate) values: Vec<T>,
+}
+
+impl<T> Page<T> {
+    /// Returns where the next page starts, or `None` if this is the last page.
+    pub(crate) fn next_start_at(&self) -> Option<u64> {
+        let next_start_at = self.start_at + self.values.len() as u64;
+
+        if self.values.is_empty() || self.is_last == Some(true) {
+            return None;
+        }
+        if let Some(total) = self.total {
+            if next_start_at >= total {
+                return None;
+            }
+        }
+
+        Some(next_start_at)
+    }
+}
+
 #[derive(Debug, Serialize)]
-struct SearchRequest {
+pub(crate) struct SearchRequest {
     fields: Vec<String>,
 
     jql: JQLStatement,
@@ -109,7 +463,9 @@ struct SearchRequest {
 #[derive(Clone, Debug)]
 pub enum IssueEditUpdateLabel {
     Add(String),
-    /* Remove would go here */
+    Remove(String),
+    /// Replaces all of the issue's labels.
+    Set(Vec<String>),
 }
 
 impl Serialize for IssueEditUpdateLabel {
@@ -117,111 +473,623 @@ impl Serialize for IssueEditUpdateLabel {
     where
         S: Serializer,
     {
+        let mut m = serializer.serialize_map(Some(1))?;
         match self {
-            IssueEditUpdateLabel::Add(label) => {
-                let mut m = serializer.serialize_map(Some(1))?;
-                m.serialize_entry("add", label)?;
-                m.end()
-            }
+            IssueEditUpdateLabel::Add(label) => m.serialize_entry("add", label)?,
+            IssueEditUpdateLabel::Remove(label) => m.serialize_entry("remove", label)?,
+            IssueEditUpdateLabel::Set(labels) => m.serialize_entry("set", labels)?,
         }
+        m.end()
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+/// Represents an operation on a field in the "update" key of an edit request.
+///
+/// What the value looks like depends on the field; see the `editmeta` of an issue (`IssueExpand::EditMeta`) for the
+/// operations and values each field supports.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::IssueEditUpdateOperation;
+/// use serde_json::json;
+///
+/// assert_eq!(
+///     serde_json::to_value(IssueEditUpdateOperation::Remove(json!({"name": "Backend"}))).unwrap(),
+///     json!({"remove": {"name": "Backend"}})
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub enum IssueEditUpdateOperation {
+    Add(JSONValue),
+    Set(JSONValue),
+    Remove(JSONValue),
+    Edit(JSONValue),
+}
+
+impl Serialize for IssueEditUpdateOperation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut m = serializer.serialize_map(Some(1))?;
+        match self {
+            IssueEditUpdateOperation::Add(value) => m.serialize_entry("add", value)?,
+            IssueEditUpdateOperation::Set(value) => m.serialize_entry("set", value)?,
+            IssueEditUpdateOperation::Remove(value) => m.serialize_entry("remove", value)?,
+            IssueEditUpdateOperation::Edit(value) => m.serialize_entry("edit", value)?,
+        }
+        m.end()
+    }
+}
+
+/// Represents the "update" key of an edit request, which applies operations to fields.
+///
+/// Operations on fields without a dedicated member, like custom fields, go in `other`, keyed by field ID.  Operations
+/// in `other` on a field which does have a dedicated member are sent after the member's own, so that the request does
+/// not name the field twice.
+#[derive(Clone, Debug, Default)]
 pub struct IssueEditUpdate {
     pub labels: Vec<IssueEditUpdateLabel>,
+
+    pub components: Vec<IssueEditUpdateOperation>,
+
+    pub fix_versions: Vec<IssueEditUpdateOperation>,
+
+    pub comment: Vec<IssueEditUpdateOperation>,
+
+    pub issuelinks: Vec<IssueEditUpdateOperation>,
+
+    pub other: HashMap<String, Vec<IssueEditUpdateOperation>>,
 }
 
-#[derive(Clone, Debug, Serialize)]
+impl IssueEditUpdate {
+    /// The IDs of the fields with a dedicated member.
+    const DEDICATED_FIELDS: [&'static str; 5] = [
+        "labels",
+        "components",
+        "fixVersions",
+        "comment",
+        "issuelinks",
+    ];
+
+    fn other_operations(&self, field: &str) -> &[IssueEditUpdateOperation] {
+        self.other.get(field).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+impl Serialize for IssueEditUpdate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut m = serializer.serialize_map(None)?;
+        serialize_operations(
+            &mut m,
+            "labels",
+            &self.labels,
+            self.other_operations("labels"),
+        )?;
+        serialize_operations(
+            &mut m,
+            "components",
+            &self.components,
+            self.other_operations("components"),
+        )?;
+        serialize_operations(
+            &mut m,
+            "fixVersions",
+            &self.fix_versions,
+            self.other_operations("fixVersions"),
+        )?;
+        serialize_operations(
+            &mut m,
+            "comment",
+            &self.comment,
+            self.other_operations("comment"),
+        )?;
+        serialize_operations(
+            &mut m,
+            "issuelinks",
+            &self.issuelinks,
+            self.other_operations("issuelinks"),
+        )?;
+        for (field, operations) in &self.other {
+            if !Self::DEDICATED_FIELDS.contains(&field.as_str()) {
+                m.serialize_entry(field, operations)?;
+            }
+        }
+        m.end()
+    }
+}
+
+/// Serializes the operations on a field with a dedicated member of `IssueEditUpdate`, followed by those for it in
+/// `other`.  Nothing is written if there are none.
+fn serialize_operations<M, T>(
+    m: &mut M,
+    field: &str,
+    operations: &[T],
+    other: &[IssueEditUpdateOperation],
+) -> Result<(), M::Error>
+where
+    M: SerializeMap,
+    T: Serialize,
+{
+    struct Operations<'a, T>(&'a [T], &'a [IssueEditUpdateOperation]);
+
+    impl<T: Serialize> Serialize for Operations<'_, T> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut seq = serializer.serialize_seq(Some(self.0.len() + self.1.len()))?;
+            for operation in self.0 {
+                seq.serialize_element(operation)?;
+            }
+            for operation in self.1 {
+                seq.serialize_element(operation)?;
+            }
+            seq.end()
+        }
+    }
+
+    if operations.is_empty() && other.is_empty() {
+        return Ok(());
+    }
+    m.serialize_entry(field, &Operations(operations, other))
+}
+
+/// Represents the body of an [edit issue request][1].
+///
+/// `fields` sets fields directly, replacing their values, while `update` applies operations to them.  A field can
+/// only appear in one of the two.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::{IssueEditRequest, IssueEditUpdate, IssueEditUpdateLabel, IssueEditUpdateOperation};
+/// use serde_json::json;
+///
+/// let mut request = IssueEditRequest {
+///     update: IssueEditUpdate {
+///         labels: vec![IssueEditUpdateLabel::Add("v2022.6.13".to_owned()), IssueEditUpdateLabel::Remove("v2022.5.10".to_owned())],
+///         fix_versions: vec![IssueEditUpdateOperation::Set(json!([{"name": "2022.6"}]))],
+///         ..IssueEditUpdate::default()
+///     },
+///     ..IssueEditRequest::default()
+/// };
+/// request.fields.insert("summary".to_owned(), json!("Rotate certificates"));
+///
+/// assert_eq!(
+///     serde_json::to_value(&request).unwrap(),
+///     json!({
+///         "fields": {"summary": "Rotate certificates"},
+///         "update": {
+///             "labels": [{"add": "v2022.6.13"}, {"remove": "v2022.5.10"}],
+///             "fixVersions": [{"set": [{"name": "2022.6"}]}]
+///         }
+///     })
+/// );
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-put
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct IssueEditRequest {
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, JSONValue>,
+
     pub update: IssueEditUpdate,
 }
 
+/// Represents the query parameters of an edit issue request.
+///
+/// Left as `None`, JIRA's defaults apply: users are notified, and only fields on the edit screen can be changed.
+#[derive(Clone, Debug, Default)]
+pub struct IssueEditOptions {
+    /// Whether to email watchers about the change.  Turning this off requires admin or project admin permissions.
+    pub notify_users: Option<bool>,
+
+    /// Whether fields hidden from the edit screen can be changed.  This requires admin permissions, or a Connect or
+    /// Forge app.
+    pub override_screen_security: Option<bool>,
+
+    /// Whether to respond with the edited issue, which `edit_issue` then returns.
+    pub return_issue: bool,
+}
+
+impl IssueEditOptions {
+    fn query(&self) -> Vec<(&'static str, bool)> {
+        let mut query = vec![];
+
+        if let Some(notify_users) = self.notify_users {
+            query.push(("notifyUsers", notify_users));
+        }
+        if let Some(override_screen_security) = self.override_screen_security {
+            query.push(("overrideScreenSecurity", override_screen_security));
+        }
+        if self.return_issue {
+            query.push(("returnIssue", true));
+        }
+
+        query
+    }
+}
+
+/// Represents the body of a [create issue request][1], or one issue in a [bulk create request][2].
+///
+/// Fields are set with the builder methods, or with `field` for anything else, like custom fields.  Which fields are
+/// required depends on the create screen configured for the project and issue type.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::{ApiVersion, IssueCreateRequest};
+/// use serde_json::json;
+///
+/// let request = IssueCreateRequest::new("SRE", "Task", "Rotate certificates")
+///     .description(ApiVersion::V2.plain_text("They expire on Friday."))
+///     .parent("SRE-1")
+///     .labels(&["certificates"])
+///     .field("customfield_10016", json!(3));
+///
+/// assert_eq!(
+///     serde_json::to_value(&request).unwrap(),
+///     json!({
+///         "fields": {
+///             "project": {"key": "SRE"},
+///             "issuetype": {"name": "Task"},
+///             "summary": "Rotate certificates",
+///             "description": "They expire on Friday.",
+///             "parent": {"key": "SRE-1"},
+///             "labels": ["certificates"],
+///             "customfield_10016": 3
+///         }
+///     })
+/// );
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-post
+/// [2]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-bulk-post
+#[derive(Clone, Debug, Serialize)]
+pub struct IssueCreateRequest {
+    fields: Map<String, JSONValue>,
+}
+
+impl IssueCreateRequest {
+    /// Starts a request for an issue in the project with the key, of the issue type with the name.
+    pub fn new(project_key: &str, issue_type_name: &str, summary: &str) -> Self {
+        IssueCreateRequest { fields: Map::new() }
+            .field("project", json!({ "key": project_key }))
+            .field("issuetype", json!({ "name": issue_type_name }))
+            .field("summary", json!(summary))
+    }
+
+    /// Sets the description.  This must be in the format of the client's `ApiVersion`.
+    pub fn description(self, description: TextBody) -> Self {
+        self.field("description", json!(description))
+    }
+
+    /// Sets the parent, by key, for subtasks and issues under an epic.
+    pub fn parent(self, parent_key: &str) -> Self {
+        self.field("parent", json!({ "key": parent_key }))
+    }
+
+    /// Sets the labels, replacing any already set.
+    pub fn labels(self, labels: &[&str]) -> Self {
+        self.field("labels", json!(labels))
+    }
+
+    /// Sets any field by ID, replacing it if it was already set.
+    pub fn field(mut self, field_id: &str, value: JSONValue) -> Self {
+        self.fields.insert(field_id.to_owned(), value);
+        self
+    }
+}
+
+/// Represents an issue JIRA created, as returned by a [create issue request][1].
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-post
+#[derive(Clone, Debug, Deserialize)]
+pub struct CreatedIssue {
+    pub id: String,
+
+    pub key: String,
+
+    #[serde(rename(deserialize = "self"))]
+    pub self_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct IssueBulkCreateRequest<'a> {
+    #[serde(rename(serialize = "issueUpdates"))]
+    issue_updates: &'a [IssueCreateRequest],
+}
+
+/// Represents an issue JIRA could not create as part of a bulk create request.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BulkCreateError {
+    pub status: u16,
+
+    #[serde(rename(deserialize = "elementErrors"))]
+    pub element_errors: JiraErrors,
+
+    /// The index of the issue in the request, starting at 0.
+    #[serde(rename(deserialize = "failedElementNumber"))]
+    pub failed_element_number: usize,
+}
+
+/// Represents the result of a [bulk create request][1].
+///
+/// JIRA creates whichever issues it can, so a bulk create can partially succeed.  Issues which could not be created
+/// are reported in `errors` rather than failing the whole request.
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-bulk-post
+#[derive(Clone, Debug, Deserialize)]
+pub struct BulkCreateResult {
+    #[serde(default)]
+    pub issues: Vec<CreatedIssue>,
+
+    #[serde(default)]
+    pub errors: Vec<BulkCreateError>,
+}
+
+impl BulkCreateResult {
+    /// Reads the result out of a bulk create response.
+    ///
+    /// JIRA responds with 400 when no issues could be created, but the body still has the per-issue errors, so that
+    /// case is a result rather than an `Error`.
+    fn from_response(response: RawResponse) -> Result<Self, Error> {
+        if response.status == StatusCode::BAD_REQUEST {
+            if let Ok(result) = serde_json::from_str::<BulkCreateResult>(&response.body) {
+                if !result.errors.is_empty() {
+                    return Ok(result);
+                }
+            }
+        }
+
+        error::parse_body(response.into_body()?)
+    }
+}
+
+/// The header JIRA requires on requests it would otherwise block as cross-site request forgery, like uploads.
+const ATLASSIAN_TOKEN: &str = "X-Atlassian-Token";
+
+/// The number of issues requested per page by `search_all`.
+const SEARCH_PAGE_SIZE: u64 = 100;
+
+/// The number of results requested per page from endpoints which page with `startAt` and `maxResults`, like
+/// `get_changelog`.
+const PAGE_SIZE: u64 = 100;
+
+/// Returns the field catalog a client keeps, if it has been fetched.
+fn cached_field_catalog(cache: &Mutex<Option<Arc<FieldCatalog>>>) -> Option<Arc<FieldCatalog>> {
+    cache.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn cache_field_catalog(cache: &Mutex<Option<Arc<FieldCatalog>>>, catalog: Arc<FieldCatalog>) {
+    *cache.lock().unwrap_or_else(|e| e.into_inner()) = Some(catalog);
+}
+
+/// Builds the base URL for JIRA's REST API from the URL of a JIRA instance.
+fn base_url(url: &str, api_version: ApiVersion) -> String {
+    format!("{}{}", url.trim_end_matches('/'), api_version.base_path())
+}
+
 /// Provides a reusable HTTP client for using parts of JIRA's [V3 REST API][1].
 ///
 /// It is currently suitable for my personal projects, and is not a complete implementation.  However, feel free to
 /// extend this to meet your needs.
 ///
+/// JIRA Server and Data Center only have the [V2 REST API][2]; use `RestClientBuilder::api_version` to talk to them.
+///
 /// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/intro/
+/// [2]: https://docs.atlassian.com/software/jira/docs/api/REST/9.6.0/
 pub struct RestClient {
+    api_version: ApiVersion,
     base_url: String,
     client: Client,
+    auth: Authenticator,
+    retry_policy: RetryPolicy,
+
+    /// The catalog `get_field_catalog` returns, once it has been fetched.
+    field_catalog: Mutex<Option<Arc<FieldCatalog>>>,
 }
 
 impl RestClient {
     /// Initialize a RestClient for the URL, with the given username and token.
     ///
     /// This may fail if the TLS backend cannot be initialized, or if the resolver cannot load the system
-    /// configuration.
-    pub fn new(url: &str, username: &str, token: &str) -> Result<Self, reqwest::Error> {
-        let base64_engine =
-            GeneralPurpose::new(&base64::alphabet::URL_SAFE, GeneralPurposeConfig::new());
-
-        let mut default_headers = HeaderMap::new();
-        default_headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        default_headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-        Self::add_auth_header(&mut default_headers, &base64_engine, username, token);
-
-        let client = ClientBuilder::new()
-            .default_headers(default_headers)
-            .build()?;
-
-        Ok(RestClient {
-            base_url: format!("{}/rest/api/3", url),
-            client,
+    /// configuration.  Use `RestClientBuilder` for other auth schemes and connection settings.
+    pub fn new(url: &str, username: &str, token: &str) -> Result<Self, Error> {
+        RestClientBuilder::new(url)
+            .auth(Auth::basic(username, token))
+            .build()
+    }
+
+    /// Returns the version of JIRA's REST API this client talks to.
+    pub fn api_version(&self) -> ApiVersion {
+        self.api_version
+    }
+
+    /// Sets the policy for retrying requests that JIRA could not serve right now.
+    ///
+    /// Clients use `RetryPolicy::default()` unless this is called, or `RestClientBuilder::retry_policy` is used.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sends the request, retrying according to the client's retry policy, and turns unsuccessful responses into the
+    /// matching `Error`.
+    ///
+    /// Returns the raw response, which callers can check with `RawResponse::into_body` if they have no special
+    /// handling for unsuccessful responses.  `idempotent` marks requests that are safe to retry even though their
+    /// method is not, like POSTs that only read.
+    fn execute(&self, request: RequestBuilder, idempotent: bool) -> Result<RawResponse, Error> {
+        Self::read_response(self.execute_streaming(request, idempotent)?)
+    }
+
+    /// Sends the request like `execute`, but leaves the body of the response unread, so that it can be streamed.
+    ///
+    /// The response is returned whatever its status; callers have to check it.
+    fn execute_streaming(
+        &self,
+        request: RequestBuilder,
+        idempotent: bool,
+    ) -> Result<Response, Error> {
+        let mut request = request.build()?;
+        let may_retry = self.retry_policy.may_retry(request.method(), idempotent);
+        let mut attempt = 1;
+        let mut refreshed_auth = false;
+
+        loop {
+            // Requests with streaming bodies cannot be cloned, and so can only be sent once.
+            let mut attempt_request = match request.try_clone() {
+                Some(attempt_request) => attempt_request,
+                None => break,
+            };
+            let sent_token = self.auth.authorize(attempt_request.headers_mut())?;
+
+            let outcome = self.client.execute(attempt_request);
+
+            // An expired OAuth 2.0 token is refreshed and the request sent again once, regardless of the retry policy.
+            if let (Ok(response), Some(sent_token)) = (&outcome, &sent_token) {
+                if response.status() == StatusCode::UNAUTHORIZED && !refreshed_auth {
+                    self.auth.refresh(sent_token)?;
+                    refreshed_auth = true;
+                    continue;
+                }
+            }
+
+            let delay = self.retry_policy.delay_before_retry(
+                attempt,
+                may_retry,
+                outcome
+                    .as_ref()
+                    .map(|response| (response.status(), response.headers())),
+            );
+
+            match delay {
+                Some(delay) => thread::sleep(delay),
+                None => return Ok(outcome?),
+            }
+
+            attempt += 1;
+        }
+
+        self.auth.authorize(request.headers_mut())?;
+        Ok(self.client.execute(request)?)
+    }
+
+    /// Reads the status, headers and body out of a response.
+    fn read_response(response: Response) -> Result<RawResponse, Error> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text()?;
+
+        Ok(RawResponse {
+            status,
+            headers,
+            body,
         })
     }
 
-    /// Encodes the auth header according to JIRA's [REST API V3 conventions][1].
+    /// Sends the request, turning unsuccessful responses into the matching `Error`.
     ///
-    /// [1]: https://developer.atlassian.com/cloud/jira/platform/basic-auth-for-rest-apis/
-    fn add_auth_header(
-        headers: &mut HeaderMap,
-        base64_engine: &GeneralPurpose,
-        username: &str,
-        token: &str,
-    ) {
-        let encoded = base64_engine.encode(format!("{}:{}", username, token));
-        // Unwrap here is considered safe since the method returns an error if the input is out of bounds, which would
-        // have to be a bug in the base64 library.
-        let mut auth_header_value =
-            HeaderValue::from_str(format!("Basic {}", encoded).as_str()).unwrap();
-        auth_header_value.set_sensitive(true);
-        headers.insert(AUTHORIZATION, auth_header_value);
+    /// Only idempotent methods are retried; see `RetryPolicy`.
+    fn send(&self, request: RequestBuilder) -> Result<String, Error> {
+        self.execute(request, false)?.into_body()
+    }
+
+    /// Sends the request, and parses the JSON body of a successful response.
+    fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, Error> {
+        error::parse_body(self.send(request)?)
     }
 
     /// Make a GET request to the specified path, using the URL, username, & token configured for the client.
     ///
     /// Returns a `reqwest::RequestBuilder` so that you can use any method available in the reqwest library.
     fn get(&self, path: &str) -> RequestBuilder {
-        self.client.get(format!("{}/{}", self.base_url, path))
+        self.client.get(format!("{}{}", self.base_url, path))
     }
 
     /// Make a POST request to the specified path, using the URL, username, & token configured for the client.
     ///
     /// Returns a `reqwest::RequestBuilder` so that you can use any method available in the reqwest library.
     fn post(&self, path: &str) -> RequestBuilder {
-        self.client.post(format!("{}/{}", self.base_url, path))
+        self.client.post(format!("{}{}", self.base_url, path))
     }
 
     /// Make a PUT request to the specified path, using the URL, username, & token configured for the client.
     ///
     /// Returns a `reqwest::RequestBuilder` so that you can use any method available in the reqwest library.
     fn put(&self, path: &str) -> RequestBuilder {
-        self.client.put(format!("{}/{}", self.base_url, path))
+        self.client.put(format!("{}{}", self.base_url, path))
+    }
+
+    /// Make a DELETE request to the specified path, using the URL, username, & token configured for the client.
+    ///
+    /// Returns a `reqwest::RequestBuilder` so that you can use any method available in the reqwest library.
+    fn delete(&self, path: &str) -> RequestBuilder {
+        self.client.delete(format!("{}{}", self.base_url, path))
+    }
+
+    /// Gets every result from an endpoint which pages with `startAt` and `maxResults`, visiting each page.
+    fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, Error> {
+        let mut start_at = 0u64;
+        let mut result = vec![];
+
+        loop {
+            let page: Page<T> = self.send_json(
+                self.get(path)
+                    .query(query)
+                    .query(&[("startAt", start_at), ("maxResults", PAGE_SIZE)]),
+            )?;
+            let next_start_at = page.next_start_at();
+            result.extend(page.values);
+
+            match next_start_at {
+                Some(next_start_at) => start_at = next_start_at,
+                None => break,
+            }
+        }
+
+        Ok(result)
     }
 
     /// Gets all configured fields for your JIRA instance.
     ///
     /// This is important because some critical functionality (story points, for example) are implemented as custom
-    /// fields, so this call is needed to match the ones for your integration by name.
+    /// fields, so this call is needed to match the ones for your integration by name.  `get_field_catalog` does the
+    /// matching.
     ///
     /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-fields/#api-rest-api-3-field-get
-    pub fn get_fields(&self) -> Result<Vec<Field>, reqwest::Error> {
-        let response = self.get("/field").send()?.error_for_status()?;
-        let fields: Vec<Field> = response.json()?;
+    pub fn get_fields(&self) -> Result<Vec<Field>, Error> {
+        self.send_json(self.get("/field"))
+    }
+
+    /// Gets all configured fields for your JIRA instance, to look up by name.
+    ///
+    /// Fields rarely change, so the catalog is only fetched the first time it is needed, and kept by the client after
+    /// that.  Use `refresh_field_catalog` to pick up fields added since.
+    pub fn get_field_catalog(&self) -> Result<Arc<FieldCatalog>, Error> {
+        if let Some(catalog) = cached_field_catalog(&self.field_catalog) {
+            return Ok(catalog);
+        }
 
-        Ok(fields)
+        self.refresh_field_catalog()
+    }
+
+    /// Gets all configured fields for your JIRA instance again, replacing the catalog the client keeps.
+    pub fn refresh_field_catalog(&self) -> Result<Arc<FieldCatalog>, Error> {
+        let catalog = Arc::new(FieldCatalog::new(self.get_fields()?));
+        cache_field_catalog(&self.field_catalog, catalog.clone());
+
+        Ok(catalog)
     }
 
     /// Search JIRA for issues matching the given JQL statement.
@@ -232,22 +1100,19 @@ impl RestClient {
     /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-search/#api-rest-api-3-search-post
     fn search(
         &self,
-        fields: &Vec<String>,
+        fields: &[String],
         jql: &JQLStatement,
         start_at: u64,
         max_results: u64,
-    ) -> Result<SearchResponse, reqwest::Error> {
-        let response = self
-            .post("/search")
-            .json(&SearchRequest {
-                fields: fields.to_vec(),
-                jql: jql.clone(),
-                start_at,
-                max_results,
-            })
-            .send()?
-            .error_for_status()?;
-        response.json()
+    ) -> Result<SearchResponse, Error> {
+        // Searching only reads issues, so it is safe to retry despite being a POST.
+        let request = self.post("/search").json(&SearchRequest {
+            fields: fields.to_vec(),
+            jql: jql.clone(),
+            start_at,
+            max_results,
+        });
+        error::parse_body(self.execute(request, true)?.into_body()?)
     }
 
     /// Search JIRA for issues matching the given JQL statement.
@@ -258,54 +1123,517 @@ impl RestClient {
     /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-search/#api-rest-api-3-search-post
     pub fn search_all(
         &self,
-        fields: &Vec<String>,
+        fields: &[String],
         jql: &JQLStatement,
-    ) -> Result<Vec<SearchIssue>, reqwest::Error> {
+    ) -> Result<Vec<SearchIssue>, Error> {
         let mut start_at = 0u64;
-        let max_results = 100u64;
         let mut result = vec![];
 
         loop {
-            let mut response = self.search(fields, jql, start_at, max_results)?;
+            let mut response = self.search(fields, jql, start_at, SEARCH_PAGE_SIZE)?;
             let num_responses = response.issues.len() as u64;
             result.append(&mut response.issues);
 
-            if num_responses < max_results {
+            if num_responses < SEARCH_PAGE_SIZE {
+                break;
+            }
+
+            start_at += num_responses
+        }
+
+        Ok(result)
+    }
+
+    /// Finds the IDs of the fields a `JiraIssue` type reads, looking up display names in `get_field_catalog` if it
+    /// uses any.
+    pub fn resolve_fields<T: JiraIssue>(&self) -> Result<ResolvedFields<T>, Error> {
+        if ResolvedFields::<T>::needs_names() {
+            ResolvedFields::new(&*self.get_field_catalog()?)
+        } else {
+            ResolvedFields::new(&FieldCatalog::default())
+        }
+    }
+
+    /// Search JIRA for issues matching the given JQL statement, reading each into a `JiraIssue` type.
+    ///
+    /// Only the fields the type reads are asked for.  Fields referred to by display name are looked up in the catalog
+    /// `get_field_catalog` keeps, so all fields are only fetched for the first search.
+    pub fn search_all_as<T: JiraIssue>(&self, jql: &JQLStatement) -> Result<Vec<T>, Error> {
+        let fields = self.resolve_fields::<T>()?;
+
+        self.search_all(fields.ids(), jql)?
+            .iter()
+            .map(|issue| T::from_issue(issue, &fields))
+            .collect()
+    }
+
+    /// Gets a single issue by its key or ID.
+    ///
+    /// If `fields` is empty, JIRA returns all navigable fields.  `expand` adds optional sections to the issue, like its
+    /// changelog.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-get
+    pub fn get_issue(
+        &self,
+        key: &str,
+        fields: &[String],
+        expand: &[IssueExpand],
+    ) -> Result<Issue, Error> {
+        let path = format!("/issue/{}", key);
+        self.send_json(self.get(&path).query(&get_issue_query(fields, expand)))
+    }
+
+    /// Gets the transitions the user can make from the issue's current status.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-transitions-get
+    pub fn get_transitions(&self, key: &str) -> Result<Vec<Transition>, Error> {
+        let path = format!("/issue/{}/transitions", key);
+        let response: TransitionsResponse = self.send_json(self.get(&path))?;
+
+        Ok(response.transitions)
+    }
+
+    /// Gets the transition which moves the issue into the status with the name, ignoring case.
+    ///
+    /// Returns `None` if the issue cannot move into that status from where it is.
+    pub fn get_transition_to_status(
+        &self,
+        key: &str,
+        status_name: &str,
+    ) -> Result<Option<Transition>, Error> {
+        let transitions = self.get_transitions(key)?;
+
+        Ok(transition::find_by_status(&transitions, status_name).cloned())
+    }
+
+    /// Moves an issue through its workflow with the transition, by ID.
+    ///
+    /// `fields` and `update` set fields on the transition screen, the same way as in `IssueEditRequest`.  `comment` is
+    /// added to the issue along with the transition.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-transitions-post
+    pub fn transition_issue(
+        &self,
+        key: &str,
+        transition_id: &str,
+        fields: &Map<String, JSONValue>,
+        update: &IssueEditUpdate,
+        comment: Option<&TextBody>,
+    ) -> Result<(), Error> {
+        let path = format!("/issue/{}/transitions", key);
+        self.send(self.post(&path).json(&TransitionRequest::new(
+            transition_id,
+            fields,
+            update,
+            comment,
+        )))?;
+
+        Ok(())
+    }
+
+    /// Gets every change made to an issue, oldest first, visiting each page for you.
+    ///
+    /// This endpoint is only on JIRA Cloud.  On JIRA Server and Data Center, use `get_issue` with
+    /// `IssueExpand::Changelog` instead.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-changelog-get
+    pub fn get_changelog(&self, key: &str) -> Result<Vec<ChangelogEntry>, Error> {
+        let path = format!("/issue/{}/changelog", key);
+        self.get_all_pages(&path, &[])
+    }
+
+    /// Gets every change made to each of the issues, by key or ID, visiting each page for you.
+    ///
+    /// The result is keyed by issue ID, even for issues which were asked for by key, since that is all JIRA returns.
+    /// Issues the user cannot see are left out.  This endpoint is only on JIRA Cloud.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-changelog-bulkfetch-post
+    pub fn bulk_fetch_changelogs(
+        &self,
+        keys: &[String],
+    ) -> Result<HashMap<String, Vec<ChangelogEntry>>, Error> {
+        let mut result = HashMap::new();
+
+        for chunk in keys.chunks(BULK_FETCH_MAX_ISSUES) {
+            let mut next_page_token = None;
+
+            loop {
+                // Fetching changelogs only reads them, so it is safe to retry despite being a POST.
+                let request = self.post("/changelog/bulkfetch").json(&BulkFetchRequest {
+                    issue_ids_or_keys: chunk,
+                    next_page_token,
+                });
+                let mut response: BulkFetchResponse =
+                    error::parse_body(self.execute(request, true)?.into_body()?)?;
+                next_page_token = response.next_page_token.take();
+                changelog::merge_bulk_fetch(&mut result, response);
+
+                if next_page_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Gets every comment on an issue, oldest first, visiting each page for you.
+    ///
+    /// Comment properties are included.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-comments/#api-rest-api-3-issue-issueidorkey-comment-get
+    pub fn get_comments(&self, key: &str) -> Result<Vec<Comment>, Error> {
+        let path = format!("/issue/{}/comment", key);
+        self.get_all_pages(&path, &[("expand", "properties")])
+    }
+
+    /// Adds a comment to an issue.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-comments/#api-rest-api-3-issue-issueidorkey-comment-post
+    pub fn add_comment(&self, key: &str, request: &CommentRequest) -> Result<Comment, Error> {
+        let path = format!("/issue/{}/comment", key);
+        self.send_json(self.post(&path).json(request))
+    }
+
+    /// Replaces the body, visibility and properties of a comment.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-comments/#api-rest-api-3-issue-issueidorkey-comment-id-put
+    pub fn update_comment(
+        &self,
+        key: &str,
+        comment_id: &str,
+        request: &CommentRequest,
+    ) -> Result<Comment, Error> {
+        let path = format!("/issue/{}/comment/{}", key, comment_id);
+        self.send_json(self.put(&path).json(request))
+    }
+
+    /// Deletes a comment.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-comments/#api-rest-api-3-issue-issueidorkey-comment-id-delete
+    pub fn delete_comment(&self, key: &str, comment_id: &str) -> Result<(), Error> {
+        let path = format!("/issue/{}/comment/{}", key, comment_id);
+        self.send(self.delete(&path))?;
+
+        Ok(())
+    }
+
+    /// Gets every worklog on an issue, oldest first, visiting each page for you.
+    ///
+    /// Worklog properties are included.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-issue-issueidorkey-worklog-get
+    pub fn get_worklogs(&self, key: &str) -> Result<Vec<Worklog>, Error> {
+        let path = format!("/issue/{}/worklog", key);
+        self.get_all_pages(&path, &[("expand", "properties")])
+    }
+
+    /// Gets a single worklog on an issue.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-issue-issueidorkey-worklog-id-get
+    pub fn get_worklog(&self, key: &str, worklog_id: &str) -> Result<Worklog, Error> {
+        let path = format!("/issue/{}/worklog/{}", key, worklog_id);
+        self.send_json(self.get(&path).query(&[("expand", "properties")]))
+    }
+
+    /// Logs time against an issue.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-issue-issueidorkey-worklog-post
+    pub fn add_worklog(
+        &self,
+        key: &str,
+        request: &WorklogRequest,
+        options: &WorklogOptions,
+    ) -> Result<Worklog, Error> {
+        let path = format!("/issue/{}/worklog", key);
+        self.send_json(
+            self.post(&path)
+                .query(&options.query(Some("reduceBy"))?)
+                .json(request),
+        )
+    }
+
+    /// Replaces the time spent, start, comment, visibility and properties of a worklog.
+    ///
+    /// `AdjustEstimate::Manual` is not supported, and fails with `Error::Validation` without sending the request.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-issue-issueidorkey-worklog-id-put
+    pub fn update_worklog(
+        &self,
+        key: &str,
+        worklog_id: &str,
+        request: &WorklogRequest,
+        options: &WorklogOptions,
+    ) -> Result<Worklog, Error> {
+        let path = format!("/issue/{}/worklog/{}", key, worklog_id);
+        self.send_json(self.put(&path).query(&options.query(None)?).json(request))
+    }
+
+    /// Deletes a worklog.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-issue-issueidorkey-worklog-id-delete
+    pub fn delete_worklog(
+        &self,
+        key: &str,
+        worklog_id: &str,
+        options: &WorklogOptions,
+    ) -> Result<(), Error> {
+        let path = format!("/issue/{}/worklog/{}", key, worklog_id);
+        self.send(
+            self.delete(&path)
+                .query(&options.query(Some("increaseBy"))?),
+        )?;
+
+        Ok(())
+    }
+
+    /// Gets every worklog, on any issue, created or updated since the time, visiting each page for you.
+    ///
+    /// This is meant for keeping a copy of worklogs in sync, like for billing; JIRA leaves out changes made in the
+    /// last minute, since those may not be visible everywhere yet.  Worklogs the user cannot see are left out.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-worklog-updated-get
+    /// and https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-worklog-list-post
+    pub fn get_worklogs_updated_since<Tz: TimeZone>(
+        &self,
+        since: &DateTime<Tz>,
+    ) -> Result<Vec<Worklog>, Error> {
+        let mut ids = vec![];
+        let mut since = since.timestamp_millis();
+
+        loop {
+            let page: UpdatedWorklogsPage =
+                self.send_json(self.get("/worklog/updated").query(&[("since", since)]))?;
+            ids.extend(page.values.iter().map(|worklog| worklog.worklog_id));
+
+            if page.last_page {
                 break;
             }
+            since = page.until;
+        }
 
-            start_at = start_at + num_responses
+        let mut result = vec![];
+        for chunk in ids.chunks(WORKLOG_LIST_MAX_IDS) {
+            // Listing worklogs only reads them, so it is safe to retry despite being a POST.
+            let request = self
+                .post("/worklog/list")
+                .query(&[("expand", "properties")])
+                .json(&WorklogIdsRequest { ids: chunk });
+            let mut worklogs: Vec<Worklog> =
+                error::parse_body(self.execute(request, true)?.into_body()?)?;
+            result.append(&mut worklogs);
         }
 
         Ok(result)
     }
 
+    /// Gets how the instance turns days and weeks into hours, for parsing and formatting durations.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-time-tracking/#api-rest-api-3-configuration-timetracking-options-get
+    pub fn get_time_tracking_settings(&self) -> Result<TimeTrackingSettings, Error> {
+        self.send_json(self.get("/configuration/timetracking/options"))
+    }
+
+    /// Attaches a file to an issue.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-attachments/#api-rest-api-3-issue-issueidorkey-attachments-post
+    pub fn add_attachment(
+        &self,
+        key: &str,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> Result<Attachment, Error> {
+        let path = format!("/issue/{}/attachments", key);
+        let form = Form::new().part("file", Part::bytes(content).file_name(file_name.to_owned()));
+        // JIRA rejects uploads without this header, as protection against cross-site request forgery.
+        let [attachment]: [Attachment; 1] = self.send_json(
+            self.post(&path)
+                .header(ATLASSIAN_TOKEN, "no-check")
+                .multipart(form),
+        )?;
+
+        Ok(attachment)
+    }
+
+    /// Gets the metadata of an attachment, like its file name and size.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-attachments/#api-rest-api-3-attachment-id-get
+    pub fn get_attachment_meta(&self, attachment_id: &str) -> Result<Attachment, Error> {
+        let path = format!("/attachment/{}", attachment_id);
+        self.send_json(self.get(&path))
+    }
+
+    /// Writes the content of an attachment to `out` as it downloads, so that large files are not held in memory.
+    ///
+    /// Returns the number of bytes written.  This endpoint is only on JIRA Cloud.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-attachments/#api-rest-api-3-attachment-content-id-get
+    pub fn download_attachment<W: io::Write + ?Sized>(
+        &self,
+        attachment_id: &str,
+        out: &mut W,
+    ) -> Result<u64, Error> {
+        let path = format!("/attachment/content/{}", attachment_id);
+        let mut response = self.execute_streaming(self.get(&path), false)?;
+
+        if !response.status().is_success() {
+            return Err(Self::read_response(response)?.into_error());
+        }
+
+        let written = io::copy(&mut response, out)?;
+        out.flush()?;
+
+        Ok(written)
+    }
+
+    /// Lists the files in an archive attached to an issue, like a zip file.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-attachments/#api-rest-api-3-attachment-id-expand-human-get
+    pub fn expand_attachment(&self, attachment_id: &str) -> Result<AttachmentArchive, Error> {
+        let path = format!("/attachment/{}/expand/human", attachment_id);
+        self.send_json(self.get(&path))
+    }
+
+    /// Deletes an attachment.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-attachments/#api-rest-api-3-attachment-id-delete
+    pub fn delete_attachment(&self, attachment_id: &str) -> Result<(), Error> {
+        let path = format!("/attachment/{}", attachment_id);
+        self.send(self.delete(&path))?;
+
+        Ok(())
+    }
+
+    /// Links two issues.  Start the request with `IssueLinkType::outward_link` or `IssueLinkType::inward_link`.
+    ///
+    /// Returns the ID of the new link, if JIRA said what it was.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-links/#api-rest-api-3-issuelink-post
+    pub fn create_issue_link(&self, request: &IssueLinkRequest) -> Result<Option<String>, Error> {
+        let response = self.execute(self.post("/issueLink").json(request), false)?;
+        // JIRA responds with 201 Created and no body, so the ID is only in the URL of the new link.
+        let id = response
+            .headers
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(link::id_from_location);
+        response.into_body()?;
+
+        Ok(id)
+    }
+
+    /// Gets a link between two issues.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-links/#api-rest-api-3-issuelink-linkid-get
+    pub fn get_issue_link(&self, link_id: &str) -> Result<IssueLink, Error> {
+        let path = format!("/issueLink/{}", link_id);
+        self.send_json(self.get(&path))
+    }
+
+    /// Deletes a link between two issues.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-links/#api-rest-api-3-issuelink-linkid-delete
+    pub fn delete_issue_link(&self, link_id: &str) -> Result<(), Error> {
+        let path = format!("/issueLink/{}", link_id);
+        self.send(self.delete(&path))?;
+
+        Ok(())
+    }
+
+    /// Gets every kind of link issues can have between them, like "Blocks".
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-link-types/#api-rest-api-3-issuelinktype-get
+    pub fn get_issue_link_types(&self) -> Result<Vec<IssueLinkType>, Error> {
+        let response: IssueLinkTypesResponse = self.send_json(self.get("/issueLinkType"))?;
+
+        Ok(response.issue_link_types)
+    }
+
+    /// Gets the links from an issue to things outside of JIRA, like pull requests.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-remote-links/#api-rest-api-3-issue-issueidorkey-remotelink-get
+    pub fn get_remote_links(&self, key: &str) -> Result<Vec<RemoteLink>, Error> {
+        let path = format!("/issue/{}/remotelink", key);
+        self.send_json(self.get(&path))
+    }
+
+    /// Links an issue to something outside of JIRA, or updates the link with the same `global_id` if the issue
+    /// already has one.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-remote-links/#api-rest-api-3-issue-issueidorkey-remotelink-post
+    pub fn upsert_remote_link(
+        &self,
+        key: &str,
+        request: &RemoteLinkRequest,
+    ) -> Result<RemoteLinkIdentifier, Error> {
+        let path = format!("/issue/{}/remotelink", key);
+        self.send_json(self.post(&path).json(request))
+    }
+
+    /// Deletes a remote link by its ID.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-remote-links/#api-rest-api-3-issue-issueidorkey-remotelink-linkid-delete
+    pub fn delete_remote_link(&self, key: &str, link_id: &str) -> Result<(), Error> {
+        let path = format!("/issue/{}/remotelink/{}", key, link_id);
+        self.send(self.delete(&path))?;
+
+        Ok(())
+    }
+
+    /// Deletes the remote link with the global ID.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-remote-links/#api-rest-api-3-issue-issueidorkey-remotelink-delete
+    pub fn delete_remote_link_by_global_id(&self, key: &str, global_id: &str) -> Result<(), Error> {
+        let path = format!("/issue/{}/remotelink", key);
+        self.send(self.delete(&path).query(&[("globalId", global_id)]))?;
+
+        Ok(())
+    }
+
     /// Searches for users in JIRA by key or email.
     ///
     /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-user-search/#api-rest-api-3-user-picker-get
-    pub fn search_users(&self, query: &str) -> Result<SearchUserResponse, reqwest::Error> {
-        let response = self
-            .get("/user/picker")
-            .query(&[("query", query)])
-            .send()?
-            .error_for_status()?;
-        response.json()
+    pub fn search_users(&self, query: &str) -> Result<SearchUserResponse, Error> {
+        self.send_json(self.get("/user/picker").query(&[("query", query)]))
+    }
+
+    /// Creates an issue.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-post
+    pub fn create_issue(&self, request: &IssueCreateRequest) -> Result<CreatedIssue, Error> {
+        self.send_json(self.post("/issue").json(request))
+    }
+
+    /// Creates up to 50 issues at once.
+    ///
+    /// Issues which JIRA could not create are reported in the result, alongside the ones it did create.
+    ///
+    /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-bulk-post
+    pub fn create_issues_bulk(
+        &self,
+        requests: &[IssueCreateRequest],
+    ) -> Result<BulkCreateResult, Error> {
+        let request = self.post("/issue/bulk").json(&IssueBulkCreateRequest {
+            issue_updates: requests,
+        });
+        BulkCreateResult::from_response(self.execute(request, false)?)
     }
 
     /// Edits an issue.
     ///
-    /// For now, this only supports the methods in the "update" key of the request, but could be extended.
+    /// Returns the edited issue if `options.return_issue` is set, and `None` otherwise.
     ///
     /// See https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-put
-    pub fn edit_issue(&self, key: &str, update: &IssueEditUpdate) -> Result<(), reqwest::Error> {
+    pub fn edit_issue(
+        &self,
+        key: &str,
+        request: &IssueEditRequest,
+        options: &IssueEditOptions,
+    ) -> Result<Option<Issue>, Error> {
         let path = format!("/issue/{}", key);
-        let response = self
-            .put(&path)
-            .json(&IssueEditRequest {
-                update: update.clone(),
-            })
-            .send()?
-            .error_for_status()?;
-        response.json()
+        let body = self.send(self.put(&path).query(&options.query()).json(request))?;
+
+        // Unless the issue is asked for, JIRA responds with 204 No Content, so there is no body to parse.
+        error::parse_optional_body(body)
     }
 }
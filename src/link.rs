@@ -0,0 +1,320 @@
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+use serde_json::{json, value::Value as JSONValue, Map};
+
+use crate::comment::CommentRequest;
+use crate::util;
+
+/// Represents a kind of link between issues, like "Blocks", as returned by a [get issue link types request][1].
+///
+/// Each link type reads one way from each end: for "Blocks", the outward description is "blocks" and the inward one
+/// is "is blocked by".  Build requests with `outward_link` or `inward_link` so that they read the way you say them,
+/// rather than working out which issue JIRA wants on which side.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::link::IssueLinkType;
+///
+/// let blocks = IssueLinkType::new("Blocks", "is blocked by", "blocks");
+///
+/// // SRE-1 blocks SRE-2, which is the same as SRE-2 is blocked by SRE-1.
+/// assert_eq!(
+///     serde_json::to_value(blocks.outward_link("SRE-1", "SRE-2")).unwrap(),
+///     serde_json::to_value(blocks.inward_link("SRE-2", "SRE-1")).unwrap()
+/// );
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-link-types/#api-rest-api-3-issuelinktype-get
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct IssueLinkType {
+    #[serde(default)]
+    pub id: Option<String>,
+
+    pub name: String,
+
+    /// How the link reads from the issue on the inward end, like "is blocked by".
+    pub inward: String,
+
+    /// How the link reads from the issue on the outward end, like "blocks".
+    pub outward: String,
+}
+
+impl IssueLinkType {
+    /// Describes a link type by name, for when its ID is not needed.
+    pub fn new(name: &str, inward: &str, outward: &str) -> Self {
+        IssueLinkType {
+            id: None,
+            name: name.to_owned(),
+            inward: inward.to_owned(),
+            outward: outward.to_owned(),
+        }
+    }
+
+    /// Starts a request for a link which reads "`issue_key` (outward description) `other_key`", like "SRE-1 blocks
+    /// SRE-2".
+    pub fn outward_link(&self, issue_key: &str, other_key: &str) -> IssueLinkRequest {
+        // JIRA calls the issue the outward description reads from the inward issue.
+        IssueLinkRequest {
+            link_type: self.clone(),
+            inward_issue_key: issue_key.to_owned(),
+            outward_issue_key: other_key.to_owned(),
+            comment: None,
+        }
+    }
+
+    /// Starts a request for a link which reads "`issue_key` (inward description) `other_key`", like "SRE-2 is blocked
+    /// by SRE-1".
+    pub fn inward_link(&self, issue_key: &str, other_key: &str) -> IssueLinkRequest {
+        self.outward_link(other_key, issue_key)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct IssueLinkTypesResponse {
+    #[serde(rename(deserialize = "issueLinkTypes"))]
+    pub(crate) issue_link_types: Vec<IssueLinkType>,
+}
+
+/// Finds the link type with the name, ignoring case.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::link::{self, IssueLinkType};
+///
+/// let types = vec![IssueLinkType::new("Blocks", "is blocked by", "blocks")];
+///
+/// assert_eq!(link::find_by_name(&types, "blocks").map(|t| t.outward.as_str()), Some("blocks"));
+/// assert!(link::find_by_name(&types, "Duplicate").is_none());
+/// ```
+pub fn find_by_name<'a>(link_types: &'a [IssueLinkType], name: &str) -> Option<&'a IssueLinkType> {
+    link_types
+        .iter()
+        .find(|link_type| link_type.name.eq_ignore_ascii_case(name))
+}
+
+/// Represents the body of a [create issue link request][1].  Start one with `IssueLinkType::outward_link` or
+/// `IssueLinkType::inward_link`.
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-links/#api-rest-api-3-issuelink-post
+#[derive(Clone, Debug)]
+pub struct IssueLinkRequest {
+    pub link_type: IssueLinkType,
+
+    /// The issue the outward description reads from.
+    pub inward_issue_key: String,
+
+    /// The issue the inward description reads from.
+    pub outward_issue_key: String,
+
+    /// A comment to add along with the link.  Properties are not supported here.
+    pub comment: Option<CommentRequest>,
+}
+
+impl Serialize for IssueLinkRequest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut m = serializer.serialize_map(None)?;
+        m.serialize_entry("type", &json!({ "name": self.link_type.name }))?;
+        m.serialize_entry("inwardIssue", &json!({ "key": self.inward_issue_key }))?;
+        m.serialize_entry("outwardIssue", &json!({ "key": self.outward_issue_key }))?;
+        if let Some(comment) = &self.comment {
+            m.serialize_entry("comment", comment)?;
+        }
+        m.end()
+    }
+}
+
+/// Represents a link between two issues, as returned by a [get issue link request][1].
+///
+/// This reads "`inward_issue` (outward description) `outward_issue`", like "SRE-1 blocks SRE-2".  In the
+/// `issuelinks` field of an issue, only the issue at the other end is set.
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-links/#api-rest-api-3-issuelink-linkid-get
+#[derive(Clone, Debug, Deserialize)]
+pub struct IssueLink {
+    pub id: String,
+
+    #[serde(rename(deserialize = "type"))]
+    pub link_type: IssueLinkType,
+
+    #[serde(default, rename(deserialize = "inwardIssue"))]
+    pub inward_issue: Option<LinkedIssue>,
+
+    #[serde(default, rename(deserialize = "outwardIssue"))]
+    pub outward_issue: Option<LinkedIssue>,
+}
+
+/// Represents another issue an issue refers to, like one at the end of an `IssueLink`, a parent or a sub-task, with a
+/// few of its fields, like `summary` and `status`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LinkedIssue {
+    pub id: String,
+
+    pub key: String,
+
+    #[serde(default)]
+    pub fields: Map<String, JSONValue>,
+}
+
+/// Finds the ID of a new issue link in the `Location` header JIRA responds with, since there is no response body.
+pub(crate) fn id_from_location(location: &str) -> Option<String> {
+    location
+        .trim_end_matches('/')
+        .rsplit_once('/')
+        .map(|(_, id)| id.to_owned())
+        .filter(|id| !id.is_empty())
+}
+
+/// Represents a link from an issue to something outside of JIRA, like a pull request, as returned by a [get remote
+/// issue links request][1].
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-remote-links/#api-rest-api-3-issue-issueidorkey-remotelink-get
+#[derive(Clone, Debug, Deserialize)]
+pub struct RemoteLink {
+    /// JIRA sends this as a number; it is a string here, like other IDs.
+    #[serde(deserialize_with = "util::deserialize_id")]
+    pub id: String,
+
+    /// The ID of the linked object, unique to the system it is in.  See `RemoteLinkRequest::global_id`.
+    #[serde(default, rename(deserialize = "globalId"))]
+    pub global_id: Option<String>,
+
+    #[serde(default)]
+    pub application: Option<RemoteLinkApplication>,
+
+    /// How the issue relates to the object, like "mentioned in".
+    #[serde(default)]
+    pub relationship: Option<String>,
+
+    pub object: RemoteObject,
+}
+
+/// Represents the body of a [create or update remote issue link request][1].
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::link::{RemoteLinkApplication, RemoteLinkRequest};
+///
+/// let request = RemoteLinkRequest {
+///     application: Some(RemoteLinkApplication::new("com.github", "GitHub")),
+///     relationship: Some("fixed by".to_owned()),
+///     ..RemoteLinkRequest::new(
+///         "github=acme/api/pull/42",
+///         "https://github.com/acme/api/pull/42",
+///         "Retry rate limited requests",
+///     )
+/// };
+///
+/// assert_eq!(
+///     serde_json::to_string(&request).unwrap(),
+///     r#"{"globalId":"github=acme/api/pull/42","application":{"type":"com.github","name":"GitHub"},"relationship":"fixed by","object":{"url":"https://github.com/acme/api/pull/42","title":"Retry rate limited requests"}}"#
+/// );
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-remote-links/#api-rest-api-3-issue-issueidorkey-remotelink-post
+#[derive(Clone, Debug, Serialize)]
+pub struct RemoteLinkRequest {
+    /// The ID of the linked object, unique to the system it is in, like `"github=acme/api/pull/42"`.  If the issue
+    /// already has a remote link with this ID, it is updated instead of a new one being made.  Without one, a new
+    /// link is always made.
+    #[serde(
+        rename(serialize = "globalId"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub global_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application: Option<RemoteLinkApplication>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<String>,
+
+    pub object: RemoteObject,
+}
+
+impl RemoteLinkRequest {
+    /// Starts a request for a link to the URL, identified by `global_id`.
+    pub fn new(global_id: &str, url: &str, title: &str) -> Self {
+        RemoteLinkRequest {
+            global_id: Some(global_id.to_owned()),
+            application: None,
+            relationship: None,
+            object: RemoteObject {
+                url: url.to_owned(),
+                title: title.to_owned(),
+                summary: None,
+                icon: None,
+                status: None,
+            },
+        }
+    }
+}
+
+/// Represents the system a remote link points into.  JIRA groups remote links by application on the issue.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RemoteLinkApplication {
+    /// The type of the application, in reverse domain name form, like `"com.github"`.
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub application_type: Option<String>,
+
+    /// The name of the application, shown to users.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl RemoteLinkApplication {
+    pub fn new(application_type: &str, name: &str) -> Self {
+        RemoteLinkApplication {
+            application_type: Some(application_type.to_owned()),
+            name: Some(name.to_owned()),
+        }
+    }
+}
+
+/// Represents what a remote link points at.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RemoteObject {
+    pub url: String,
+
+    pub title: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<RemoteLinkIcon>,
+
+    /// Whether the object is resolved, like a merged pull request.  JIRA strikes resolved links through.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<RemoteObjectStatus>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RemoteLinkIcon {
+    /// The URL of a 16x16 pixel image.
+    #[serde(default, rename = "url16x16", skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// The text shown when hovering over the icon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RemoteObjectStatus {
+    pub resolved: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<RemoteLinkIcon>,
+}
+
+/// Represents the response to a create or update remote issue link request.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RemoteLinkIdentifier {
+    #[serde(deserialize_with = "util::deserialize_id")]
+    pub id: String,
+}
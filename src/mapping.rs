@@ -0,0 +1,185 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::de::DeserializeOwned;
+use serde_json::value::Value as JSONValue;
+
+use crate::fields::FieldCatalog;
+use crate::{Error, SearchIssue};
+
+#[cfg(feature = "derive")]
+pub use jimberlage_jira_client_derive::JiraIssue;
+
+/// Represents a type which is read out of the fields of an issue, so that it does not have to be picked apart by hand.
+///
+/// With the `derive` feature, this can be derived.  Each field of the struct is read from the issue field named by its
+/// `#[jira(...)]` attribute:
+///
+/// - `#[jira(field = "customfield_10016")]` reads the field with the ID.
+/// - `#[jira(name = "Story Points")]` reads the field with the display name, which is looked up in a
+///   `FieldCatalog`.  IDs of custom fields differ between JIRA sites, while names are usually the same.
+/// - `#[jira(key)]` and `#[jira(id)]` read the key and ID of the issue itself, into a `String`.
+/// - Without an attribute, the field with the same ID as the struct field is read, which suits standard fields like
+///   `summary` and `labels`.
+///
+/// Fields are read with `serde`, and missing fields are read as `null`, so use an `Option` for anything which may not
+/// be set.  `#[jira(deserialize_with = "path")]` reads a field with a function like those given to
+/// `#[serde(deserialize_with = "...")]`, such as `util::deserialize_datetime` for timestamps.
+///
+/// `RestClient::search_all_as` puts all of this together.
+///
+/// ### Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use chrono::{DateTime, FixedOffset};
+/// use jimberlage_jira_client::mapping::{JiraIssue, ResolvedFields};
+/// use jimberlage_jira_client::fields::FieldCatalog;
+/// use jimberlage_jira_client::{Field, SearchIssue};
+///
+/// #[derive(JiraIssue)]
+/// struct Story {
+///     #[jira(key)]
+///     key: String,
+///     summary: String,
+///     #[jira(name = "Story Points")]
+///     story_points: Option<f64>,
+///     #[jira(field = "created", deserialize_with = "jimberlage_jira_client::util::deserialize_datetime")]
+///     created: DateTime<FixedOffset>,
+/// }
+///
+/// let fields: Vec<Field> = serde_json::from_str(r#"[
+///   {"id": "summary", "name": "Summary"},
+///   {"id": "customfield_10016", "name": "Story Points"}
+/// ]"#).unwrap();
+/// let resolved = ResolvedFields::<Story>::new(&FieldCatalog::new(fields)).unwrap();
+/// assert_eq!(resolved.ids(), ["summary", "customfield_10016", "created"]);
+///
+/// let issue: SearchIssue = serde_json::from_str(r#"{
+///   "id": "10001",
+///   "key": "SRE-1",
+///   "fields": {"summary": "Rotate certificates", "created": "2023-05-10T14:23:01.123+0000"}
+/// }"#).unwrap();
+/// let story = Story::from_issue(&issue, &resolved).unwrap();
+///
+/// assert_eq!(story.key, "SRE-1");
+/// assert_eq!(story.summary, "Rotate certificates");
+/// assert_eq!(story.story_points, None);
+/// # }
+/// ```
+pub trait JiraIssue: Sized {
+    /// Lists the issue fields the type reads, in the order `from_issue` expects them to be resolved.
+    fn fields() -> &'static [FieldRef];
+
+    /// Reads the type out of an issue, which should have been fetched with the IDs in `fields`.
+    fn from_issue(issue: &SearchIssue, fields: &ResolvedFields<Self>) -> Result<Self, Error>;
+}
+
+/// Refers to a field on an issue, either by its ID or by the name JIRA shows for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldRef {
+    /// The ID of the field, like `"summary"` or `"customfield_10016"`.
+    Id(&'static str),
+
+    /// The display name of the field, like `"Story Points"`.
+    Name(&'static str),
+}
+
+impl FieldRef {
+    /// Finds the ID of the field, looking display names up in `catalog`.
+    pub fn resolve(&self, catalog: &FieldCatalog) -> Result<String, Error> {
+        match *self {
+            FieldRef::Id(id) => Ok(id.to_owned()),
+            FieldRef::Name(name) => catalog.find_by_name(name).map(|field| field.id.clone()),
+        }
+    }
+}
+
+impl fmt::Display for FieldRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldRef::Id(id) => write!(f, "{}", id),
+            FieldRef::Name(name) => write!(f, "\"{}\"", name),
+        }
+    }
+}
+
+/// Holds the IDs of the fields a `JiraIssue` type reads, once any display names have been looked up.
+///
+/// Pass `ids` as the fields to `RestClient::search_all` or `RestClient::get_issue`, then read each issue with
+/// `JiraIssue::from_issue`.
+pub struct ResolvedFields<T> {
+    ids: Vec<String>,
+    issue_type: PhantomData<fn() -> T>,
+}
+
+impl<T: JiraIssue> ResolvedFields<T> {
+    /// Resolves the fields of `T`, looking display names up in `catalog`.
+    ///
+    /// If `T` only refers to fields by ID, `catalog` may be empty.
+    pub fn new(catalog: &FieldCatalog) -> Result<Self, Error> {
+        Ok(ResolvedFields {
+            ids: T::fields()
+                .iter()
+                .map(|field| field.resolve(catalog))
+                .collect::<Result<Vec<String>, Error>>()?,
+            issue_type: PhantomData,
+        })
+    }
+
+    /// Returns whether any field of `T` is referred to by display name, and so needs `RestClient::get_field_catalog`.
+    pub fn needs_names() -> bool {
+        T::fields()
+            .iter()
+            .any(|field| matches!(field, FieldRef::Name(_)))
+    }
+}
+
+impl<T> ResolvedFields<T> {
+    /// Returns the IDs of the fields, in the order of `JiraIssue::fields`.
+    pub fn ids(&self) -> &[String] {
+        &self.ids
+    }
+
+    #[doc(hidden)]
+    pub fn id(&self, index: usize) -> &str {
+        &self.ids[index]
+    }
+}
+
+impl<T> Clone for ResolvedFields<T> {
+    fn clone(&self) -> Self {
+        ResolvedFields {
+            ids: self.ids.clone(),
+            issue_type: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for ResolvedFields<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedFields")
+            .field("ids", &self.ids)
+            .finish()
+    }
+}
+
+/// Reads a field for derived `JiraIssue` implementations, treating a missing field as `null`.
+#[doc(hidden)]
+pub fn read_field<T: DeserializeOwned>(issue: &SearchIssue, id: &str) -> Result<T, Error> {
+    read_field_with(issue, id, |value| T::deserialize(value))
+}
+
+/// Reads a field for derived `JiraIssue` implementations with a `deserialize_with` function.
+#[doc(hidden)]
+pub fn read_field_with<T, F>(issue: &SearchIssue, id: &str, deserialize: F) -> Result<T, Error>
+where
+    F: for<'a> FnOnce(&'a JSONValue) -> Result<T, serde_json::Error>,
+{
+    let value = issue.fields.get(id).unwrap_or(&JSONValue::Null);
+
+    deserialize(value).map_err(|source| Error::Deserialization {
+        source,
+        body: value.to_string(),
+    })
+}
@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use reqwest::{header::HeaderMap, Method, StatusCode};
+
+use crate::error;
+
+/// Configures how a client retries requests that JIRA could not serve right now.
+///
+/// JIRA Cloud [rate limits][1] clients by responding with 429 and a `Retry-After` header, and sometimes responds with
+/// 503 when it is under load.  Requests that fail this way, or that time out or fail to connect, are retried with
+/// exponential backoff.  When JIRA sends `Retry-After`, that delay is used instead of the backoff, up to `max_backoff`.
+///
+/// Only idempotent requests (GET, HEAD, PUT, DELETE, OPTIONS) are retried by default, since retrying something like
+/// issue creation could create duplicates.  Requests which only read data despite being a POST, like searching, are
+/// treated as idempotent by the client.  A 429 is retried whatever the method, since JIRA did not process the request.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use jimberlage_jira_client::RetryPolicy;
+///
+/// let policy = RetryPolicy {
+///     max_attempts: 5,
+///     initial_backoff: Duration::from_millis(500),
+///     ..RetryPolicy::default()
+/// };
+///
+/// assert_eq!(policy.backoff(1), Duration::from_millis(500));
+/// assert_eq!(policy.backoff(3), Duration::from_secs(2));
+/// assert_eq!(policy.backoff(20), policy.max_backoff);
+/// assert_eq!(RetryPolicy::none().max_attempts, 1);
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rate-limiting/
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// The total number of times a request is attempted, including the first.  1 disables retries.
+    pub max_attempts: u32,
+
+    /// The delay before the first retry.  It doubles for each retry after that.
+    pub initial_backoff: Duration,
+
+    /// The longest delay between attempts.  This caps `Retry-After` too, so that a server cannot stall the client.
+    pub max_backoff: Duration,
+
+    /// Whether to randomize each backoff between zero and its full value, so that many clients failing at once do not
+    /// retry in lockstep.
+    pub jitter: bool,
+
+    /// Whether to retry requests which are not idempotent, like creating an issue.
+    pub retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// A policy which never retries.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Returns the backoff before the given retry, without jitter applied.  The first retry is retry 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let multiplier = 2u32.saturating_pow(retry.saturating_sub(1));
+
+        self.initial_backoff
+            .saturating_mul(multiplier)
+            .min(self.max_backoff)
+    }
+
+    /// Returns whether a request with this method may be retried after any failure, not just a 429.
+    ///
+    /// `idempotent` lets the client mark requests which are safe to retry even though their method is not, like
+    /// POSTs that only search.
+    pub(crate) fn may_retry(&self, method: &Method, idempotent: bool) -> bool {
+        idempotent
+            || self.retry_non_idempotent
+            || matches!(
+                *method,
+                Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+            )
+    }
+
+    /// Returns how long to wait before retrying, or `None` if the outcome of this attempt should be returned as is.
+    ///
+    /// `attempt` is the number of the attempt that just finished, starting at 1.  `may_retry` is the result of
+    /// `RetryPolicy::may_retry` for the request.
+    pub(crate) fn delay_before_retry(
+        &self,
+        attempt: u32,
+        may_retry: bool,
+        outcome: Result<(StatusCode, &HeaderMap), &reqwest::Error>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retry_after = match outcome {
+            // JIRA rejects rate limited requests before processing them, so they are safe to send again.
+            Ok((StatusCode::TOO_MANY_REQUESTS, headers)) => error::retry_after(headers),
+            _ if !may_retry => return None,
+            Ok((StatusCode::SERVICE_UNAVAILABLE, headers)) => error::retry_after(headers),
+            Ok((StatusCode::BAD_GATEWAY, _)) | Ok((StatusCode::GATEWAY_TIMEOUT, _)) => None,
+            Err(e) if e.is_timeout() || e.is_connect() => None,
+            _ => return None,
+        };
+
+        Some(match retry_after {
+            Some(retry_after) => retry_after.min(self.max_backoff),
+            None => self.jittered_backoff(attempt),
+        })
+    }
+
+    fn jittered_backoff(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+
+        if self.jitter {
+            backoff.mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Retries up to 3 times, starting at a 1 second backoff and backing off to at most 30 seconds.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::{fmt, time::Duration};
+
+use serde::Deserialize;
+
+/// Represents how an instance turns days and weeks into hours, as returned by a [get time tracking settings
+/// request][1].
+///
+/// JIRA durations like `1w 2d 3h 30m`, used for worklogs and estimates, count working days and weeks rather than
+/// calendar ones, and each instance picks how long those are.  The default is JIRA's: 8 hour days and 5 day weeks.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use jimberlage_jira_client::time_tracking::TimeTrackingSettings;
+///
+/// let settings = TimeTrackingSettings {
+///     working_hours_per_day: 7.5,
+///     ..TimeTrackingSettings::default()
+/// };
+/// let duration = settings.parse_duration("1w 2d 3h 30m").unwrap();
+///
+/// // A week is 37.5 hours, and a day 7.5, so this is 56 hours.
+/// assert_eq!(duration, Duration::from_secs(56 * 60 * 60));
+/// assert_eq!(settings.format_duration(duration), "1w 2d 3h 30m");
+/// assert!(settings.parse_duration("soon").is_err());
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-time-tracking/#api-rest-api-3-configuration-timetracking-options-get
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct TimeTrackingSettings {
+    #[serde(rename(deserialize = "workingHoursPerDay"))]
+    pub working_hours_per_day: f64,
+
+    #[serde(rename(deserialize = "workingDaysPerWeek"))]
+    pub working_days_per_week: f64,
+
+    /// The unit of durations given as a bare number, like `90`.
+    #[serde(default, rename(deserialize = "defaultUnit"))]
+    pub default_unit: TimeUnit,
+}
+
+impl Default for TimeTrackingSettings {
+    fn default() -> Self {
+        TimeTrackingSettings {
+            working_hours_per_day: 8.0,
+            working_days_per_week: 5.0,
+            default_unit: TimeUnit::default(),
+        }
+    }
+}
+
+impl TimeTrackingSettings {
+    /// Returns how many seconds one of the unit lasts.
+    fn seconds_in(&self, unit: TimeUnit) -> f64 {
+        match unit {
+            TimeUnit::Minute => 60.0,
+            TimeUnit::Hour => 60.0 * 60.0,
+            TimeUnit::Day => self.working_hours_per_day * 60.0 * 60.0,
+            TimeUnit::Week => self.working_days_per_week * self.working_hours_per_day * 60.0 * 60.0,
+        }
+    }
+
+    /// Parses a duration the way JIRA does, like `1w 2d 3h 30m`.
+    ///
+    /// Each number is followed by a unit, `w`, `d`, `h` or `m`, in either case, and numbers may have a fractional
+    /// part, like `1.5h`.  The spaces between them are optional.  A bare number, like `90`, is in the default unit.
+    pub fn parse_duration(&self, s: &str) -> Result<Duration, ParseDurationError> {
+        let error = || ParseDurationError {
+            input: s.to_owned(),
+        };
+        let trimmed = s.trim();
+
+        if let Ok(amount) = trimmed.parse::<f64>() {
+            return self.seconds(amount, self.default_unit).ok_or_else(error);
+        }
+
+        let mut seconds = Duration::ZERO;
+        let mut rest = trimmed;
+
+        while !rest.is_empty() {
+            let number_len = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .ok_or_else(error)?;
+            let amount = rest[..number_len].parse::<f64>().map_err(|_| error())?;
+            let unit = match rest[number_len..].chars().next() {
+                Some('w' | 'W') => TimeUnit::Week,
+                Some('d' | 'D') => TimeUnit::Day,
+                Some('h' | 'H') => TimeUnit::Hour,
+                Some('m' | 'M') => TimeUnit::Minute,
+                _ => return Err(error()),
+            };
+            seconds += self.seconds(amount, unit).ok_or_else(error)?;
+            rest = rest[number_len + 1..].trim_start();
+        }
+
+        if trimmed.is_empty() {
+            Err(error())
+        } else {
+            Ok(seconds)
+        }
+    }
+
+    /// Converts an amount of the unit into a duration, rounded to the second.
+    fn seconds(&self, amount: f64, unit: TimeUnit) -> Option<Duration> {
+        let seconds = (amount * self.seconds_in(unit)).round();
+
+        if seconds.is_finite() && seconds >= 0.0 {
+            Some(Duration::from_secs(seconds as u64))
+        } else {
+            None
+        }
+    }
+
+    /// Formats a duration the way JIRA shows it, like `1w 2d 3h 30m`.
+    ///
+    /// Units with nothing in them are left out, and anything under a minute is dropped, since JIRA does not show
+    /// seconds.  A duration under a minute is formatted as `0m`.
+    pub fn format_duration(&self, duration: Duration) -> String {
+        let mut remaining = duration.as_secs();
+        let mut parts = vec![];
+
+        for (unit, suffix) in [
+            (TimeUnit::Week, 'w'),
+            (TimeUnit::Day, 'd'),
+            (TimeUnit::Hour, 'h'),
+            (TimeUnit::Minute, 'm'),
+        ] {
+            let unit_seconds = self.seconds_in(unit).round() as u64;
+            if unit_seconds == 0 {
+                continue;
+            }
+
+            let amount = remaining / unit_seconds;
+            if amount > 0 {
+                parts.push(format!("{}{}", amount, suffix));
+                remaining -= amount * unit_seconds;
+            }
+        }
+
+        if parts.is_empty() {
+            "0m".to_owned()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeUnit {
+    #[default]
+    Minute,
+    Hour,
+    Day,
+    Week,
+}
+
+/// Returned by `TimeTrackingSettings::parse_duration` for anything which is not a JIRA duration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDurationError {
+    input: String,
+}
+
+impl fmt::Display for ParseDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid duration {:?}, expected something like \"1w 2d 3h 30m\"",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for ParseDurationError {}
+
+/// Formats a duration as whole minutes, which JIRA reads the same way whatever the time tracking settings are.
+///
+/// Partial minutes are rounded up, so that something like 30 seconds is not sent as no time at all.
+pub(crate) fn minutes(duration: Duration) -> String {
+    format!("{}m", duration.as_nanos().div_ceil(60_000_000_000))
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, value::Value as JSONValue, Map};
+
+use crate::{IssueEditUpdate, IssueEditUpdateOperation, Status, TextBody};
+
+/// Represents a transition an issue can make through its workflow, as returned by a [get transitions request][1].
+///
+/// Transition IDs are specific to a workflow, so the same move (say, "In Progress" to "Done") can have a different ID
+/// in each project.  Use `find_by_status` to pick a transition by where it goes instead.
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-transitions-get
+#[derive(Clone, Debug, Deserialize)]
+pub struct Transition {
+    pub id: String,
+
+    pub name: String,
+
+    /// The status the issue is in after the transition.
+    pub to: Status,
+
+    #[serde(default, rename(deserialize = "hasScreen"))]
+    pub has_screen: bool,
+
+    #[serde(default, rename(deserialize = "isGlobal"))]
+    pub is_global: bool,
+
+    #[serde(default, rename(deserialize = "isConditional"))]
+    pub is_conditional: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct TransitionsResponse {
+    pub(crate) transitions: Vec<Transition>,
+}
+
+/// Finds the transition which moves an issue into the status with the name, ignoring case.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::transition::{self, Transition};
+///
+/// let transitions: Vec<Transition> = serde_json::from_str(r#"[
+///   {"id": "11", "name": "Start work", "to": {"id": "3", "name": "In Progress"}},
+///   {"id": "31", "name": "Close", "to": {"id": "10001", "name": "Done"}}
+/// ]"#).unwrap();
+///
+/// assert_eq!(transition::find_by_status(&transitions, "done").map(|t| t.id.as_str()), Some("31"));
+/// assert!(transition::find_by_status(&transitions, "Won't Do").is_none());
+/// ```
+pub fn find_by_status<'a>(
+    transitions: &'a [Transition],
+    status_name: &str,
+) -> Option<&'a Transition> {
+    transitions
+        .iter()
+        .find(|transition| transition.to.name.eq_ignore_ascii_case(status_name))
+}
+
+/// Represents the body of a [transition issue request][1].
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issues/#api-rest-api-3-issue-issueidorkey-transitions-post
+#[derive(Debug, Serialize)]
+pub(crate) struct TransitionRequest {
+    transition: JSONValue,
+
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    fields: Map<String, JSONValue>,
+
+    update: IssueEditUpdate,
+}
+
+impl TransitionRequest {
+    pub(crate) fn new(
+        transition_id: &str,
+        fields: &Map<String, JSONValue>,
+        update: &IssueEditUpdate,
+        comment: Option<&TextBody>,
+    ) -> Self {
+        let mut update = update.clone();
+
+        // JIRA has no separate key for a transition comment; it is added like any other comment in an edit.
+        if let Some(comment) = comment {
+            update
+                .comment
+                .push(IssueEditUpdateOperation::Add(json!({ "body": comment })));
+        }
+
+        TransitionRequest {
+            transition: json!({ "id": transition_id }),
+            fields: fields.clone(),
+            update,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use chrono::{DateTime, FixedOffset};
+use serde::{de, Deserialize, Deserializer, Serializer};
 use serde_json::Value;
 
 /// Gets a string out of a json object at a given path.
@@ -21,28 +23,100 @@ use serde_json::Value;
 ///
 /// assert_eq!(util::get_string_in_json(&value, &path), Some("Done".to_owned()));
 /// ```
-pub fn get_string_in_json<'a>(value: &Value, path: &Vec<&'a str>) -> Option<String> {
+pub fn get_string_in_json(value: &Value, path: &[&str]) -> Option<String> {
     if path.is_empty() {
         return None;
     }
 
     let mut current_value = value;
 
-    for i in 0..(path.len() - 1) {
+    for key in &path[..path.len() - 1] {
         if let Value::Object(m) = current_value {
-            if let Some(inner) = m.get(path[i]) {
+            if let Some(inner) = m.get(*key) {
                 current_value = inner;
             }
         }
     }
 
     if let Value::Object(m) = current_value {
-        if let Some(inner) = m.get(path[path.len() - 1]) {
-            if let Value::String(s) = inner {
-                return Some(s.clone());
-            }
+        if let Some(Value::String(s)) = m.get(path[path.len() - 1]) {
+            return Some(s.clone());
         }
     }
 
     None
 }
+
+/// Parses a timestamp in the format JIRA uses for fields like `created` and `updated`.
+///
+/// JIRA's timestamps look like RFC 3339, but leave the colon out of the offset, so chrono's RFC 3339 parser rejects
+/// them.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::util;
+///
+/// let created = util::parse_datetime("2023-05-10T14:23:01.123+0200").unwrap();
+///
+/// assert_eq!(created.to_rfc3339(), "2023-05-10T14:23:01.123+02:00".to_owned());
+/// assert!(util::parse_datetime("yesterday").is_none());
+/// ```
+pub fn parse_datetime(s: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z").ok()
+}
+
+/// Deserializes a timestamp in JIRA's format, for use with `#[serde(deserialize_with = "...")]` or
+/// `#[jira(deserialize_with = "...")]`.
+///
+/// A few newer endpoints, like bulk changelog fetches, send milliseconds since the epoch instead, so those are
+/// accepted too.
+pub fn deserialize_datetime<'de, D>(deserializer: D) -> Result<DateTime<FixedOffset>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timestamp {
+        Formatted(String),
+        EpochMillis(i64),
+    }
+
+    match Timestamp::deserialize(deserializer)? {
+        Timestamp::Formatted(s) => parse_datetime(&s)
+            .ok_or_else(|| de::Error::custom(format!("invalid JIRA timestamp: {}", s))),
+        Timestamp::EpochMillis(millis) => DateTime::from_timestamp_millis(millis)
+            .map(|datetime| datetime.fixed_offset())
+            .ok_or_else(|| de::Error::custom(format!("invalid JIRA timestamp: {}", millis))),
+    }
+}
+
+/// Serializes a timestamp in JIRA's format, for use with `#[serde(serialize_with = "...")]`.
+pub(crate) fn serialize_datetime<S>(
+    datetime: &DateTime<FixedOffset>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&datetime.format("%Y-%m-%dT%H:%M:%S%.3f%z"))
+}
+
+/// Deserializes an ID which JIRA sends as a string from some endpoints and as a number from others, for use with
+/// `#[serde(deserialize_with = "...")]`.
+pub(crate) fn deserialize_id<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Id {
+        String(String),
+        Number(u64),
+    }
+
+    Ok(match Id::deserialize(deserializer)? {
+        Id::String(id) => id,
+        Id::Number(id) => id.to_string(),
+    })
+}
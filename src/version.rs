@@ -0,0 +1,96 @@
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+use serde_json::value::Value as JSONValue;
+
+use crate::adf::Document;
+
+/// Represents which version of JIRA's REST API a client talks to.
+///
+/// JIRA Cloud speaks [V3][1], where rich text is [Atlassian Document Format][2] and users are identified by
+/// `accountId`.  JIRA Server and Data Center only speak [V2][3], where rich text is wiki markup and users are
+/// identified by `name` or `key`.
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/intro/
+/// [2]: https://developer.atlassian.com/cloud/jira/platform/apis/document/structure/
+/// [3]: https://docs.atlassian.com/software/jira/docs/api/REST/9.6.0/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ApiVersion {
+    V2,
+    #[default]
+    V3,
+}
+
+impl ApiVersion {
+    /// Returns the path of the REST API relative to the URL of the JIRA instance.
+    pub(crate) fn base_path(&self) -> &'static str {
+        match self {
+            ApiVersion::V2 => "/rest/api/2",
+            ApiVersion::V3 => "/rest/api/3",
+        }
+    }
+
+    /// Wraps plain text in the rich text format this version of the API expects.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use jimberlage_jira_client::{ApiVersion, TextBody};
+    ///
+    /// assert_eq!(ApiVersion::V2.plain_text("Deployed"), TextBody::WikiMarkup("Deployed".to_owned()));
+    /// assert_eq!(
+    ///     serde_json::to_string(&ApiVersion::V3.plain_text("Deployed")).unwrap(),
+    ///     r#"{"content":[{"content":[{"text":"Deployed","type":"text"}],"type":"paragraph"}],"type":"doc","version":1}"#
+    /// );
+    /// ```
+    pub fn plain_text(&self, text: &str) -> TextBody {
+        match self {
+            ApiVersion::V2 => TextBody::WikiMarkup(text.to_owned()),
+            ApiVersion::V3 => Document::builder()
+                .paragraph(|p| p.text(text))
+                .build()
+                .into(),
+        }
+    }
+}
+
+/// Represents a rich text field, like an issue description or a comment body.
+///
+/// Which variant JIRA sends and accepts depends on the `ApiVersion`: V2 uses wiki markup strings, and V3 uses ADF
+/// documents.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TextBody {
+    WikiMarkup(String),
+    Adf(JSONValue),
+}
+
+/// Identifies a user in a request, like when assigning an issue or mentioning someone.
+///
+/// V3 of the API only accepts `AccountId`, and V2 on JIRA Server and Data Center only accepts `Name`.
+///
+/// ### Example
+///
+/// ```
+/// use jimberlage_jira_client::UserRef;
+///
+/// assert_eq!(serde_json::to_string(&UserRef::AccountId("5b10a2844c20165700ede21g".to_owned())).unwrap(), r#"{"accountId":"5b10a2844c20165700ede21g"}"#);
+/// assert_eq!(serde_json::to_string(&UserRef::Name("jsmith".to_owned())).unwrap(), r#"{"name":"jsmith"}"#);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UserRef {
+    AccountId(String),
+    Name(String),
+}
+
+impl Serialize for UserRef {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut m = serializer.serialize_map(Some(1))?;
+        match self {
+            UserRef::AccountId(account_id) => m.serialize_entry("accountId", account_id)?,
+            UserRef::Name(name) => m.serialize_entry("name", name)?,
+        }
+        m.end()
+    }
+}
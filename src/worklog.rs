@@ -0,0 +1,216 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::comment::{EntityProperty, Visibility};
+use crate::error::{Error, JiraErrors};
+use crate::time_tracking;
+use crate::{util, TextBody, User};
+
+/// Represents time logged against an issue, as returned by a [get worklogs request][1].
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-issue-issueidorkey-worklog-get
+#[derive(Clone, Debug, Deserialize)]
+pub struct Worklog {
+    pub id: String,
+
+    #[serde(default, rename(deserialize = "issueId"))]
+    pub issue_id: Option<String>,
+
+    #[serde(default)]
+    pub author: Option<User>,
+
+    #[serde(default, rename(deserialize = "updateAuthor"))]
+    pub update_author: Option<User>,
+
+    /// What the time was spent on, in the format of the client's `ApiVersion`.
+    #[serde(default)]
+    pub comment: Option<TextBody>,
+
+    /// When the work started.
+    #[serde(deserialize_with = "util::deserialize_datetime")]
+    pub started: DateTime<FixedOffset>,
+
+    /// The time spent, formatted by JIRA, like `"3h 20m"`.
+    #[serde(default, rename(deserialize = "timeSpent"))]
+    pub time_spent: Option<String>,
+
+    #[serde(rename(deserialize = "timeSpentSeconds"))]
+    pub time_spent_seconds: u64,
+
+    #[serde(deserialize_with = "util::deserialize_datetime")]
+    pub created: DateTime<FixedOffset>,
+
+    #[serde(deserialize_with = "util::deserialize_datetime")]
+    pub updated: DateTime<FixedOffset>,
+
+    /// Who can see the worklog.  `None` means anyone who can see the issue.
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+
+    #[serde(default)]
+    pub properties: Vec<EntityProperty>,
+}
+
+/// Represents the body of an [add worklog request][1] or an [update worklog request][2].
+///
+/// ### Example
+///
+/// ```
+/// use chrono::DateTime;
+/// use jimberlage_jira_client::ApiVersion;
+/// use jimberlage_jira_client::time_tracking::TimeTrackingSettings;
+/// use jimberlage_jira_client::worklog::WorklogRequest;
+///
+/// let request = WorklogRequest {
+///     comment: Some(ApiVersion::V2.plain_text("Incident review")),
+///     ..WorklogRequest::new(
+///         DateTime::parse_from_rfc3339("2023-05-10T14:00:00+02:00").unwrap(),
+///         TimeTrackingSettings::default().parse_duration("1h 30m").unwrap(),
+///     )
+/// };
+///
+/// assert_eq!(
+///     serde_json::to_string(&request).unwrap(),
+///     r#"{"comment":"Incident review","started":"2023-05-10T14:00:00.000+0200","timeSpentSeconds":5400}"#
+/// );
+/// ```
+///
+/// [1]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-issue-issueidorkey-worklog-post
+/// [2]: https://developer.atlassian.com/cloud/jira/platform/rest/v3/api-group-issue-worklogs/#api-rest-api-3-issue-issueidorkey-worklog-id-put
+#[derive(Clone, Debug, Serialize)]
+pub struct WorklogRequest {
+    /// What the time was spent on, in the format of the client's `ApiVersion`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<TextBody>,
+
+    #[serde(serialize_with = "util::serialize_datetime")]
+    pub started: DateTime<FixedOffset>,
+
+    /// The time spent.  JIRA only keeps whole seconds.
+    #[serde(
+        rename(serialize = "timeSpentSeconds"),
+        serialize_with = "serialize_seconds"
+    )]
+    pub time_spent: Duration,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<EntityProperty>,
+}
+
+impl WorklogRequest {
+    /// Starts a request for time spent from `started`, visible to anyone who can see the issue.
+    pub fn new(started: DateTime<FixedOffset>, time_spent: Duration) -> Self {
+        WorklogRequest {
+            comment: None,
+            started,
+            time_spent,
+            visibility: None,
+            properties: vec![],
+        }
+    }
+}
+
+fn serialize_seconds<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u64(duration.as_secs())
+}
+
+/// Represents how adding, updating or deleting a worklog changes the issue's remaining estimate.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum AdjustEstimate {
+    /// Takes the time spent off the remaining estimate, or puts it back when deleting.  This is JIRA's default.
+    #[default]
+    Auto,
+
+    /// Leaves the remaining estimate alone.
+    Leave,
+
+    /// Sets the remaining estimate.
+    New(Duration),
+
+    /// Takes the duration off the remaining estimate when adding, or adds it when deleting.  Updates do not support
+    /// this, and fail with `Error::Validation` without being sent.
+    Manual(Duration),
+}
+
+/// Represents the query parameters of an add, update or delete worklog request.
+///
+/// Left as they are, JIRA's defaults apply: the remaining estimate is adjusted automatically and users are notified.
+#[derive(Clone, Debug, Default)]
+pub struct WorklogOptions {
+    pub adjust_estimate: AdjustEstimate,
+
+    /// Whether to email watchers about the change.
+    pub notify_users: Option<bool>,
+}
+
+impl WorklogOptions {
+    /// Builds the query, where `manual_param` is the name JIRA gives the amount for `AdjustEstimate::Manual`, which
+    /// differs between adding and deleting.  `None` means the request does not support `AdjustEstimate::Manual`.
+    pub(crate) fn query(
+        &self,
+        manual_param: Option<&'static str>,
+    ) -> Result<Vec<(&'static str, String)>, Error> {
+        let mut query = vec![];
+
+        match &self.adjust_estimate {
+            AdjustEstimate::Auto => {}
+            AdjustEstimate::Leave => query.push(("adjustEstimate", "leave".to_owned())),
+            AdjustEstimate::New(estimate) => {
+                query.push(("adjustEstimate", "new".to_owned()));
+                query.push(("newEstimate", time_tracking::minutes(*estimate)));
+            }
+            AdjustEstimate::Manual(amount) => {
+                // JIRA rejects this too, but only after the request has been sent.
+                let Some(manual_param) = manual_param else {
+                    return Err(Error::Validation(JiraErrors {
+                        error_messages: vec![],
+                        errors: HashMap::from([(
+                            "adjustEstimate".to_owned(),
+                            "manual is not supported when updating a worklog".to_owned(),
+                        )]),
+                    }));
+                };
+                query.push(("adjustEstimate", "manual".to_owned()));
+                query.push((manual_param, time_tracking::minutes(*amount)));
+            }
+        }
+        if let Some(notify_users) = self.notify_users {
+            query.push(("notifyUsers", notify_users.to_string()));
+        }
+
+        Ok(query)
+    }
+}
+
+/// The most worklogs JIRA returns from a single get worklogs by ID request.
+pub(crate) const WORKLOG_LIST_MAX_IDS: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct UpdatedWorklogsPage {
+    pub(crate) values: Vec<UpdatedWorklog>,
+
+    /// Where the next page starts, in milliseconds since the epoch.
+    pub(crate) until: i64,
+
+    #[serde(rename(deserialize = "lastPage"))]
+    pub(crate) last_page: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct UpdatedWorklog {
+    #[serde(rename(deserialize = "worklogId"))]
+    pub(crate) worklog_id: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct WorklogIdsRequest<'a> {
+    pub(crate) ids: &'a [u64],
+}
@@ -0,0 +1,154 @@
+use jimberlage_jira_client::adf::{Document, Mark, Node};
+use jimberlage_jira_client::{ApiVersion, TextBody};
+use serde_json::json;
+
+#[test]
+fn round_trips_documents_with_unknown_nodes_and_marks() {
+    let value = json!({
+        "type": "doc",
+        "version": 1,
+        "content": [
+            {"type": "heading", "attrs": {"level": 3}, "content": [{"type": "text", "text": "Impact"}]},
+            {"type": "paragraph", "content": [
+                {"type": "text", "text": "See ", "marks": [{"type": "em"}]},
+                {"type": "text", "text": "the runbook", "marks": [{"type": "link", "attrs": {"href": "https://example.com/runbook"}}]},
+                {"type": "text", "text": " flagged", "marks": [{"type": "annotation", "attrs": {"id": "a1", "annotationType": "inlineComment"}}]},
+                {"type": "status", "attrs": {"text": "BLOCKED", "color": "red"}}
+            ]},
+            {"type": "mediaSingle", "attrs": {"layout": "center"}, "content": [
+                {"type": "media", "attrs": {"id": "abc", "type": "file", "collection": ""}}
+            ]},
+            {"type": "orderedList", "attrs": {"order": 3}, "content": [
+                {"type": "listItem", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "Page on-call"}]}]}
+            ]},
+            {"type": "panel", "attrs": {"panelType": "error"}, "content": [{"type": "paragraph"}]},
+            {"type": "paragraph", "marks": [{"type": "alignment", "attrs": {"align": "center"}}], "content": [
+                {"type": "text", "text": "Centered"}
+            ]},
+            {"type": "heading", "attrs": {"level": 4}, "marks": [{"type": "indentation", "attrs": {"level": 1}}], "content": [
+                {"type": "text", "text": "Indented"}
+            ]},
+            {"type": "codeBlock", "marks": [{"type": "breakout", "attrs": {"mode": "wide"}}], "content": [
+                {"type": "text", "text": "cargo test"}
+            ]}
+        ]
+    });
+
+    let doc: Document = serde_json::from_value(value.clone()).unwrap();
+
+    assert!(matches!(doc.content[2], Node::Unknown(_)));
+    assert!(
+        matches!(&doc.content[1].content()[2], Node::Text { marks, .. } if matches!(marks[0], Mark::Unknown(_)))
+    );
+    assert!(
+        matches!(&doc.content[5], Node::Paragraph { marks, .. } if matches!(marks[0], Mark::Unknown(_)))
+    );
+    assert_eq!(serde_json::to_value(&doc).unwrap(), value);
+    assert!(doc.validate().is_ok());
+    assert_eq!(
+        doc.to_plain_text(),
+        "Impact\nSee the runbook flaggedBLOCKED\nPage on-call\nCentered\nIndented\ncargo test"
+    );
+}
+
+#[test]
+fn round_trips_attributes_without_a_member() {
+    let value = json!({
+        "type": "doc",
+        "version": 1,
+        "content": [
+            {"type": "table", "attrs": {"layout": "default", "localId": "t1"}, "content": [
+                {"type": "tableRow", "content": [
+                    {"type": "tableCell", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "api"}]}]}
+                ]}
+            ]},
+            {"type": "paragraph", "attrs": {"localId": "p1"}, "content": [
+                {"type": "text", "text": "Deployed"},
+                {"type": "hardBreak", "attrs": {"text": "\n"}},
+                {"type": "mention", "attrs": {"id": "5b10a2844c20165700ede21g", "localId": "m1"}}
+            ]},
+            {"type": "heading", "attrs": {"level": 2, "localId": "h1"}, "content": [{"type": "text", "text": "Impact"}]}
+        ]
+    });
+
+    let doc: Document = serde_json::from_value(value.clone()).unwrap();
+
+    assert!(matches!(doc.content[1], Node::Paragraph { .. }));
+    assert_eq!(serde_json::to_value(&doc).unwrap(), value);
+    assert!(doc.validate().is_ok());
+}
+
+#[test]
+fn rejects_documents_of_another_type() {
+    let result = serde_json::from_value::<Document>(json!({
+        "type": "paragraph",
+        "version": 1,
+        "content": []
+    }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn leaves_out_empty_text() {
+    let doc = Document::builder()
+        .paragraph(|p| p.text("").strong(""))
+        .build();
+
+    assert_eq!(doc.content[0].content(), &[]);
+    assert!(doc.validate().is_ok());
+    assert_eq!(
+        ApiVersion::V3.plain_text("").to_adf().unwrap().validate(),
+        Ok(())
+    );
+}
+
+#[test]
+fn reports_every_validation_error_with_its_path() {
+    let doc: Document = serde_json::from_value(json!({
+        "type": "doc",
+        "version": 1,
+        "content": [
+            {"type": "bulletList", "content": []},
+            {"type": "paragraph", "content": [
+                {"type": "text", "text": "x", "marks": [{"type": "code"}, {"type": "strong"}]},
+                {"type": "paragraph"}
+            ]},
+            {"type": "heading", "attrs": {"level": "big"}, "content": []},
+            {"type": "codeBlock", "content": [{"type": "text", "text": "ls", "marks": [{"type": "em"}]}]}
+        ]
+    }))
+    .unwrap();
+
+    let errors: Vec<String> = doc
+        .validate()
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert_eq!(
+        errors,
+        vec![
+            "content[0].content: bulletList must have content",
+            "content[1].content[0]: code mark can only be combined with link",
+            "content[1].content[1]: paragraph is not allowed in paragraph",
+            "content[2]: heading is missing required attributes or content",
+            "content[3].content[0]: text in a code block cannot have marks",
+        ]
+    );
+}
+
+#[test]
+fn reads_adf_out_of_text_bodies() {
+    let body = TextBody::from(
+        Document::builder()
+            .paragraph(|p| p.text("Deployed"))
+            .build(),
+    );
+
+    assert_eq!(body.to_adf().unwrap().to_plain_text(), "Deployed");
+    assert!(TextBody::WikiMarkup("Deployed".to_owned())
+        .to_adf()
+        .is_none());
+}
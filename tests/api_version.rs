@@ -0,0 +1,48 @@
+use jimberlage_jira_client::{ApiVersion, Auth, RestClient, RestClientBuilder, UserRef};
+use mockito::{Matcher, Server};
+
+#[test]
+fn v2_clients_use_the_v2_api() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/rest/api/2/user/picker")
+        .match_query(Matcher::UrlEncoded("query".into(), "jsmith".into()))
+        .with_body(r#"{"users": [{"name": "jsmith", "key": "JIRAUSER10100", "displayName": "Jane Smith"}], "total": 1}"#)
+        .create();
+
+    let client = RestClientBuilder::new(&server.url())
+        .api_version(ApiVersion::V2)
+        .auth(Auth::bearer("personal-access-token"))
+        .build()
+        .unwrap();
+    let response = client.search_users("jsmith").unwrap();
+
+    mock.assert();
+    assert_eq!(client.api_version(), ApiVersion::V2);
+    assert_eq!(response.users[0].account_id, None);
+    assert_eq!(response.users[0].key.as_deref(), Some("JIRAUSER10100"));
+    assert_eq!(
+        response.users[0].user_ref(),
+        Some(UserRef::Name("jsmith".to_owned()))
+    );
+}
+
+#[test]
+fn v3_clients_use_the_v3_api() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/rest/api/3/user/picker")
+        .match_query(Matcher::Any)
+        .with_body(r#"{"users": [{"accountId": "5b10a2844c20165700ede21g", "displayName": "Jane Smith"}], "total": 1}"#)
+        .create();
+
+    let client = RestClient::new(&server.url(), "me@example.com", "token").unwrap();
+    let response = client.search_users("jane").unwrap();
+
+    mock.assert();
+    assert_eq!(client.api_version(), ApiVersion::V3);
+    assert_eq!(
+        response.users[0].user_ref(),
+        Some(UserRef::AccountId("5b10a2844c20165700ede21g".to_owned()))
+    );
+}
@@ -0,0 +1,127 @@
+use jimberlage_jira_client::Error;
+use mockito::{Matcher, Server};
+
+mod common;
+
+use common::client;
+
+const ATTACHMENT: &str = r#"{
+    "id": 10000,
+    "filename": "build.log",
+    "author": {"accountId": "5b10a2844c20165700ede21g", "displayName": "Build Bot"},
+    "created": "2023-05-10T14:23:01.123+0000",
+    "size": 13,
+    "mimeType": "text/plain",
+    "content": "https://example.atlassian.net/rest/api/3/attachment/content/10000"
+}"#;
+
+#[test]
+fn uploads_attachments_as_multipart_forms() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/rest/api/3/issue/SRE-1/attachments")
+        .match_header("X-Atlassian-Token", "no-check")
+        .match_header(
+            "content-type",
+            Matcher::Regex("^multipart/form-data; boundary=".to_owned()),
+        )
+        .match_body(Matcher::AllOf(vec![
+            Matcher::Regex(r#"name="file"; filename="build.log""#.to_owned()),
+            Matcher::Regex("BUILD FAILED\n".to_owned()),
+        ]))
+        .with_body(format!("[{}]", ATTACHMENT))
+        .create();
+
+    let attachment = client(&server)
+        .add_attachment("SRE-1", "build.log", b"BUILD FAILED\n".to_vec())
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(attachment.id, "10000");
+    assert_eq!(attachment.mime_type.as_deref(), Some("text/plain"));
+}
+
+#[test]
+fn downloads_attachments_into_a_writer() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/rest/api/3/attachment/content/10000")
+        .with_body("BUILD FAILED\n")
+        .create();
+    server
+        .mock("GET", "/rest/api/3/attachment/content/10001")
+        .with_status(404)
+        .with_body(r#"{"errorMessages": ["The attachment does not exist."]}"#)
+        .create();
+    let client = client(&server);
+    let mut out = vec![];
+
+    let written = client.download_attachment("10000", &mut out).unwrap();
+    let missing = client.download_attachment("10001", &mut out);
+
+    assert_eq!(written, 13);
+    assert_eq!(out, b"BUILD FAILED\n");
+    assert!(
+        matches!(missing, Err(Error::NotFound(errors)) if errors.error_messages == ["The attachment does not exist."])
+    );
+}
+
+#[test]
+fn reads_expands_and_deletes_attachments() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/rest/api/3/attachment/10000")
+        .with_body(ATTACHMENT)
+        .create();
+    server
+        .mock("GET", "/rest/api/3/attachment/10002/expand/human")
+        .with_body(
+            r#"{
+                "id": 10002,
+                "name": "logs.zip",
+                "entries": [
+                    {"path": "logs/build.log", "index": 0, "size": "13 B", "mediaType": "text/plain", "label": "build.log"}
+                ],
+                "totalEntryCount": 1,
+                "mediaType": "application/zip"
+            }"#,
+        )
+        .create();
+    let delete = server
+        .mock("DELETE", "/rest/api/3/attachment/10000")
+        .with_status(204)
+        .create();
+    let client = client(&server);
+
+    let attachment = client.get_attachment_meta("10000").unwrap();
+    let archive = client.expand_attachment("10002").unwrap();
+    client.delete_attachment(&attachment.id).unwrap();
+
+    assert_eq!(attachment.size, 13);
+    assert_eq!(archive.id, "10002");
+    assert_eq!(archive.entries[0].path, "logs/build.log");
+    delete.assert();
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn async_client_downloads_attachments_into_a_writer() {
+    use jimberlage_jira_client::AsyncRestClient;
+
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/rest/api/3/attachment/content/10000")
+        .with_body("BUILD FAILED\n")
+        .create_async()
+        .await;
+    let mut out = vec![];
+
+    let written = AsyncRestClient::new(&server.url(), "me@example.com", "token")
+        .unwrap()
+        .download_attachment("10000", &mut out)
+        .await
+        .unwrap();
+
+    assert_eq!(written, 13);
+    assert_eq!(out, b"BUILD FAILED\n");
+}
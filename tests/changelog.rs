@@ -0,0 +1,83 @@
+use mockito::{Matcher, Server};
+
+mod common;
+
+use common::client;
+
+#[test]
+fn pages_through_an_issue_changelog() {
+    let mut server = Server::new();
+    let first_page = server
+        .mock("GET", "/rest/api/3/issue/SRE-1/changelog")
+        .match_query(Matcher::UrlEncoded("startAt".into(), "0".into()))
+        .with_body(
+            r#"{"startAt": 0, "maxResults": 1, "total": 2, "isLast": false, "values": [
+                {"id": "1", "created": "2023-05-10T14:23:01.123+0000", "items": [
+                    {"field": "status", "fromString": "To Do", "toString": "In Progress"}
+                ]}
+            ]}"#,
+        )
+        .create();
+    let second_page = server
+        .mock("GET", "/rest/api/3/issue/SRE-1/changelog")
+        .match_query(Matcher::UrlEncoded("startAt".into(), "1".into()))
+        .with_body(
+            r#"{"startAt": 1, "maxResults": 1, "total": 2, "isLast": true, "values": [
+                {"id": "2", "created": "2023-05-11T09:00:00.000+0000", "items": [
+                    {"field": "status", "fromString": "In Progress", "toString": "Done"}
+                ]}
+            ]}"#,
+        )
+        .create();
+
+    let changelog = client(&server).get_changelog("SRE-1").unwrap();
+
+    first_page.assert();
+    second_page.assert();
+    assert_eq!(
+        changelog
+            .iter()
+            .filter_map(|entry| entry.status_change())
+            .collect::<Vec<(&str, &str)>>(),
+        vec![("To Do", "In Progress"), ("In Progress", "Done")]
+    );
+}
+
+#[test]
+fn bulk_fetches_changelogs_across_pages() {
+    let mut server = Server::new();
+    server
+        .mock("POST", "/rest/api/3/changelog/bulkfetch")
+        .match_body(Matcher::JsonString(
+            r#"{"issueIdsOrKeys": ["SRE-1", "SRE-2"]}"#.to_owned(),
+        ))
+        .with_body(
+            r#"{"issueChangeLogs": [
+                {"issueId": "10001", "changeHistories": [{"id": "1", "created": 1683728581123, "items": []}]}
+            ], "nextPageToken": "abc"}"#,
+        )
+        .create();
+    server
+        .mock("POST", "/rest/api/3/changelog/bulkfetch")
+        .match_body(Matcher::JsonString(
+            r#"{"issueIdsOrKeys": ["SRE-1", "SRE-2"], "nextPageToken": "abc"}"#.to_owned(),
+        ))
+        .with_body(
+            r#"{"issueChangeLogs": [
+                {"issueId": "10001", "changeHistories": [{"id": "2", "created": 1683795600000, "items": []}]},
+                {"issueId": "10002", "changeHistories": [{"id": "3", "created": 1683795600000, "items": []}]}
+            ]}"#,
+        )
+        .create();
+
+    let changelogs = client(&server)
+        .bulk_fetch_changelogs(&["SRE-1".to_owned(), "SRE-2".to_owned()])
+        .unwrap();
+
+    assert_eq!(changelogs["10001"].len(), 2);
+    assert_eq!(changelogs["10002"].len(), 1);
+    assert_eq!(
+        changelogs["10001"][0].created.to_rfc3339(),
+        "2023-05-10T14:23:01.123+00:00"
+    );
+}
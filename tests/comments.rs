@@ -0,0 +1,96 @@
+use jimberlage_jira_client::comment::{CommentRequest, EntityProperty, Visibility};
+use jimberlage_jira_client::TextBody;
+use mockito::{Matcher, Server};
+use serde_json::json;
+
+mod common;
+
+use common::client;
+
+const COMMENT: &str = r#"{
+    "id": "10100",
+    "author": {"accountId": "5b10a2844c20165700ede21g", "displayName": "Deploy Bot"},
+    "body": {"type": "doc", "version": 1, "content": []},
+    "created": "2023-05-10T14:23:01.123+0000",
+    "updated": "2023-05-10T14:23:01.123+0000",
+    "visibility": {"type": "role", "value": "Developers"},
+    "properties": [{"key": "deploy", "value": {"sha": "abc123"}}]
+}"#;
+
+#[test]
+fn pages_through_comments() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/rest/api/3/issue/SRE-1/comment")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("expand".into(), "properties".into()),
+            Matcher::UrlEncoded("startAt".into(), "0".into()),
+        ]))
+        .with_body(format!(
+            r#"{{"startAt": 0, "maxResults": 100, "total": 1, "comments": [{}]}}"#,
+            COMMENT
+        ))
+        .create();
+
+    let comments = client(&server).get_comments("SRE-1").unwrap();
+
+    mock.assert();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].visibility, Some(Visibility::role("Developers")));
+    assert_eq!(comments[0].properties[0].value, json!({"sha": "abc123"}));
+}
+
+#[test]
+fn adds_updates_and_deletes_comments() {
+    let mut server = Server::new();
+    let add = server
+        .mock("POST", "/rest/api/3/issue/SRE-1/comment")
+        .match_body(Matcher::JsonString(
+            r#"{"body": "Deployed", "properties": [{"key": "deploy", "value": {"sha": "abc123"}}]}"#
+                .to_owned(),
+        ))
+        .with_status(201)
+        .with_body(COMMENT)
+        .create();
+    let update = server
+        .mock("PUT", "/rest/api/3/issue/SRE-1/comment/10100")
+        .match_body(Matcher::JsonString(
+            r#"{"body": "Rolled back", "visibility": {"type": "role", "value": "Developers"}}"#
+                .to_owned(),
+        ))
+        .with_body(COMMENT)
+        .create();
+    let delete = server
+        .mock("DELETE", "/rest/api/3/issue/SRE-1/comment/10100")
+        .with_status(204)
+        .create();
+    let client = client(&server);
+
+    let comment = client
+        .add_comment(
+            "SRE-1",
+            &CommentRequest {
+                properties: vec![EntityProperty {
+                    key: "deploy".to_owned(),
+                    value: json!({"sha": "abc123"}),
+                }],
+                ..CommentRequest::new(TextBody::WikiMarkup("Deployed".to_owned()))
+            },
+        )
+        .unwrap();
+    client
+        .update_comment(
+            "SRE-1",
+            &comment.id,
+            &CommentRequest {
+                visibility: Some(Visibility::role("Developers")),
+                ..CommentRequest::new(TextBody::WikiMarkup("Rolled back".to_owned()))
+            },
+        )
+        .unwrap();
+    client.delete_comment("SRE-1", &comment.id).unwrap();
+
+    add.assert();
+    update.assert();
+    delete.assert();
+}
@@ -0,0 +1,7 @@
+use jimberlage_jira_client::RestClient;
+use mockito::Server;
+
+/// Returns a client which talks to the mock server with basic auth.
+pub fn client(server: &Server) -> RestClient {
+    RestClient::new(&server.url(), "me@example.com", "token").unwrap()
+}
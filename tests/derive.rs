@@ -0,0 +1,203 @@
+#![cfg(feature = "derive")]
+
+use chrono::{DateTime, FixedOffset};
+use jimberlage_jira_client::fields::{FieldCatalog, Priority};
+use jimberlage_jira_client::jql::{JQLClause, JQLStatement, JQLValue};
+use jimberlage_jira_client::mapping::{FieldRef, JiraIssue, ResolvedFields};
+use jimberlage_jira_client::{Error, Field, SearchIssue};
+use mockito::{Matcher, Server};
+use serde_json::json;
+
+mod common;
+
+use common::client;
+
+#[derive(Debug, JiraIssue)]
+struct Story {
+    #[jira(key)]
+    key: String,
+
+    summary: String,
+
+    priority: Option<Priority>,
+
+    #[jira(name = "Story Points")]
+    story_points: Option<f64>,
+
+    #[jira(field = "customfield_10020")]
+    sprint_ids: Vec<u64>,
+
+    #[jira(
+        field = "created",
+        deserialize_with = "jimberlage_jira_client::util::deserialize_datetime"
+    )]
+    created: DateTime<FixedOffset>,
+}
+
+fn fields() -> Vec<Field> {
+    serde_json::from_value(json!([
+        {"id": "summary", "name": "Summary"},
+        {"id": "customfield_10016", "name": "Story Points"},
+        {"id": "customfield_10020", "name": "Sprint"}
+    ]))
+    .unwrap()
+}
+
+fn catalog() -> FieldCatalog {
+    FieldCatalog::new(fields())
+}
+
+#[test]
+fn lists_and_resolves_the_fields_a_struct_reads() {
+    assert_eq!(
+        Story::fields(),
+        [
+            FieldRef::Id("summary"),
+            FieldRef::Id("priority"),
+            FieldRef::Name("Story Points"),
+            FieldRef::Id("customfield_10020"),
+            FieldRef::Id("created"),
+        ]
+    );
+    assert_eq!(
+        ResolvedFields::<Story>::new(&catalog()).unwrap().ids(),
+        [
+            "summary",
+            "priority",
+            "customfield_10016",
+            "customfield_10020",
+            "created"
+        ]
+    );
+}
+
+#[test]
+fn reports_names_which_do_not_resolve_to_one_field() {
+    let mut fields = fields();
+
+    fields.retain(|field| field.name != "Story Points");
+    assert!(matches!(
+        ResolvedFields::<Story>::new(&FieldCatalog::new(fields)),
+        Err(Error::UnknownField(name)) if name == "Story Points"
+    ));
+
+    let fields: Vec<Field> = serde_json::from_value(json!([
+        {"id": "customfield_10016", "name": "Story Points"},
+        {"id": "customfield_10028", "name": "Story Points"}
+    ]))
+    .unwrap();
+    assert!(matches!(
+        ResolvedFields::<Story>::new(&FieldCatalog::new(fields)),
+        Err(Error::AmbiguousField { ids, .. }) if ids == ["customfield_10016", "customfield_10028"]
+    ));
+}
+
+#[test]
+fn reads_structs_out_of_issues() {
+    let resolved = ResolvedFields::<Story>::new(&catalog()).unwrap();
+    let issue: SearchIssue = serde_json::from_value(json!({
+        "id": "10001",
+        "key": "SRE-1",
+        "fields": {
+            "summary": "Rotate certificates",
+            "priority": null,
+            "customfield_10016": 3.0,
+            "customfield_10020": [12, 13],
+            "created": "2023-05-10T14:23:01.123+0000"
+        }
+    }))
+    .unwrap();
+
+    let story = Story::from_issue(&issue, &resolved).unwrap();
+
+    assert_eq!(story.key, "SRE-1");
+    assert_eq!(story.summary, "Rotate certificates");
+    assert!(story.priority.is_none());
+    assert_eq!(story.story_points, Some(3.0));
+    assert_eq!(story.sprint_ids, [12, 13]);
+    assert_eq!(story.created.to_rfc3339(), "2023-05-10T14:23:01.123+00:00");
+
+    let issue: SearchIssue =
+        serde_json::from_value(json!({"id": "10002", "key": "SRE-2", "fields": {}})).unwrap();
+    assert!(matches!(
+        Story::from_issue(&issue, &resolved),
+        Err(Error::Deserialization { body, .. }) if body == "null"
+    ));
+}
+
+#[test]
+fn searches_for_only_the_fields_a_struct_reads() {
+    #[derive(JiraIssue)]
+    struct Summary {
+        #[jira(id)]
+        id: String,
+
+        summary: String,
+    }
+
+    let mut server = Server::new();
+    let search = server
+        .mock("POST", "/rest/api/3/search")
+        .match_body(Matcher::PartialJsonString(
+            r#"{"fields": ["summary"]}"#.to_owned(),
+        ))
+        .with_body(
+            r#"{"issues": [{"id": "10001", "key": "SRE-1", "fields": {"summary": "Rotate certificates"}}]}"#,
+        )
+        .create();
+    // Fields are only fetched when a struct refers to one by name.
+    let get_fields = server.mock("GET", "/rest/api/3/field").expect(0).create();
+
+    let issues = client(&server)
+        .search_all_as::<Summary>(&JQLStatement {
+            clause: JQLClause::Equals("project".into(), JQLValue::String("SRE".to_owned())),
+            order_by: None,
+        })
+        .unwrap();
+
+    search.assert();
+    get_fields.assert();
+    assert_eq!(issues[0].id, "10001");
+    assert_eq!(issues[0].summary, "Rotate certificates");
+}
+
+#[test]
+fn keeps_the_field_catalog_between_searches() {
+    #[derive(JiraIssue)]
+    struct Estimate {
+        #[jira(name = "Story Points")]
+        story_points: Option<f64>,
+    }
+
+    let mut server = Server::new();
+    server
+        .mock("POST", "/rest/api/3/search")
+        .match_body(Matcher::PartialJsonString(
+            r#"{"fields": ["customfield_10016"]}"#.to_owned(),
+        ))
+        .with_body(
+            r#"{"issues": [{"id": "10001", "key": "SRE-1", "fields": {"customfield_10016": 3}}]}"#,
+        )
+        .expect(2)
+        .create();
+    let get_fields = server
+        .mock("GET", "/rest/api/3/field")
+        .with_body(
+            serde_json::to_string(&json!([{"id": "customfield_10016", "name": "Story Points"}]))
+                .unwrap(),
+        )
+        .expect(2)
+        .create();
+    let jql = JQLStatement {
+        clause: JQLClause::Equals("project".into(), JQLValue::String("SRE".to_owned())),
+        order_by: None,
+    };
+    let client = client(&server);
+
+    client.search_all_as::<Estimate>(&jql).unwrap();
+    let issues = client.search_all_as::<Estimate>(&jql).unwrap();
+    client.refresh_field_catalog().unwrap();
+
+    get_fields.assert();
+    assert_eq!(issues[0].story_points, Some(3.0));
+}
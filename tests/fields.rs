@@ -0,0 +1,202 @@
+use jimberlage_jira_client::fields::FieldValue;
+use jimberlage_jira_client::{Error, RestClient, SearchIssue};
+use mockito::Server;
+use serde_json::json;
+
+fn issue(fields: serde_json::Value) -> SearchIssue {
+    serde_json::from_value(json!({"id": "10002", "key": "SRE-2", "fields": fields})).unwrap()
+}
+
+#[test]
+fn reads_standard_fields() {
+    let issue = issue(json!({
+        "summary": "Rotate certificates",
+        "status": {"id": "3", "name": "In Progress", "statusCategory": {"id": 4, "key": "indeterminate", "name": "In Progress"}},
+        "assignee": {"accountId": "5b10a2844c20165700ede21g", "displayName": "Jane Smith"},
+        "priority": {"id": "2", "name": "High"},
+        "issuetype": {"id": "10003", "name": "Sub-task", "subtask": true, "hierarchyLevel": -1},
+        "labels": ["security"],
+        "components": [{"id": "10000", "name": "Backend"}],
+        "fixVersions": [{"id": "10100", "name": "1.2.0", "released": false, "releaseDate": "2023-05-10"}],
+        "created": "2023-05-10T14:23:01.123+0000",
+        "resolutiondate": null,
+        "parent": {"id": "10001", "key": "SRE-1", "fields": {"summary": "Harden TLS"}},
+        "subtasks": [],
+        "issuelinks": [{
+            "id": "10200",
+            "type": {"id": "10000", "name": "Blocks", "inward": "is blocked by", "outward": "blocks"},
+            "outwardIssue": {"id": "10003", "key": "SRE-3"}
+        }],
+        "timetracking": {"originalEstimate": "1d", "originalEstimateSeconds": 28800}
+    }));
+
+    assert_eq!(
+        issue.summary().unwrap(),
+        FieldValue::Value("Rotate certificates".to_owned())
+    );
+    assert_eq!(
+        issue.status().unwrap().into_option().unwrap().name,
+        "In Progress"
+    );
+    assert_eq!(
+        issue
+            .assignee()
+            .unwrap()
+            .into_option()
+            .unwrap()
+            .display_name
+            .as_deref(),
+        Some("Jane Smith")
+    );
+    assert_eq!(
+        issue.priority().unwrap().into_option().unwrap().name,
+        "High"
+    );
+    assert!(issue.issue_type().unwrap().into_option().unwrap().subtask);
+    assert_eq!(
+        issue.labels().unwrap(),
+        FieldValue::Value(vec!["security".to_owned()])
+    );
+    assert_eq!(
+        issue.components().unwrap().into_option().unwrap()[0].name,
+        "Backend"
+    );
+    assert_eq!(
+        issue.fix_versions().unwrap().into_option().unwrap()[0]
+            .release_date
+            .as_deref(),
+        Some("2023-05-10")
+    );
+    assert_eq!(
+        issue.created().unwrap().map(|created| created.to_rfc3339()),
+        FieldValue::Value("2023-05-10T14:23:01.123+00:00".to_owned())
+    );
+    assert_eq!(issue.parent().unwrap().into_option().unwrap().key, "SRE-1");
+    assert!(issue.subtasks().unwrap().into_option().unwrap().is_empty());
+    assert_eq!(
+        issue.issue_links().unwrap().into_option().unwrap()[0]
+            .outward_issue
+            .as_ref()
+            .map(|issue| issue.key.as_str()),
+        Some("SRE-3")
+    );
+    assert_eq!(
+        issue
+            .time_tracking()
+            .unwrap()
+            .into_option()
+            .unwrap()
+            .original_estimate_seconds,
+        Some(28800)
+    );
+}
+
+#[test]
+fn tells_fields_which_were_not_requested_from_null_ones() {
+    let issue = issue(json!({"resolutiondate": null, "assignee": null}));
+
+    assert!(matches!(issue.resolution_date().unwrap(), FieldValue::Null));
+    assert!(matches!(issue.assignee().unwrap(), FieldValue::Null));
+    assert!(matches!(issue.updated().unwrap(), FieldValue::NotRequested));
+    assert!(matches!(
+        issue.reporter().unwrap(),
+        FieldValue::NotRequested
+    ));
+    assert!(issue.assignee().unwrap().is_requested());
+    assert!(!issue.reporter().unwrap().is_requested());
+}
+
+#[test]
+fn reports_fields_in_an_unexpected_shape() {
+    let issue = issue(json!({"summary": 42, "created": "yesterday"}));
+
+    assert!(matches!(
+        issue.summary(),
+        Err(Error::Deserialization { body, .. }) if body == "42"
+    ));
+    assert!(matches!(
+        issue.created(),
+        Err(Error::Deserialization { .. })
+    ));
+    assert_eq!(
+        issue.field::<u64>("summary").unwrap(),
+        FieldValue::Value(42)
+    );
+}
+
+#[test]
+fn resolves_fields_by_id_clause_name_and_display_name() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/rest/api/3/field")
+        .with_body(
+            r#"[
+                {
+                    "id": "summary", "name": "Summary", "custom": false, "orderable": true, "navigable": true,
+                    "searchable": true, "clauseNames": ["summary"], "schema": {"type": "string", "system": "summary"}
+                },
+                {
+                    "id": "customfield_10016", "key": "customfield_10016", "name": "Story Points", "custom": true,
+                    "orderable": true, "navigable": true, "searchable": true,
+                    "clauseNames": ["cf[10016]", "Story Points", "Story Points[Number]"],
+                    "schema": {
+                        "type": "number",
+                        "custom": "com.atlassian.jira.plugin.system.customfieldtypes:float",
+                        "customId": 10016
+                    }
+                },
+                {
+                    "id": "customfield_10020", "name": "Sprint", "custom": true, "searchable": true,
+                    "clauseNames": ["cf[10020]", "Sprint"],
+                    "schema": {"type": "array", "items": "json", "custom": "com.pyxis.greenhopper.jira:gh-sprint", "customId": 10020}
+                },
+                {
+                    "id": "customfield_10030", "name": "Sprint", "custom": true, "searchable": true,
+                    "clauseNames": ["cf[10030]", "Sprint"], "schema": {"type": "string", "customId": 10030}
+                },
+                {"id": "thumbnail", "name": "Images", "custom": false, "clauseNames": []}
+            ]"#,
+        )
+        .create();
+
+    let catalog = RestClient::new(&server.url(), "me@example.com", "token")
+        .unwrap()
+        .get_field_catalog()
+        .unwrap();
+
+    let story_points = catalog.resolve("Story Points").unwrap();
+    assert_eq!(story_points.id, "customfield_10016");
+    assert!(story_points.custom && story_points.searchable && story_points.orderable);
+    let schema = story_points.schema.as_ref().unwrap();
+    assert_eq!(schema.field_type, "number");
+    assert_eq!(schema.custom_id, Some(10016));
+    assert_eq!(
+        catalog.resolve("story points[number]").unwrap().id,
+        "customfield_10016"
+    );
+    assert_eq!(
+        catalog.resolve("cf[10030]").unwrap().id,
+        "customfield_10030"
+    );
+    assert_eq!(catalog.resolve("Images").unwrap().id, "thumbnail");
+    assert!(catalog.get("thumbnail").unwrap().schema.is_none());
+    assert_eq!(
+        catalog
+            .get("summary")
+            .unwrap()
+            .schema
+            .as_ref()
+            .unwrap()
+            .system
+            .as_deref(),
+        Some("summary")
+    );
+    assert!(matches!(
+        catalog.find_by_name("Sprint"),
+        Err(Error::AmbiguousField { ids, .. }) if ids == ["customfield_10020", "customfield_10030"]
+    ));
+    assert!(matches!(
+        catalog.resolve("cf[99999]"),
+        Err(Error::UnknownField(name)) if name == "cf[99999]"
+    ));
+}
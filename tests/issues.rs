@@ -0,0 +1,277 @@
+use jimberlage_jira_client::{
+    Error, IssueCreateRequest, IssueEditOptions, IssueEditRequest, IssueEditUpdate,
+    IssueEditUpdateLabel, IssueEditUpdateOperation, IssueExpand, TextBody,
+};
+use mockito::{Matcher, Server};
+use serde_json::{json, Map};
+
+mod common;
+
+use common::client;
+
+#[test]
+fn gets_an_issue_with_expanded_sections() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/rest/api/3/issue/SRE-1")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("fields".into(), "summary,status".into()),
+            Matcher::UrlEncoded("expand".into(), "names,renderedFields".into()),
+        ]))
+        .with_body(
+            r#"{
+                "id": "10001",
+                "key": "SRE-1",
+                "fields": {"summary": "Rotate certificates", "status": {"statusCategory": {"name": "Done"}}},
+                "renderedFields": {"summary": "Rotate certificates"},
+                "names": {"summary": "Summary", "status": "Status"}
+            }"#,
+        )
+        .create();
+
+    let issue = client(&server)
+        .get_issue(
+            "SRE-1",
+            &["summary".to_owned(), "status".to_owned()],
+            &[IssueExpand::Names, IssueExpand::RenderedFields],
+        )
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(issue.key, "SRE-1");
+    assert_eq!(issue.status_category(), Some("Done".to_owned()));
+    assert_eq!(
+        issue.names.unwrap().get("summary").map(String::as_str),
+        Some("Summary")
+    );
+    assert!(issue.changelog.is_none());
+}
+
+#[test]
+fn reports_missing_issues() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/rest/api/3/issue/SRE-404")
+        .with_status(404)
+        .with_body(r#"{"errorMessages": ["Issue does not exist or you do not have permission to see it."], "errors": {}}"#)
+        .create();
+
+    let result = client(&server).get_issue("SRE-404", &[], &[]);
+
+    assert!(matches!(result, Err(Error::NotFound(errors)) if errors.error_messages.len() == 1));
+}
+
+#[test]
+fn creates_an_issue() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/rest/api/3/issue")
+        .match_body(Matcher::PartialJsonString(
+            r#"{"fields": {"project": {"key": "SRE"}, "summary": "Rotate certificates"}}"#.to_owned(),
+        ))
+        .with_status(201)
+        .with_body(r#"{"id": "10002", "key": "SRE-2", "self": "https://jira.example.com/rest/api/3/issue/10002"}"#)
+        .create();
+
+    let created = client(&server)
+        .create_issue(&IssueCreateRequest::new(
+            "SRE",
+            "Task",
+            "Rotate certificates",
+        ))
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(created.key, "SRE-2");
+}
+
+#[test]
+fn reports_partial_failures_when_bulk_creating_issues() {
+    let mut server = Server::new();
+    server
+        .mock("POST", "/rest/api/3/issue/bulk")
+        .with_status(201)
+        .with_body(
+            r#"{
+                "issues": [{"id": "10002", "key": "SRE-2", "self": "https://jira.example.com/rest/api/3/issue/10002"}],
+                "errors": [{
+                    "status": 400,
+                    "elementErrors": {"errorMessages": [], "errors": {"issuetype": "The issue type selected is invalid."}},
+                    "failedElementNumber": 1
+                }]
+            }"#,
+        )
+        .create();
+
+    let result = client(&server)
+        .create_issues_bulk(&[
+            IssueCreateRequest::new("SRE", "Task", "Rotate certificates"),
+            IssueCreateRequest::new("SRE", "Chore", "Renew domains"),
+        ])
+        .unwrap();
+
+    assert_eq!(result.issues.len(), 1);
+    assert_eq!(result.errors[0].failed_element_number, 1);
+    assert!(result.errors[0]
+        .element_errors
+        .errors
+        .contains_key("issuetype"));
+}
+
+#[test]
+fn reports_total_failures_when_bulk_creating_issues() {
+    let mut server = Server::new();
+    server
+        .mock("POST", "/rest/api/3/issue/bulk")
+        .with_status(400)
+        .with_body(
+            r#"{
+                "issues": [],
+                "errors": [{
+                    "status": 400,
+                    "elementErrors": {"errorMessages": [], "errors": {"project": "valid project is required"}},
+                    "failedElementNumber": 0
+                }]
+            }"#,
+        )
+        .create();
+
+    let result = client(&server)
+        .create_issues_bulk(&[IssueCreateRequest::new(
+            "NOPE",
+            "Task",
+            "Rotate certificates",
+        )])
+        .unwrap();
+
+    assert!(result.issues.is_empty());
+    assert_eq!(result.errors.len(), 1);
+}
+
+fn add_label_request() -> IssueEditRequest {
+    IssueEditRequest {
+        update: IssueEditUpdate {
+            labels: vec![IssueEditUpdateLabel::Add("v2022.6.13".to_owned())],
+            ..IssueEditUpdate::default()
+        },
+        ..IssueEditRequest::default()
+    }
+}
+
+#[test]
+fn merges_other_operations_on_fields_with_a_dedicated_member() {
+    let mut update = IssueEditUpdate {
+        labels: vec![IssueEditUpdateLabel::Add("v2022.6.13".to_owned())],
+        ..IssueEditUpdate::default()
+    };
+    update.other.insert(
+        "labels".to_owned(),
+        vec![IssueEditUpdateOperation::Remove(json!("v2022.5.10"))],
+    );
+    update.other.insert(
+        "fixVersions".to_owned(),
+        vec![IssueEditUpdateOperation::Add(json!({"name": "2022.6"}))],
+    );
+
+    let serialized = serde_json::to_string(&update).unwrap();
+
+    assert_eq!(serialized.matches(r#""labels""#).count(), 1);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&serialized).unwrap(),
+        json!({
+            "labels": [{"add": "v2022.6.13"}, {"remove": "v2022.5.10"}],
+            "fixVersions": [{"add": {"name": "2022.6"}}]
+        })
+    );
+}
+
+#[test]
+fn edits_an_issue() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("PUT", "/rest/api/3/issue/SRE-1")
+        .match_query(Matcher::UrlEncoded("notifyUsers".into(), "false".into()))
+        .match_body(Matcher::JsonString(
+            r#"{"update": {"labels": [{"add": "v2022.6.13"}]}}"#.to_owned(),
+        ))
+        .with_status(204)
+        .create();
+
+    let issue = client(&server)
+        .edit_issue(
+            "SRE-1",
+            &add_label_request(),
+            &IssueEditOptions {
+                notify_users: Some(false),
+                ..IssueEditOptions::default()
+            },
+        )
+        .unwrap();
+
+    mock.assert();
+    assert!(issue.is_none());
+}
+
+#[test]
+fn returns_the_edited_issue_when_asked() {
+    let mut server = Server::new();
+    server
+        .mock("PUT", "/rest/api/3/issue/SRE-1")
+        .match_query(Matcher::UrlEncoded("returnIssue".into(), "true".into()))
+        .with_status(200)
+        .with_body(r#"{"id": "10001", "key": "SRE-1", "fields": {"labels": ["v2022.6.13"]}}"#)
+        .create();
+
+    let issue = client(&server)
+        .edit_issue(
+            "SRE-1",
+            &add_label_request(),
+            &IssueEditOptions {
+                return_issue: true,
+                ..IssueEditOptions::default()
+            },
+        )
+        .unwrap();
+
+    assert_eq!(issue.unwrap().key, "SRE-1");
+}
+
+#[test]
+fn transitions_an_issue_by_target_status() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/rest/api/3/issue/SRE-1/transitions")
+        .with_body(
+            r#"{"transitions": [
+                {"id": "11", "name": "Start work", "to": {"id": "3", "name": "In Progress"}},
+                {"id": "31", "name": "Close", "to": {"id": "10001", "name": "Done", "statusCategory": {"id": 3, "key": "done", "name": "Done"}}}
+            ]}"#,
+        )
+        .create();
+    let transition_mock = server
+        .mock("POST", "/rest/api/3/issue/SRE-1/transitions")
+        .match_body(Matcher::JsonString(
+            r#"{"transition": {"id": "31"}, "update": {"comment": [{"add": {"body": "Deployed"}}]}}"#
+                .to_owned(),
+        ))
+        .with_status(204)
+        .create();
+    let client = client(&server);
+
+    let transition = client
+        .get_transition_to_status("SRE-1", "done")
+        .unwrap()
+        .unwrap();
+    client
+        .transition_issue(
+            "SRE-1",
+            &transition.id,
+            &Map::new(),
+            &IssueEditUpdate::default(),
+            Some(&TextBody::WikiMarkup("Deployed".to_owned())),
+        )
+        .unwrap();
+
+    transition_mock.assert();
+    assert_eq!(transition.to.status_category.unwrap().key, "done");
+}
@@ -0,0 +1,1032 @@
+use chrono::{FixedOffset, NaiveDate, TimeDelta, TimeZone};
+use jimberlage_jira_client::jql::{
+    field, JQLClause, JQLDuration, JQLDurationUnit, JQLEmpty, JQLField, JQLFunction,
+    JQLHistoryPredicate, JQLOrderBy, JQLOrderByPart, JQLOrdering, JQLStatement, JQLValue,
+    SerializableToJQL,
+};
+
+fn string(s: &str) -> JQLValue {
+    JQLValue::String(s.to_owned())
+}
+
+fn equals(field: &str, value: &str) -> Box<JQLClause> {
+    Box::new(JQLClause::Equals(field.into(), string(value)))
+}
+
+#[test]
+fn serializes_comparison_operators() {
+    let cases = vec![
+        (
+            JQLClause::NotEquals("status".into(), string("Done")),
+            "status != \"Done\"",
+        ),
+        (
+            JQLClause::GreaterThan("votes".into(), string("4")),
+            "votes > \"4\"",
+        ),
+        (
+            JQLClause::LessThan("votes".into(), string("4")),
+            "votes < \"4\"",
+        ),
+        (
+            JQLClause::NotIn("project".into(), vec![string("PE"), string("SRE")]),
+            "project NOT IN (\"PE\", \"SRE\")",
+        ),
+        (
+            JQLClause::Contains("summary".into(), string("flaky")),
+            "summary ~ \"flaky\"",
+        ),
+        (
+            JQLClause::NotContains("summary".into(), string("flaky")),
+            "summary !~ \"flaky\"",
+        ),
+        (
+            JQLClause::Is("assignee".into(), JQLEmpty::Null),
+            "assignee IS NULL",
+        ),
+        (
+            JQLClause::IsNot("fixVersion".into(), JQLEmpty::Empty),
+            "fixVersion IS NOT EMPTY",
+        ),
+    ];
+
+    for (clause, expected) in cases {
+        assert_eq!(clause.serialize_to_jql(), expected);
+    }
+}
+
+#[test]
+fn serializes_history_operators() {
+    let start = JQLValue::NaiveDate(NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+    let end = JQLValue::NaiveDate(NaiveDate::from_ymd_opt(2023, 2, 1).unwrap());
+    let cases = vec![
+        (
+            JQLClause::Was("status".into(), string("In Progress"), vec![]),
+            "status WAS \"In Progress\"",
+        ),
+        (
+            JQLClause::WasIn(
+                "status".into(),
+                vec![string("Open"), string("Reopened")],
+                vec![JQLHistoryPredicate::During(start.clone(), end.clone())],
+            ),
+            "status WAS IN (\"Open\", \"Reopened\") DURING (\"2023-01-01\", \"2023-02-01\")",
+        ),
+        (
+            JQLClause::WasNot(
+                "assignee".into(),
+                string("jsmith"),
+                vec![JQLHistoryPredicate::Before(end.clone())],
+            ),
+            "assignee WAS NOT \"jsmith\" BEFORE \"2023-02-01\"",
+        ),
+        (
+            JQLClause::WasNotIn("status".into(), vec![string("Done")], vec![]),
+            "status WAS NOT IN (\"Done\")",
+        ),
+        (
+            JQLClause::Changed(
+                "priority".into(),
+                vec![
+                    JQLHistoryPredicate::By(string("jsmith")),
+                    JQLHistoryPredicate::After(start.clone()),
+                    JQLHistoryPredicate::On(end),
+                ],
+            ),
+            "priority CHANGED BY \"jsmith\" AFTER \"2023-01-01\" ON \"2023-02-01\"",
+        ),
+        (
+            JQLClause::Changed("status".into(), vec![]),
+            "status CHANGED",
+        ),
+    ];
+
+    for (clause, expected) in cases {
+        assert_eq!(clause.serialize_to_jql(), expected);
+    }
+}
+
+#[test]
+fn only_parenthesizes_where_precedence_requires_it() {
+    let cases = vec![
+        // AND binds more tightly than OR, so neither needs parentheses here.
+        (
+            JQLClause::Or(vec![
+                Box::new(JQLClause::And(vec![equals("x", "1"), equals("b", "2")])),
+                equals("c", "3"),
+            ]),
+            "x = \"1\" AND b = \"2\" OR c = \"3\"",
+        ),
+        (
+            JQLClause::And(vec![
+                Box::new(JQLClause::Or(vec![equals("x", "1"), equals("b", "2")])),
+                equals("c", "3"),
+            ]),
+            "(x = \"1\" OR b = \"2\") AND c = \"3\"",
+        ),
+        // Nested groups of the same kind flatten.
+        (
+            JQLClause::Or(vec![
+                equals("x", "1"),
+                Box::new(JQLClause::Or(vec![equals("b", "2"), equals("c", "3")])),
+            ]),
+            "x = \"1\" OR b = \"2\" OR c = \"3\"",
+        ),
+        (
+            JQLClause::Not(Box::new(JQLClause::And(vec![
+                equals("x", "1"),
+                equals("b", "2"),
+            ]))),
+            "NOT (x = \"1\" AND b = \"2\")",
+        ),
+        (
+            JQLClause::And(vec![
+                Box::new(JQLClause::Not(equals("x", "1"))),
+                equals("b", "2"),
+            ]),
+            "NOT x = \"1\" AND b = \"2\"",
+        ),
+        (
+            JQLClause::Not(Box::new(JQLClause::Not(equals("x", "1")))),
+            "NOT NOT x = \"1\"",
+        ),
+        // Groups of one clause, and empty groups, do not need parentheses of their own.
+        (
+            JQLClause::Not(Box::new(JQLClause::Or(vec![
+                Box::new(JQLClause::And(vec![])),
+                equals("x", "1"),
+            ]))),
+            "NOT x = \"1\"",
+        ),
+        (
+            JQLClause::And(vec![
+                Box::new(JQLClause::Or(vec![Box::new(JQLClause::Or(vec![
+                    equals("x", "1"),
+                    equals("b", "2"),
+                ]))])),
+                equals("c", "3"),
+            ]),
+            "(x = \"1\" OR b = \"2\") AND c = \"3\"",
+        ),
+        // NOT of an empty group is empty too, and left out like the group would be.
+        (JQLClause::Not(Box::new(JQLClause::And(vec![]))), ""),
+        (
+            JQLClause::And(vec![
+                Box::new(JQLClause::Not(Box::new(JQLClause::Or(vec![])))),
+                equals("x", "1"),
+            ]),
+            "x = \"1\"",
+        ),
+        (
+            JQLClause::Or(vec![
+                Box::new(JQLClause::Not(Box::new(JQLClause::Not(Box::new(
+                    JQLClause::And(vec![]),
+                ))))),
+                equals("x", "1"),
+                equals("b", "2"),
+            ]),
+            "x = \"1\" OR b = \"2\"",
+        ),
+    ];
+
+    for (clause, expected) in cases {
+        assert_eq!(clause.serialize_to_jql(), expected);
+    }
+}
+
+#[test]
+fn orders_statements_without_a_clause() {
+    let statement = JQLStatement {
+        clause: JQLClause::And(vec![]),
+        order_by: Some(JQLOrderBy(vec![JQLOrderByPart {
+            field: "created".into(),
+            ordering: Some(JQLOrdering::Desc),
+        }])),
+    };
+
+    assert_eq!(statement.serialize_to_jql(), "ORDER BY created DESC");
+
+    let statement = JQLStatement {
+        clause: JQLClause::Not(Box::new(JQLClause::And(vec![]))),
+        ..statement
+    };
+
+    assert_eq!(statement.serialize_to_jql(), "ORDER BY created DESC");
+}
+
+#[test]
+fn quotes_fields_which_are_not_plain_words() {
+    let statement = JQLStatement {
+        clause: JQLClause::And(vec![
+            Box::new(JQLClause::GreaterThan(
+                "Story Points".into(),
+                JQLValue::Int(3),
+            )),
+            Box::new(JQLClause::Equals("Epic Link".into(), string("SRE-1"))),
+            Box::new(JQLClause::Equals(
+                JQLField::Name("Say \"hi\"".to_owned()),
+                string("yes"),
+            )),
+            Box::new(JQLClause::Is("cf[10016]".into(), JQLEmpty::Empty)),
+            Box::new(JQLClause::Equals(
+                JQLField::System("issue.property[support].level".to_owned()),
+                string("gold"),
+            )),
+            Box::new(JQLClause::Equals(
+                JQLField::System("select".to_owned()),
+                string("x"),
+            )),
+        ]),
+        order_by: Some(JQLOrderBy(vec![
+            JQLOrderByPart {
+                field: JQLField::from_custom_field_id("customfield_10020").unwrap(),
+                ordering: Some(JQLOrdering::Desc),
+            },
+            JQLOrderByPart {
+                field: "Order".into(),
+                ordering: None,
+            },
+        ])),
+    };
+
+    assert_eq!(
+        statement.serialize_to_jql(),
+        "\"Story Points\" > 3 AND \"Epic Link\" = \"SRE\\\\-1\" AND \"Say \\\"hi\\\"\" = \"yes\" \
+         AND cf[10016] IS EMPTY AND issue.property[support].level = \"gold\" AND \"select\" = \"x\" \
+         ORDER BY cf[10020] DESC, \"Order\""
+    );
+    assert_eq!(JQLField::from("CF[42]"), JQLField::Custom(42));
+    assert_eq!(JQLField::from("customfield_10016"), JQLField::Custom(10016));
+    assert_eq!(
+        field("customfield_10016").is_empty().serialize_to_jql(),
+        "cf[10016] IS EMPTY"
+    );
+    assert_eq!(
+        JQLField::from("fixVersion"),
+        JQLField::System("fixVersion".to_owned())
+    );
+    assert_eq!(JQLField::from("order"), JQLField::Name("order".to_owned()));
+    assert_eq!(
+        JQLField::from("issue.property[support].level"),
+        JQLField::System("issue.property[support].level".to_owned())
+    );
+    assert_eq!(
+        field("issue.property[support].level")
+            .eq("gold")
+            .serialize_to_jql(),
+        "issue.property[support].level = \"gold\""
+    );
+    // The builder reads the field the way the parser does.
+    assert_eq!(
+        parse("issue.property[support].level = gold"),
+        field("issue.property[support].level").eq("gold")
+    );
+    assert_eq!(JQLField::from_custom_field_id("summary"), None);
+}
+
+#[test]
+fn serializes_values() {
+    let cases: Vec<(JQLValue, &str)> = vec![
+        (42.into(), "42"),
+        ((-3).into(), "-3"),
+        (7u8.into(), "7"),
+        ((-7i16).into(), "-7"),
+        (7usize.into(), "7"),
+        ((-7isize).into(), "-7"),
+        (u64::MAX.into(), "\"18446744073709551615\""),
+        (0.5.into(), "0.5"),
+        ("SRE".into(), "\"SRE\""),
+        (
+            NaiveDate::from_ymd_opt(2023, 5, 10)
+                .unwrap()
+                .and_hms_opt(9, 5, 0)
+                .unwrap()
+                .into(),
+            "\"2023/05/10 09:05\"",
+        ),
+        (JQLDuration::days(4).into(), "4d"),
+        (JQLDuration::new(-1, JQLDurationUnit::Year).into(), "-1y"),
+        (JQLValue::Empty, "EMPTY"),
+        (JQLValue::Null, "NULL"),
+    ];
+
+    for (value, expected) in cases {
+        assert_eq!(value.serialize_to_jql(), expected);
+    }
+}
+
+#[test]
+fn converts_every_integer_type() {
+    let ids: Vec<u64> = vec![10001, 10002];
+    let votes: usize = 5;
+
+    assert_eq!(
+        field("id")
+            .in_(ids)
+            .and(field("votes").gt(votes))
+            .serialize_to_jql(),
+        "id IN (10001, 10002) AND votes > 5"
+    );
+    assert_eq!(JQLValue::from(i64::MAX as u64), JQLValue::Int(i64::MAX));
+    assert_eq!(
+        JQLValue::from(i64::MAX as u64 + 1),
+        JQLValue::String("9223372036854775808".to_owned())
+    );
+}
+
+#[test]
+fn serializes_functions() {
+    let cases = vec![
+        (JQLFunction::CurrentUser, "currentUser()"),
+        (
+            JQLFunction::MembersOf("jira-users".to_owned()),
+            "membersOf(\"jira-users\")",
+        ),
+        (JQLFunction::StartOfDay(None), "startOfDay()"),
+        (
+            JQLFunction::StartOfMonth(Some(JQLDuration::new(-1, JQLDurationUnit::Month))),
+            "startOfMonth(-1M)",
+        ),
+        (JQLFunction::StartOfYear(None), "startOfYear()"),
+        (
+            JQLFunction::EndOfDay(Some(JQLDuration::hours(2))),
+            "endOfDay(2h)",
+        ),
+        (JQLFunction::EndOfWeek(None), "endOfWeek()"),
+        (JQLFunction::EndOfYear(None), "endOfYear()"),
+        (JQLFunction::Now, "now()"),
+        (JQLFunction::OpenSprints, "openSprints()"),
+        (JQLFunction::ClosedSprints, "closedSprints()"),
+        (JQLFunction::FutureSprints, "futureSprints()"),
+        (JQLFunction::IssueHistory, "issueHistory()"),
+        (
+            JQLFunction::LinkedIssues("SRE-1".to_owned(), None),
+            "linkedIssues(\"SRE-1\")",
+        ),
+        (JQLFunction::ReleasedVersions(None), "releasedVersions()"),
+        (
+            JQLFunction::UnreleasedVersions(Some("SRE".to_owned())),
+            "unreleasedVersions(\"SRE\")",
+        ),
+        (
+            JQLFunction::LatestReleasedVersion("SRE".to_owned()),
+            "latestReleasedVersion(\"SRE\")",
+        ),
+        (
+            JQLFunction::UpdatedBy(
+                "jsmith".to_owned(),
+                Some(Box::new(JQLDuration::weeks(-2).into())),
+                Some(Box::new(JQLFunction::Now.into())),
+            ),
+            "updatedBy(\"jsmith\", -2w, now())",
+        ),
+        (
+            JQLFunction::Other("issueFunction".to_owned(), vec!["say \"hi\"".into()]),
+            "issueFunction(\"say \\\"hi\\\"\")",
+        ),
+        // Arguments are not text searches, so only quotes and backslashes are escaped in them.
+        (
+            JQLFunction::Other("portfolioChildIssuesOf".to_owned(), vec!["SRE-1".into()]),
+            "portfolioChildIssuesOf(\"SRE-1\")",
+        ),
+        (
+            JQLFunction::Other("subtasksOf".to_owned(), vec!["status != Done".into()]),
+            "subtasksOf(\"status != Done\")",
+        ),
+        (
+            JQLFunction::UpdatedBy("jsmith".to_owned(), Some(Box::new("-1d".into())), None),
+            "updatedBy(\"jsmith\", \"-1d\")",
+        ),
+    ];
+
+    for (function, expected) in cases {
+        assert_eq!(function.serialize_to_jql(), expected);
+    }
+    assert_eq!(
+        JQLClause::In(
+            "fixVersion".into(),
+            vec![
+                JQLFunction::UnreleasedVersions(None).into(),
+                JQLValue::Empty
+            ]
+        )
+        .serialize_to_jql(),
+        "fixVersion IN (unreleasedVersions(), EMPTY)"
+    );
+}
+
+#[test]
+fn builds_clauses_fluently() {
+    let status = field("status");
+    let since = FixedOffset::east_opt(2 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(2023, 5, 10, 14, 23, 0)
+        .unwrap();
+
+    let clause = field("project")
+        .in_(["SRE", "PE"])
+        .and(status.ne("Done").or(status.is_empty()))
+        .and(field("votes").ge(4).and(!field("Story Points").lt(2.5)))
+        .and(
+            status
+                .was_in(["Open", "Reopened"])
+                .by(JQLFunction::CurrentUser),
+        )
+        .and(
+            field("assignee")
+                .changed()
+                .during(NaiveDate::from_ymd_opt(2023, 5, 1).unwrap(), since)
+                .changed_to(JQLValue::Empty),
+        )
+        .and(field("created").gt(TimeDelta::days(-14)));
+
+    assert_eq!(
+        clause.serialize_to_jql(),
+        "project IN (\"SRE\", \"PE\") AND (status != \"Done\" OR status IS EMPTY) AND votes >= 4 \
+         AND NOT \"Story Points\" < 2.5 AND status WAS IN (\"Open\", \"Reopened\") BY currentUser() \
+         AND assignee CHANGED DURING (\"2023-05-01\", \"2023/05/10 14:23\") TO EMPTY AND created > -2w"
+    );
+    assert!(matches!(&clause, JQLClause::And(clauses) if clauses.len() == 7));
+    assert_eq!(
+        field("cf[10016]")
+            .is_not_empty()
+            .order_by([field("rank").into(), field("created").desc()])
+            .serialize_to_jql(),
+        "cf[10016] IS NOT EMPTY ORDER BY rank, created DESC"
+    );
+    assert_eq!(
+        JQLStatement::from(field("labels").not_in(Vec::<String>::new())),
+        JQLStatement::parse("labels NOT IN ()").unwrap()
+    );
+    assert_eq!(
+        field("status")
+            .changed()
+            .changed_from("In Progress")
+            .changed_to("Done")
+            .serialize_to_jql(),
+        "status CHANGED FROM \"In Progress\" TO \"Done\""
+    );
+}
+
+#[test]
+#[should_panic(expected = "history predicates only apply to WAS and CHANGED clauses")]
+fn rejects_history_on_other_clauses() {
+    field("status").eq("Done").after("2023-05-10");
+}
+
+fn parse(jql: &str) -> JQLClause {
+    JQLStatement::parse(jql).unwrap().clause
+}
+
+#[test]
+fn parses_precedence_of_not_and_or() {
+    assert_eq!(
+        parse("x = 1 OR b = 2 AND NOT c = 3"),
+        JQLClause::Or(vec![
+            Box::new(JQLClause::Equals("x".into(), JQLValue::Int(1))),
+            Box::new(JQLClause::And(vec![
+                Box::new(JQLClause::Equals("b".into(), JQLValue::Int(2))),
+                Box::new(JQLClause::Not(Box::new(JQLClause::Equals(
+                    "c".into(),
+                    JQLValue::Int(3)
+                )))),
+            ])),
+        ])
+    );
+    assert_eq!(
+        parse("(x = 1 || b = 2) && !c = 3"),
+        JQLClause::And(vec![
+            Box::new(JQLClause::Or(vec![
+                Box::new(JQLClause::Equals("x".into(), JQLValue::Int(1))),
+                Box::new(JQLClause::Equals("b".into(), JQLValue::Int(2))),
+            ])),
+            Box::new(JQLClause::Not(Box::new(JQLClause::Equals(
+                "c".into(),
+                JQLValue::Int(3)
+            )))),
+        ])
+    );
+}
+
+#[test]
+fn parses_operators_and_values() {
+    let cases = vec![
+        (
+            "fixVersion not in (\"1.0\", EMPTY)",
+            JQLClause::NotIn("fixVersion".into(), vec![string("1.0"), JQLValue::Empty]),
+        ),
+        (
+            "sprint in openSprints()",
+            JQLClause::In(
+                "sprint".into(),
+                vec![JQLValue::Function(JQLFunction::OpenSprints)],
+            ),
+        ),
+        (
+            "summary !~ 'say \\'hi\\''",
+            JQLClause::NotContains("summary".into(), string("say 'hi'")),
+        ),
+        (
+            "assignee is not null",
+            JQLClause::IsNot("assignee".into(), JQLEmpty::Null),
+        ),
+        (
+            "cf[10016] >= 2.5",
+            JQLClause::GreaterThanEquals("cf[10016]".into(), JQLValue::Float(2.5)),
+        ),
+        (
+            "created > -2w",
+            JQLClause::GreaterThan("created".into(), JQLDuration::weeks(-2).into()),
+        ),
+        // Only numbers written as they serialize are read as numbers, and quoted relative dates are read like quoted
+        // dates.
+        (
+            "fixVersion = 1.10",
+            JQLClause::Equals("fixVersion".into(), string("1.10")),
+        ),
+        (
+            "cf[10010] = 007",
+            JQLClause::Equals("cf[10010]".into(), string("007")),
+        ),
+        (
+            "created > \"-2w\"",
+            JQLClause::GreaterThan("created".into(), JQLDuration::weeks(-2).into()),
+        ),
+        (
+            "created < '+1d'",
+            JQLClause::LessThan("created".into(), JQLDuration::days(1).into()),
+        ),
+        (
+            "labels = \"4d\"",
+            JQLClause::Equals("labels".into(), string("4d")),
+        ),
+        (
+            "created < \"2023/05/10 14:23\"",
+            JQLClause::LessThan(
+                "created".into(),
+                NaiveDate::from_ymd_opt(2023, 5, 10)
+                    .unwrap()
+                    .and_hms_opt(14, 23, 0)
+                    .unwrap()
+                    .into(),
+            ),
+        ),
+        (
+            "due <= 2023-05-10",
+            JQLClause::LessThanEquals(
+                "due".into(),
+                NaiveDate::from_ymd_opt(2023, 5, 10).unwrap().into(),
+            ),
+        ),
+        (
+            "labels = \"\\\\[urgent\\\\]\"",
+            JQLClause::Equals("labels".into(), string("[urgent]")),
+        ),
+    ];
+
+    for (jql, expected) in cases {
+        assert_eq!(parse(jql), expected, "{}", jql);
+    }
+}
+
+#[test]
+fn parses_functions() {
+    let cases = vec![
+        (
+            "startOfMonth(-1)",
+            JQLFunction::StartOfMonth(Some(JQLDuration::new(-1, JQLDurationUnit::Month))),
+        ),
+        ("ENDOFDAY()", JQLFunction::EndOfDay(None)),
+        (
+            "membersOf(jira-users)",
+            JQLFunction::MembersOf("jira-users".to_owned()),
+        ),
+        (
+            "linkedIssues(\"SRE-1\", \"is blocked by\")",
+            JQLFunction::LinkedIssues("SRE-1".to_owned(), Some("is blocked by".to_owned())),
+        ),
+        (
+            "updatedBy(jsmith, -1w)",
+            JQLFunction::UpdatedBy(
+                "jsmith".to_owned(),
+                Some(Box::new(JQLDuration::weeks(-1).into())),
+                None,
+            ),
+        ),
+        (
+            "subtasksOf(\"project = SRE\")",
+            JQLFunction::Other("subtasksOf".to_owned(), vec![string("project = SRE")]),
+        ),
+        (
+            "subtasksOf(\"status != Done\", 'C:\\\\')",
+            JQLFunction::Other(
+                "subtasksOf".to_owned(),
+                vec![string("status != Done"), string("C:\\")],
+            ),
+        ),
+        (
+            "portfolioChildIssuesOf(\"SRE\\-1\")",
+            JQLFunction::Other("portfolioChildIssuesOf".to_owned(), vec![string("SRE\\-1")]),
+        ),
+        (
+            "now(1)",
+            JQLFunction::Other("now".to_owned(), vec![JQLValue::Int(1)]),
+        ),
+    ];
+
+    for (function, expected) in cases {
+        assert_eq!(
+            parse(&format!("issue in {}", function)),
+            JQLClause::In("issue".into(), vec![JQLValue::Function(expected)]),
+            "{}",
+            function
+        );
+    }
+}
+
+#[test]
+fn parses_history_operators_and_ordering() {
+    let statement = JQLStatement::parse(
+        "status was not in (Open, Reopened) during (\"2023-01-01\", now()) by jsmith \
+         and priority changed from Low to High order by priority desc, created",
+    )
+    .unwrap();
+
+    assert_eq!(
+        statement.clause,
+        JQLClause::And(vec![
+            Box::new(JQLClause::WasNotIn(
+                "status".into(),
+                vec![string("Open"), string("Reopened")],
+                vec![
+                    JQLHistoryPredicate::During(
+                        NaiveDate::from_ymd_opt(2023, 1, 1).unwrap().into(),
+                        JQLFunction::Now.into()
+                    ),
+                    JQLHistoryPredicate::By(string("jsmith")),
+                ],
+            )),
+            Box::new(JQLClause::Changed(
+                "priority".into(),
+                vec![
+                    JQLHistoryPredicate::From(string("Low")),
+                    JQLHistoryPredicate::To(string("High")),
+                ],
+            )),
+        ])
+    );
+    assert_eq!(
+        statement.order_by,
+        Some(JQLOrderBy(vec![
+            JQLOrderByPart {
+                field: "priority".into(),
+                ordering: Some(JQLOrdering::Desc),
+            },
+            JQLOrderByPart {
+                field: "created".into(),
+                ordering: None,
+            },
+        ]))
+    );
+    assert_eq!(
+        "ORDER BY created".parse::<JQLStatement>().unwrap().clause,
+        JQLClause::And(vec![])
+    );
+}
+
+#[test]
+fn parses_fields() {
+    assert_eq!(
+        JQLStatement::parse("\"Story Points\" > 3 AND cf[10016] IS EMPTY ORDER BY \"Epic Link\"")
+            .unwrap(),
+        JQLStatement {
+            clause: JQLClause::And(vec![
+                Box::new(JQLClause::GreaterThan(
+                    JQLField::Name("Story Points".to_owned()),
+                    JQLValue::Int(3)
+                )),
+                Box::new(JQLClause::Is(JQLField::Custom(10016), JQLEmpty::Empty)),
+            ]),
+            order_by: Some(JQLOrderBy(vec![JQLOrderByPart {
+                field: JQLField::Name("Epic Link".to_owned()),
+                ordering: None,
+            }])),
+        }
+    );
+}
+
+#[test]
+fn reports_where_parsing_failed() {
+    let cases = vec![
+        (
+            "project = ",
+            "expected a value, found the end of the query",
+            10..10,
+        ),
+        ("project SRE", "expected an operator, found SRE", 8..11),
+        ("(a = 1", "expected \")\", found the end of the query", 6..6),
+        (
+            "a = 1 b = 2",
+            "expected AND, OR, ORDER BY or the end of the query, found b",
+            6..7,
+        ),
+        (
+            "a in b",
+            "expected a list of values or a function, found b",
+            5..6,
+        ),
+        ("a is 1", "expected EMPTY or NULL, found 1", 5..6),
+        ("summary ~ \"flaky", "unterminated string", 10..16),
+        ("a = \"\\u12\"", "expected four hex digits after \\u", 5..10),
+    ];
+
+    for (jql, message, span) in cases {
+        let error = JQLStatement::parse(jql).unwrap_err();
+        assert_eq!(
+            (error.message.as_str(), error.span),
+            (message, span),
+            "{}",
+            jql
+        );
+    }
+}
+
+#[test]
+fn serializes_parsed_jql_as_written() {
+    let cases = vec![
+        ("fixVersion = 1.10", "fixVersion = \"1.10\""),
+        (
+            "fixVersion in (2.0, 1.10)",
+            "fixVersion IN (\"2.0\", \"1.10\")",
+        ),
+        ("cf[10010] = 007", "cf[10010] = \"007\""),
+        ("key = SRE-1", "key = \"SRE\\\\-1\""),
+        ("created > \"-2w\"", "created > -2w"),
+        ("created >= -2w", "created >= -2w"),
+        (
+            "votes > 10 and storyPoints = 2.5",
+            "votes > 10 AND storyPoints = 2.5",
+        ),
+        ("x = -0", "x = -0"),
+        (
+            "project = SRE and (assignee is empty or assignee = currentUser())",
+            "project = \"SRE\" AND (assignee IS EMPTY OR assignee = currentUser())",
+        ),
+        (
+            "created >= startOfMonth(-1) and updated < endOfDay()",
+            "created >= startOfMonth(-1M) AND updated < endOfDay()",
+        ),
+        (
+            "issue in portfolioChildIssuesOf(\"SRE-1\")",
+            "issue IN (portfolioChildIssuesOf(\"SRE-1\"))",
+        ),
+        (
+            "issue in subtasksOf(\"status != Done\") order by rank",
+            "issue IN (subtasksOf(\"status != Done\")) ORDER BY rank",
+        ),
+        (
+            "issueFunction in linkedIssuesOf('project = SRE', \"blocks\", 2)",
+            "issueFunction IN (linkedIssuesOf(\"project = SRE\", \"blocks\", 2))",
+        ),
+        (
+            "issue in updatedBy(jsmith, \"-1d\")",
+            "issue IN (updatedBy(\"jsmith\", \"-1d\"))",
+        ),
+    ];
+
+    for (jql, expected) in cases {
+        let statement = JQLStatement::parse(jql).unwrap();
+        let serialized = statement.serialize_to_jql();
+
+        assert_eq!(serialized, expected, "{}", jql);
+        assert_eq!(
+            JQLStatement::parse(&serialized).unwrap(),
+            statement,
+            "{}",
+            jql
+        );
+    }
+}
+
+mod round_trip {
+    use chrono::NaiveDate;
+    use jimberlage_jira_client::jql::{
+        JQLClause, JQLDuration, JQLDurationUnit, JQLEmpty, JQLField, JQLFunction,
+        JQLHistoryPredicate, JQLOrderBy, JQLOrderByPart, JQLOrdering, JQLStatement, JQLValue,
+        SerializableToJQL,
+    };
+    use proptest::prelude::*;
+
+    const KEYWORDS: &[&str] = &[
+        "and", "or", "not", "in", "is", "was", "changed", "order", "by", "empty", "null", "asc",
+        "desc", "after", "before", "on", "during", "from", "to",
+    ];
+
+    /// Names of functions which are read into a typed `JQLFunction`, so `Other` does not use them.
+    const FUNCTIONS: &[&str] = &[
+        "currentuser",
+        "membersof",
+        "startofday",
+        "startofweek",
+        "startofmonth",
+        "startofyear",
+        "endofday",
+        "endofweek",
+        "endofmonth",
+        "endofyear",
+        "now",
+        "opensprints",
+        "closedsprints",
+        "futuresprints",
+        "issuehistory",
+        "linkedissues",
+        "releasedversions",
+        "unreleasedversions",
+        "latestreleasedversion",
+        "updatedby",
+    ];
+
+    fn word() -> impl Strategy<Value = String> {
+        "[a-zA-Z][a-zA-Z0-9_]{0,8}".prop_filter("keywords are not fields", |field| {
+            !KEYWORDS.contains(&field.to_lowercase().as_str())
+        })
+    }
+
+    /// Fields which read back as they were written.  System fields which are reserved words are quoted, and so read
+    /// back as names.
+    fn field() -> impl Strategy<Value = JQLField> {
+        prop_oneof![
+            4 => word().prop_map(JQLField::from),
+            1 => any::<u64>().prop_map(JQLField::Custom),
+            1 => "[ -~]{0,12}".prop_map(JQLField::Name),
+        ]
+    }
+
+    fn duration() -> impl Strategy<Value = JQLDuration> {
+        (
+            -1000i64..1000,
+            prop_oneof![
+                Just(JQLDurationUnit::Minute),
+                Just(JQLDurationUnit::Hour),
+                Just(JQLDurationUnit::Day),
+                Just(JQLDurationUnit::Week),
+                Just(JQLDurationUnit::Month),
+                Just(JQLDurationUnit::Year),
+            ],
+        )
+            .prop_map(|(amount, unit)| JQLDuration::new(amount, unit))
+    }
+
+    fn scalar() -> impl Strategy<Value = JQLValue> {
+        prop_oneof![
+            any::<String>().prop_map(JQLValue::String),
+            any::<i64>().prop_map(JQLValue::Int),
+            prop_oneof![-1e12f64..1e12, Just(-0.0)].prop_map(JQLValue::Float),
+            (1000i32..9999, 1u32..=12, 1u32..=28).prop_map(|(year, month, day)| {
+                JQLValue::NaiveDate(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+            }),
+            (1000i32..9999, 1u32..=12, 1u32..=28, 0u32..24, 0u32..60).prop_map(
+                |(year, month, day, hour, minute)| {
+                    JQLValue::NaiveDateTime(
+                        NaiveDate::from_ymd_opt(year, month, day)
+                            .unwrap()
+                            .and_hms_opt(hour, minute, 0)
+                            .unwrap(),
+                    )
+                }
+            ),
+            duration().prop_map(JQLValue::Duration),
+            Just(JQLValue::Empty),
+            Just(JQLValue::Null),
+        ]
+    }
+
+    fn function() -> impl Strategy<Value = JQLFunction> {
+        prop_oneof![
+            Just(JQLFunction::CurrentUser),
+            Just(JQLFunction::Now),
+            Just(JQLFunction::OpenSprints),
+            Just(JQLFunction::IssueHistory),
+            any::<String>().prop_map(JQLFunction::MembersOf),
+            proptest::option::of(duration()).prop_map(JQLFunction::StartOfWeek),
+            proptest::option::of(duration()).prop_map(JQLFunction::EndOfYear),
+            (any::<String>(), proptest::option::of(any::<String>()))
+                .prop_map(|(key, link_type)| JQLFunction::LinkedIssues(key, link_type)),
+            proptest::option::of(any::<String>()).prop_map(JQLFunction::UnreleasedVersions),
+            any::<String>().prop_map(JQLFunction::LatestReleasedVersion),
+            (
+                any::<String>(),
+                proptest::option::of((scalar(), proptest::option::of(scalar())))
+            )
+                .prop_map(|(user, dates)| match dates {
+                    Some((from, to)) => {
+                        JQLFunction::UpdatedBy(user, Some(Box::new(from)), to.map(Box::new))
+                    }
+                    None => JQLFunction::UpdatedBy(user, None, None),
+                }),
+            (
+                "[a-zA-Z][a-zA-Z0-9]{0,8}".prop_filter("typed functions are not other", |name| {
+                    !FUNCTIONS.contains(&name.to_lowercase().as_str())
+                }),
+                prop::collection::vec(scalar(), 0..3)
+            )
+                .prop_map(|(name, arguments)| JQLFunction::Other(name, arguments)),
+        ]
+    }
+
+    fn value() -> impl Strategy<Value = JQLValue> {
+        prop_oneof![4 => scalar(), 1 => function().prop_map(JQLValue::Function)]
+    }
+
+    fn predicate() -> impl Strategy<Value = JQLHistoryPredicate> {
+        prop_oneof![
+            value().prop_map(JQLHistoryPredicate::After),
+            value().prop_map(JQLHistoryPredicate::Before),
+            value().prop_map(JQLHistoryPredicate::On),
+            (value(), value()).prop_map(|(start, end)| JQLHistoryPredicate::During(start, end)),
+            value().prop_map(JQLHistoryPredicate::By),
+            value().prop_map(JQLHistoryPredicate::From),
+            value().prop_map(JQLHistoryPredicate::To),
+        ]
+    }
+
+    fn comparison() -> impl Strategy<Value = JQLClause> {
+        let values = || prop::collection::vec(value(), 0..3);
+        let predicates = || prop::collection::vec(predicate(), 0..3);
+
+        prop_oneof![
+            (field(), value()).prop_map(|(f, v)| JQLClause::Equals(f, v)),
+            (field(), value()).prop_map(|(f, v)| JQLClause::NotEquals(f, v)),
+            (field(), value()).prop_map(|(f, v)| JQLClause::GreaterThan(f, v)),
+            (field(), value()).prop_map(|(f, v)| JQLClause::GreaterThanEquals(f, v)),
+            (field(), value()).prop_map(|(f, v)| JQLClause::LessThan(f, v)),
+            (field(), value()).prop_map(|(f, v)| JQLClause::LessThanEquals(f, v)),
+            (field(), value()).prop_map(|(f, v)| JQLClause::Contains(f, v)),
+            (field(), value()).prop_map(|(f, v)| JQLClause::NotContains(f, v)),
+            (field(), values()).prop_map(|(f, v)| JQLClause::In(f, v)),
+            (field(), values()).prop_map(|(f, v)| JQLClause::NotIn(f, v)),
+            (
+                field(),
+                prop_oneof![Just(JQLEmpty::Empty), Just(JQLEmpty::Null)]
+            )
+                .prop_map(|(f, e)| JQLClause::Is(f, e)),
+            (
+                field(),
+                prop_oneof![Just(JQLEmpty::Empty), Just(JQLEmpty::Null)]
+            )
+                .prop_map(|(f, e)| JQLClause::IsNot(f, e)),
+            (field(), value(), predicates()).prop_map(|(f, v, p)| JQLClause::Was(f, v, p)),
+            (field(), values(), predicates()).prop_map(|(f, v, p)| JQLClause::WasIn(f, v, p)),
+            (field(), value(), predicates()).prop_map(|(f, v, p)| JQLClause::WasNot(f, v, p)),
+            (field(), values(), predicates()).prop_map(|(f, v, p)| JQLClause::WasNotIn(f, v, p)),
+            (field(), predicates()).prop_map(|(f, p)| JQLClause::Changed(f, p)),
+        ]
+    }
+
+    fn clause() -> impl Strategy<Value = JQLClause> {
+        // Empty groups, and NOTs of them, are left out when serialized, so they read back as nothing.
+        comparison().prop_recursive(4, 32, 4, |inner| {
+            let clauses = prop::collection::vec(inner.clone().prop_map(Box::new), 0..4);
+
+            prop_oneof![
+                clauses.clone().prop_map(JQLClause::And),
+                clauses.prop_map(JQLClause::Or),
+                inner.prop_map(|clause| JQLClause::Not(Box::new(clause))),
+            ]
+        })
+    }
+
+    fn statement() -> impl Strategy<Value = JQLStatement> {
+        let order_by = prop::collection::vec(
+            (
+                field(),
+                proptest::option::of(prop_oneof![Just(JQLOrdering::Asc), Just(JQLOrdering::Desc)]),
+            )
+                .prop_map(|(field, ordering)| JQLOrderByPart { field, ordering }),
+            1..3,
+        )
+        .prop_map(JQLOrderBy);
+
+        (clause(), proptest::option::of(order_by))
+            .prop_map(|(clause, order_by)| JQLStatement { clause, order_by })
+    }
+
+    proptest! {
+        #[test]
+        fn parsing_serialized_statements_gives_the_same_jql(statement in statement()) {
+            let jql = statement.serialize_to_jql();
+            let parsed = JQLStatement::parse(&jql)
+                .map_err(|e| TestCaseError::fail(format!("{} in {}", e, jql)))?;
+
+            prop_assert_eq!(parsed.serialize_to_jql(), jql);
+        }
+
+        #[test]
+        fn parsing_is_stable_once_serialized(statement in statement()) {
+            let parsed = JQLStatement::parse(&statement.serialize_to_jql()).unwrap();
+            let reparsed = JQLStatement::parse(&parsed.serialize_to_jql()).unwrap();
+
+            prop_assert_eq!(reparsed, parsed);
+        }
+    }
+}
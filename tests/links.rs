@@ -0,0 +1,127 @@
+use jimberlage_jira_client::link::{self, RemoteLinkApplication, RemoteLinkRequest};
+use mockito::{Matcher, Server};
+
+mod common;
+
+use common::client;
+
+#[test]
+fn links_issues_in_the_direction_they_read() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/rest/api/3/issueLinkType")
+        .with_body(
+            r#"{"issueLinkTypes": [
+                {"id": "10000", "name": "Blocks", "inward": "is blocked by", "outward": "blocks"},
+                {"id": "10001", "name": "Cloners", "inward": "is cloned by", "outward": "clones"}
+            ]}"#,
+        )
+        .create();
+    // "SRE-2 is blocked by SRE-1" is sent with SRE-1 as the inward issue.
+    let create = server
+        .mock("POST", "/rest/api/3/issueLink")
+        .match_body(Matcher::JsonString(
+            r#"{"type": {"name": "Blocks"}, "inwardIssue": {"key": "SRE-1"}, "outwardIssue": {"key": "SRE-2"}}"#
+                .to_owned(),
+        ))
+        .with_status(201)
+        .with_header("Location", &format!("{}/rest/api/3/issueLink/10100", server.url()))
+        .create();
+    server
+        .mock("GET", "/rest/api/3/issueLink/10100")
+        .with_body(
+            r#"{
+                "id": "10100",
+                "type": {"id": "10000", "name": "Blocks", "inward": "is blocked by", "outward": "blocks"},
+                "inwardIssue": {"id": "10001", "key": "SRE-1", "fields": {"summary": "Rotate certificates"}},
+                "outwardIssue": {"id": "10002", "key": "SRE-2"}
+            }"#,
+        )
+        .create();
+    let delete = server
+        .mock("DELETE", "/rest/api/3/issueLink/10100")
+        .with_status(204)
+        .create();
+    let client = client(&server);
+
+    let link_types = client.get_issue_link_types().unwrap();
+    let blocks = link::find_by_name(&link_types, "blocks").unwrap();
+    let id = client
+        .create_issue_link(&blocks.inward_link("SRE-2", "SRE-1"))
+        .unwrap()
+        .unwrap();
+    let issue_link = client.get_issue_link(&id).unwrap();
+    client.delete_issue_link(&id).unwrap();
+
+    create.assert();
+    delete.assert();
+    assert_eq!(id, "10100");
+    assert_eq!(&issue_link.link_type, blocks);
+    assert_eq!(issue_link.inward_issue.unwrap().key, "SRE-1");
+    assert_eq!(issue_link.outward_issue.unwrap().key, "SRE-2");
+}
+
+#[test]
+fn upserts_and_deletes_remote_links() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/rest/api/3/issue/SRE-1/remotelink")
+        .with_body(
+            r#"[{
+                "id": 10000,
+                "globalId": "github=acme/api/pull/42",
+                "application": {"type": "com.github", "name": "GitHub"},
+                "relationship": "fixed by",
+                "object": {
+                    "url": "https://github.com/acme/api/pull/42",
+                    "title": "Retry rate limited requests",
+                    "status": {"resolved": true}
+                }
+            }]"#,
+        )
+        .create();
+    let upsert = server
+        .mock("POST", "/rest/api/3/issue/SRE-1/remotelink")
+        .match_body(Matcher::PartialJsonString(
+            r#"{"globalId": "github=acme/api/pull/42", "object": {"url": "https://github.com/acme/api/pull/42", "title": "Retry rate limited requests"}}"#
+                .to_owned(),
+        ))
+        .with_body(r#"{"id": 10000, "self": "https://example.atlassian.net/rest/api/3/issue/SRE-1/remotelink/10000"}"#)
+        .create();
+    let delete = server
+        .mock("DELETE", "/rest/api/3/issue/SRE-1/remotelink")
+        .match_query(Matcher::UrlEncoded(
+            "globalId".into(),
+            "github=acme/api/pull/42".into(),
+        ))
+        .with_status(204)
+        .create();
+    let client = client(&server);
+
+    let identifier = client
+        .upsert_remote_link(
+            "SRE-1",
+            &RemoteLinkRequest {
+                application: Some(RemoteLinkApplication::new("com.github", "GitHub")),
+                ..RemoteLinkRequest::new(
+                    "github=acme/api/pull/42",
+                    "https://github.com/acme/api/pull/42",
+                    "Retry rate limited requests",
+                )
+            },
+        )
+        .unwrap();
+    let remote_links = client.get_remote_links("SRE-1").unwrap();
+    client
+        .delete_remote_link_by_global_id("SRE-1", "github=acme/api/pull/42")
+        .unwrap();
+
+    upsert.assert();
+    delete.assert();
+    assert_eq!(identifier.id, "10000");
+    assert_eq!(remote_links[0].id, identifier.id);
+    assert_eq!(
+        remote_links[0].object.status.as_ref().map(|s| s.resolved),
+        Some(true)
+    );
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "macros")]
+
+use chrono::NaiveDate;
+use jimberlage_jira_client::jql::{
+    field, jql, JQLClause, JQLField, JQLFunction, JQLStatement, JQLValue, SerializableToJQL,
+};
+
+#[test]
+fn checks_jql_and_fills_in_placeholders() {
+    let project = "SRE";
+    let statuses = vec!["Done".to_owned(), "Won't Do".to_owned()];
+    let since = NaiveDate::from_ymd_opt(2023, 5, 10).unwrap();
+
+    let statement = jql!(
+        r#"project = {project} AND status NOT IN {statuses} AND summary ~ {} AND description ~ {0}
+           AND status WAS {} AFTER {since} ORDER BY created DESC"#,
+        "C:\\ [urgent]",
+        "In Progress",
+    );
+
+    assert_eq!(
+        statement.serialize_to_jql(),
+        "project = \"SRE\" AND status NOT IN (\"Done\", \"Won't Do\") AND summary ~ \"C\\\\:\\\\\\\\ \\\\[urgent\\\\]\" \
+         AND description ~ \"C\\\\:\\\\\\\\ \\\\[urgent\\\\]\" AND status WAS \"In Progress\" AFTER \"2023-05-10\" \
+         ORDER BY created DESC"
+    );
+    // Values are filled in as they are, rather than being read back out of JQL.
+    assert!(matches!(
+        statement.clause,
+        JQLClause::And(clauses)
+            if *clauses[2] == JQLClause::Contains("summary".into(), "C:\\ [urgent]".into())
+    ));
+}
+
+#[test]
+fn fills_in_values_inside_lists_and_reuses_arguments() {
+    let account_id = String::from("5b10a2844c20165700ede21g");
+    let assignee = &account_id;
+
+    let statement = jql!(
+        "assignee IN ({assignee}, {me}) OR reporter = {assignee} OR votes >= {votes}",
+        me = JQLFunction::CurrentUser,
+        votes = 4,
+    );
+
+    assert_eq!(
+        statement,
+        JQLStatement {
+            clause: JQLClause::Or(vec![
+                Box::new(JQLClause::In(
+                    JQLField::System("assignee".to_owned()),
+                    vec![account_id.clone().into(), JQLFunction::CurrentUser.into()],
+                )),
+                Box::new(JQLClause::Equals("reporter".into(), account_id.into())),
+                Box::new(JQLClause::GreaterThanEquals(
+                    "votes".into(),
+                    JQLValue::Int(4)
+                )),
+            ]),
+            order_by: None,
+        }
+    );
+    assert_eq!(
+        jql!("ORDER BY rank").serialize_to_jql(),
+        "ORDER BY rank".to_owned()
+    );
+}
+
+#[test]
+fn builds_the_statement_it_was_given_when_compiled() {
+    let cases = vec![
+        (
+            jql!("created >= -2w AND due < 2023-05-10 AND updated > \"2023/05/10 14:23\""),
+            "created >= -2w AND due < 2023-05-10 AND updated > \"2023/05/10 14:23\"",
+        ),
+        (
+            jql!("fixVersion IN (1.10, EMPTY) OR cf[10016] > 2.5 OR \"Story Points\" IS NOT NULL"),
+            "fixVersion IN (1.10, EMPTY) OR cf[10016] > 2.5 OR \"Story Points\" IS NOT NULL",
+        ),
+        (
+            jql!("NOT (issue IN linkedIssues(SRE-1, blocks) OR issue IN subtasksOf(\"status != Done\"))"),
+            "NOT (issue IN linkedIssues(SRE-1, blocks) OR issue IN subtasksOf(\"status != Done\"))",
+        ),
+        (
+            jql!("status CHANGED FROM Open TO Done DURING (startOfMonth(-1), now()) BY currentUser()"),
+            "status CHANGED FROM Open TO Done DURING (startOfMonth(-1), now()) BY currentUser()",
+        ),
+        (
+            jql!("issue IN updatedBy(jsmith, -1d) ORDER BY rank, created DESC"),
+            "issue IN updatedBy(jsmith, -1d) ORDER BY rank, created DESC",
+        ),
+    ];
+
+    for (statement, jql) in cases {
+        assert_eq!(statement, JQLStatement::parse(jql).unwrap(), "{}", jql);
+    }
+}
+
+#[test]
+fn fills_in_every_integer_type() {
+    let ids: Vec<u64> = vec![10001, 10002];
+    let votes: usize = 5;
+
+    assert_eq!(
+        jql!("id IN {ids} AND votes > {votes}").clause,
+        field("id").in_([10001, 10002]).and(field("votes").gt(5))
+    );
+}
+
+#[test]
+fn rejects_templates_which_cannot_be_filled_in() {
+    trybuild::TestCases::new().compile_fail("tests/macros/*.rs");
+}
@@ -0,0 +1,5 @@
+use jimberlage_jira_client::jql::jql;
+
+fn main() {
+    jql!("project = {}", "SRE", "PE");
+}
@@ -0,0 +1,143 @@
+use jimberlage_jira_client::adf::Document;
+use jimberlage_jira_client::comment::CommentRequest;
+use jimberlage_jira_client::RestClient;
+use mockito::{Matcher, Server};
+use serde_json::json;
+
+/// Markdown which survives being converted into ADF and back unchanged.
+const ROUND_TRIPS: &[&str] = &[
+    "# Incident *report*\n",
+    "Some **bold *and em*** text with `code`, a [link](https://example.com \"Runbook\") and ~~strike~~.\n",
+    "First line\\\nsecond line, snake_case and \\*stars\\*.\n",
+    "\\# not a heading, \\[not a link\\] and 1986. not a list.\n",
+    "1986\\. A great year\n",
+    "\\- not a list, but #hashtag and 1.2.0 are fine\n",
+    "> quote\n>\n> - with a list\n",
+    "- tight\n- list\n  - nested\n  - more\n",
+    "3. three\n4. four\n",
+    "- [ ] todo\n- [x] done\n  - [ ] nested\n",
+    "```rust\nfn main() {}\n```\n",
+    "````\na ``` b\n````\n",
+    "| Service | Version |\n| --- | --- |\n| `api\\|web` | **1.2.0** |\n| db |  |\n",
+    "[`docs`](<https://example.com/a (b)>)\n",
+    "---\n",
+];
+
+#[test]
+fn round_trips_markdown() {
+    for markdown in ROUND_TRIPS {
+        let doc = Document::from_markdown(markdown);
+
+        assert!(doc.validate().is_ok(), "{} is invalid ADF", markdown);
+        assert_eq!(&doc.to_markdown(), markdown);
+    }
+}
+
+#[test]
+fn converts_tables_and_task_lists() {
+    let doc = Document::from_markdown("| A | B |\n| :-- | --: |\n| 1 |\n\n- [x] shipped\n");
+
+    assert_eq!(
+        serde_json::to_value(&doc).unwrap(),
+        json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                {"type": "table", "content": [
+                    {"type": "tableRow", "content": [
+                        {"type": "tableHeader", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "A"}]}]},
+                        {"type": "tableHeader", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "B"}]}]}
+                    ]},
+                    {"type": "tableRow", "content": [
+                        {"type": "tableCell", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "1"}]}]},
+                        {"type": "tableCell", "content": [{"type": "paragraph"}]}
+                    ]}
+                ]},
+                {"type": "taskList", "attrs": {"localId": "2"}, "content": [
+                    {"type": "taskItem", "attrs": {"localId": "1", "state": "DONE"}, "content": [{"type": "text", "text": "shipped"}]}
+                ]}
+            ]
+        })
+    );
+}
+
+#[test]
+fn degrades_markdown_adf_cannot_hold() {
+    let doc = Document::from_markdown(
+        "![diagram](https://example.com/d.png)\n\n- ## heading in a list\n\n- [x] checked\n- unchecked\n\n<div>html</div>\n",
+    );
+
+    assert!(doc.validate().is_ok());
+    assert_eq!(
+        doc.to_markdown(),
+        "[diagram](https://example.com/d.png)\n\n- heading in a list\n- \\[x\\] checked\n- unchecked\n\n\\<div>html\\</div>\n"
+    );
+}
+
+#[test]
+fn degrades_adf_markdown_cannot_hold() {
+    let doc: Document = serde_json::from_value(json!({
+        "type": "doc",
+        "version": 1,
+        "content": [
+            {"type": "paragraph", "content": [
+                {"type": "mention", "attrs": {"id": "5b10a2844c20165700ede21g", "text": "@Jane Smith"}},
+                {"type": "text", "text": " fixed "},
+                {"type": "inlineCard", "attrs": {"url": "https://jira.example.com/browse/SRE-1"}},
+                {"type": "text", "text": " for real", "marks": [{"type": "underline"}, {"type": "textColor", "attrs": {"color": "#ff5630"}}]},
+                {"type": "emoji", "attrs": {"shortName": ":tada:"}}
+            ]},
+            {"type": "panel", "attrs": {"panelType": "warning"}, "content": [
+                {"type": "paragraph", "content": [{"type": "text", "text": "Watch the error rate."}]}
+            ]},
+            {"type": "mediaSingle", "content": [{"type": "media", "attrs": {"id": "abc", "type": "file", "collection": ""}}]},
+            {"type": "table", "content": [{"type": "tableRow", "content": [
+                {"type": "tableCell", "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "one"}]},
+                    {"type": "paragraph", "content": [{"type": "text", "text": "two"}]}
+                ]}
+            ]}]}
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+        doc.to_markdown(),
+        "@Jane Smith fixed <https://jira.example.com/browse/SRE-1> for real:tada:\n\n> Watch the error rate.\n\n| one<br>two |\n| --- |\n"
+    );
+}
+
+#[test]
+fn sends_markdown_as_a_comment() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("POST", "/rest/api/3/issue/SRE-1/comment")
+        .match_body(Matcher::PartialJson(json!({
+            "body": {"type": "doc", "version": 1, "content": [
+                {"type": "paragraph", "content": [
+                    {"type": "text", "text": "Deployed "},
+                    {"type": "text", "text": "v1.2.0", "marks": [{"type": "code"}]}
+                ]}
+            ]}
+        })))
+        .with_status(201)
+        .with_body(
+            r#"{
+                "id": "10100",
+                "body": {"type": "doc", "version": 1, "content": []},
+                "created": "2023-05-10T14:23:01.123+0000",
+                "updated": "2023-05-10T14:23:01.123+0000"
+            }"#,
+        )
+        .create();
+
+    RestClient::new(&server.url(), "me@example.com", "token")
+        .unwrap()
+        .add_comment(
+            "SRE-1",
+            &CommentRequest::new(Document::from_markdown("Deployed `v1.2.0`").into()),
+        )
+        .unwrap();
+
+    mock.assert();
+}
@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use jimberlage_jira_client::{Error, IssueCreateRequest, RestClient, RetryPolicy};
+use mockito::Server;
+
+fn fast_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        jitter: false,
+        retry_non_idempotent: false,
+    }
+}
+
+fn client(server: &Server) -> RestClient {
+    RestClient::new(&server.url(), "user@example.com", "token")
+        .unwrap()
+        .with_retry_policy(fast_retry_policy())
+}
+
+#[test]
+fn retries_rate_limited_requests_after_retry_after() {
+    let mut server = Server::new();
+    let rate_limited = server
+        .mock("GET", "/rest/api/3/field")
+        .with_status(429)
+        .with_header("Retry-After", "0")
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/rest/api/3/field")
+        .with_status(200)
+        .with_body(r#"[{"id": "summary", "name": "Summary"}]"#)
+        .expect(1)
+        .create();
+
+    let fields = client(&server).get_fields().unwrap();
+
+    assert_eq!(fields.len(), 1);
+    rate_limited.assert();
+    ok.assert();
+}
+
+#[test]
+fn caps_retry_after_at_max_backoff() {
+    let mut server = Server::new();
+    let rate_limited = server
+        .mock("GET", "/rest/api/3/field")
+        .with_status(429)
+        .with_header("Retry-After", "86400")
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/rest/api/3/field")
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create();
+
+    let fields = client(&server).get_fields().unwrap();
+
+    assert!(fields.is_empty());
+    rate_limited.assert();
+    ok.assert();
+}
+
+#[test]
+fn retries_rate_limited_requests_which_are_not_idempotent() {
+    let mut server = Server::new();
+    let rate_limited = server
+        .mock("POST", "/rest/api/3/issue")
+        .with_status(429)
+        .with_header("Retry-After", "0")
+        .expect(1)
+        .create();
+    let created = server
+        .mock("POST", "/rest/api/3/issue")
+        .with_status(201)
+        .with_body(r#"{"id": "10002", "key": "SRE-2", "self": "https://jira.example.com/rest/api/3/issue/10002"}"#)
+        .expect(1)
+        .create();
+
+    let issue = client(&server)
+        .create_issue(&IssueCreateRequest::new(
+            "SRE",
+            "Task",
+            "Rotate certificates",
+        ))
+        .unwrap();
+
+    assert_eq!(issue.key, "SRE-2");
+    rate_limited.assert();
+    created.assert();
+}
+
+#[test]
+fn does_not_retry_unavailable_requests_which_are_not_idempotent() {
+    let mut server = Server::new();
+    let unavailable = server
+        .mock("POST", "/rest/api/3/issue")
+        .with_status(503)
+        .expect(1)
+        .create();
+
+    let result = client(&server).create_issue(&IssueCreateRequest::new(
+        "SRE",
+        "Task",
+        "Rotate certificates",
+    ));
+
+    assert!(matches!(result, Err(Error::Status { status, .. }) if status.as_u16() == 503));
+    unavailable.assert();
+}
+
+#[test]
+fn gives_up_after_max_attempts() {
+    let mut server = Server::new();
+    let unavailable = server
+        .mock("GET", "/rest/api/3/field")
+        .with_status(503)
+        .expect(3)
+        .create();
+
+    let result = client(&server).get_fields();
+
+    assert!(matches!(
+        result,
+        Err(Error::Status { status, .. }) if status.as_u16() == 503
+    ));
+    unavailable.assert();
+}
+
+#[test]
+fn does_not_retry_client_errors() {
+    let mut server = Server::new();
+    let bad_request = server
+        .mock("GET", "/rest/api/3/user/picker")
+        .match_query(mockito::Matcher::Any)
+        .with_status(400)
+        .with_body(r#"{"errorMessages": ["The query parameter is required."], "errors": {}}"#)
+        .expect(1)
+        .create();
+
+    let result = client(&server).search_users("");
+
+    assert!(matches!(result, Err(Error::Validation(_))));
+    bad_request.assert();
+}
+
+#[test]
+fn does_not_retry_when_disabled() {
+    let mut server = Server::new();
+    let rate_limited = server
+        .mock("GET", "/rest/api/3/field")
+        .with_status(429)
+        .with_header("Retry-After", "0")
+        .expect(1)
+        .create();
+
+    let result = client(&server)
+        .with_retry_policy(RetryPolicy::none())
+        .get_fields();
+
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited {
+            retry_after: Some(retry_after)
+        }) if retry_after == Duration::ZERO
+    ));
+    rate_limited.assert();
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn async_client_retries_rate_limited_requests() {
+    use jimberlage_jira_client::AsyncRestClient;
+
+    let mut server = Server::new_async().await;
+    let rate_limited = server
+        .mock("GET", "/rest/api/3/field")
+        .with_status(429)
+        .expect(1)
+        .create_async()
+        .await;
+    let ok = server
+        .mock("GET", "/rest/api/3/field")
+        .with_status(200)
+        .with_body("[]")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let fields = AsyncRestClient::new(&server.url(), "user@example.com", "token")
+        .unwrap()
+        .with_retry_policy(fast_retry_policy())
+        .get_fields()
+        .await
+        .unwrap();
+
+    assert!(fields.is_empty());
+    rate_limited.assert_async().await;
+    ok.assert_async().await;
+}
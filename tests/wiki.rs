@@ -0,0 +1,111 @@
+use jimberlage_jira_client::adf::Document;
+use jimberlage_jira_client::{ApiVersion, TextBody};
+use serde_json::{json, Value as JSONValue};
+use std::fs;
+use std::path::Path;
+
+/// Each `name.wiki` in the corpus is parsed and compared to `name.json`, then rendered back and compared to
+/// `name.out.wiki`, or to `name.wiki` itself when there is no `.out.wiki` file.
+#[test]
+fn converts_the_wiki_corpus() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/wiki_corpus");
+    let mut cases = 0;
+
+    for entry in fs::read_dir(&corpus).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        let Some(stem) = name.strip_suffix(".wiki") else {
+            continue;
+        };
+        if stem.ends_with(".out") {
+            continue;
+        }
+
+        let markup = fs::read_to_string(&path).unwrap();
+        let expected: JSONValue = serde_json::from_str(
+            &fs::read_to_string(corpus.join(format!("{}.json", stem))).unwrap(),
+        )
+        .unwrap();
+        let output = fs::read_to_string(corpus.join(format!("{}.out.wiki", stem)))
+            .unwrap_or_else(|_| markup.clone());
+
+        let doc = Document::from_wiki_markup(&markup);
+
+        assert!(doc.validate().is_ok(), "{} is invalid ADF", name);
+        assert_eq!(serde_json::to_value(&doc).unwrap(), expected, "{}", name);
+        assert_eq!(doc.to_wiki_markup(), output.trim_end(), "{}", name);
+        assert_eq!(
+            Document::from_wiki_markup(&output),
+            doc,
+            "{} is not stable",
+            name
+        );
+        cases += 1;
+    }
+
+    assert!(cases > 0);
+}
+
+#[test]
+fn escapes_text_which_looks_like_markup() {
+    let doc: Document = serde_json::from_value(json!({
+        "type": "doc",
+        "version": 1,
+        "content": [
+            {"type": "paragraph", "content": [
+                {"type": "text", "text": "un"},
+                {"type": "text", "text": "bold", "marks": [{"type": "strong"}]},
+                {"type": "text", "text": "ed, *stars*, [brackets], {braces} and a-b -c :)"}
+            ]},
+            {"type": "paragraph", "content": [
+                {"type": "text", "text": "* not a list"},
+                {"type": "hardBreak"},
+                {"type": "text", "text": "h1. not a heading"}
+            ]},
+            {"type": "taskList", "attrs": {"localId": "1"}, "content": [
+                {"type": "taskItem", "attrs": {"localId": "2", "state": "DONE"}, "content": [{"type": "text", "text": "shipped"}]}
+            ]},
+            {"type": "table", "content": [{"type": "tableRow", "content": [
+                {"type": "tableCell", "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "a|b"}]},
+                    {"type": "paragraph", "content": [{"type": "text", "text": "two"}]}
+                ]},
+                {"type": "tableCell", "content": [{"type": "paragraph"}]}
+            ]}]}
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+        doc.to_wiki_markup(),
+        "un{*}bold{*}ed, \\*stars\\*, \\[brackets\\], \\{braces\\} and a-b \\-c \\:)\n\n\\* not a list\nh1\\. not a heading\n\n* \\[x\\] shipped\n\n|a\\|b\\\\two| |"
+    );
+}
+
+#[test]
+fn leaves_out_tables_without_cells() {
+    let doc = Document::from_wiki_markup("||\n|\n\nafter");
+
+    assert!(doc.validate().is_ok());
+    assert_eq!(doc.to_wiki_markup(), "after");
+}
+
+#[test]
+fn converts_text_bodies_between_api_versions() {
+    let wiki = TextBody::WikiMarkup("See [the runbook|https://example.com/runbook]".to_owned());
+    let adf = wiki.clone().into_api_version(ApiVersion::V3);
+
+    assert_eq!(
+        adf,
+        TextBody::Adf(json!({
+            "type": "doc",
+            "version": 1,
+            "content": [{"type": "paragraph", "content": [
+                {"type": "text", "text": "See "},
+                {"type": "text", "text": "the runbook", "marks": [{"type": "link", "attrs": {"href": "https://example.com/runbook"}}]}
+            ]}]
+        }))
+    );
+    assert_eq!(adf.into_api_version(ApiVersion::V2), wiki);
+    assert_eq!(wiki.clone().into_api_version(ApiVersion::V2), wiki);
+}
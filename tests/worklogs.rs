@@ -0,0 +1,270 @@
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use jimberlage_jira_client::time_tracking::{TimeTrackingSettings, TimeUnit};
+use jimberlage_jira_client::worklog::{AdjustEstimate, WorklogOptions, WorklogRequest};
+use jimberlage_jira_client::{Error, TextBody};
+use mockito::{Matcher, Server};
+
+mod common;
+
+use common::client;
+
+fn worklog(id: &str) -> String {
+    format!(
+        r#"{{
+            "id": "{}",
+            "issueId": "10001",
+            "author": {{"accountId": "5b10a2844c20165700ede21g", "displayName": "Jane Smith"}},
+            "comment": "Incident review",
+            "started": "2023-05-10T14:00:00.000+0200",
+            "timeSpent": "1h 30m",
+            "timeSpentSeconds": 5400,
+            "created": "2023-05-10T16:00:00.000+0200",
+            "updated": "2023-05-10T16:00:00.000+0200"
+        }}"#,
+        id
+    )
+}
+
+fn started() -> DateTime<FixedOffset> {
+    DateTime::parse_from_rfc3339("2023-05-10T14:00:00+02:00").unwrap()
+}
+
+#[test]
+fn pages_through_worklogs() {
+    let mut server = Server::new();
+    let mock = server
+        .mock("GET", "/rest/api/3/issue/SRE-1/worklog")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("expand".into(), "properties".into()),
+            Matcher::UrlEncoded("startAt".into(), "0".into()),
+        ]))
+        .with_body(format!(
+            r#"{{"startAt": 0, "maxResults": 100, "total": 1, "worklogs": [{}]}}"#,
+            worklog("100")
+        ))
+        .create();
+
+    let worklogs = client(&server).get_worklogs("SRE-1").unwrap();
+
+    mock.assert();
+    assert_eq!(worklogs.len(), 1);
+    assert_eq!(worklogs[0].time_spent_seconds, 5400);
+    assert_eq!(
+        worklogs[0].comment,
+        Some(TextBody::WikiMarkup("Incident review".to_owned()))
+    );
+    assert_eq!(worklogs[0].started, started());
+}
+
+#[test]
+fn adds_updates_and_deletes_worklogs() {
+    let mut server = Server::new();
+    let add = server
+        .mock("POST", "/rest/api/3/issue/SRE-1/worklog")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("adjustEstimate".into(), "manual".into()),
+            Matcher::UrlEncoded("reduceBy".into(), "120m".into()),
+        ]))
+        .match_body(Matcher::JsonString(
+            r#"{"started": "2023-05-10T14:00:00.000+0200", "timeSpentSeconds": 5400}"#.to_owned(),
+        ))
+        .with_status(201)
+        .with_body(worklog("100"))
+        .create();
+    let update = server
+        .mock("PUT", "/rest/api/3/issue/SRE-1/worklog/100")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("adjustEstimate".into(), "new".into()),
+            Matcher::UrlEncoded("newEstimate".into(), "480m".into()),
+            Matcher::UrlEncoded("notifyUsers".into(), "false".into()),
+        ]))
+        .match_body(Matcher::JsonString(
+            r#"{"comment": "Incident review", "started": "2023-05-10T14:00:00.000+0200", "timeSpentSeconds": 7200}"#
+                .to_owned(),
+        ))
+        .with_body(worklog("100"))
+        .create();
+    let delete = server
+        .mock("DELETE", "/rest/api/3/issue/SRE-1/worklog/100")
+        .match_query(Matcher::UrlEncoded("adjustEstimate".into(), "leave".into()))
+        .with_status(204)
+        .create();
+    let client = client(&server);
+
+    let worklog = client
+        .add_worklog(
+            "SRE-1",
+            &WorklogRequest::new(started(), Duration::from_secs(5400)),
+            &WorklogOptions {
+                adjust_estimate: AdjustEstimate::Manual(Duration::from_secs(2 * 60 * 60)),
+                ..WorklogOptions::default()
+            },
+        )
+        .unwrap();
+    client
+        .update_worklog(
+            "SRE-1",
+            &worklog.id,
+            &WorklogRequest {
+                comment: Some(TextBody::WikiMarkup("Incident review".to_owned())),
+                ..WorklogRequest::new(started(), Duration::from_secs(7200))
+            },
+            &WorklogOptions {
+                adjust_estimate: AdjustEstimate::New(Duration::from_secs(8 * 60 * 60)),
+                notify_users: Some(false),
+            },
+        )
+        .unwrap();
+    client
+        .delete_worklog(
+            "SRE-1",
+            &worklog.id,
+            &WorklogOptions {
+                adjust_estimate: AdjustEstimate::Leave,
+                ..WorklogOptions::default()
+            },
+        )
+        .unwrap();
+
+    add.assert();
+    update.assert();
+    delete.assert();
+}
+
+#[test]
+fn rejects_manual_estimate_adjustments_on_update() {
+    let mut server = Server::new();
+    let update = server
+        .mock("PUT", "/rest/api/3/issue/SRE-1/worklog/10100")
+        .match_query(Matcher::Any)
+        .expect(0)
+        .create();
+
+    let result = client(&server).update_worklog(
+        "SRE-1",
+        "10100",
+        &WorklogRequest::new(started(), Duration::from_secs(5400)),
+        &WorklogOptions {
+            adjust_estimate: AdjustEstimate::Manual(Duration::from_secs(60 * 60)),
+            ..WorklogOptions::default()
+        },
+    );
+
+    assert!(
+        matches!(result, Err(Error::Validation(errors)) if errors.errors.contains_key("adjustEstimate"))
+    );
+    update.assert();
+}
+
+#[test]
+fn rounds_estimates_up_to_whole_minutes() {
+    let mut server = Server::new();
+    let add = server
+        .mock("POST", "/rest/api/3/issue/SRE-1/worklog")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("adjustEstimate".into(), "new".into()),
+            Matcher::UrlEncoded("newEstimate".into(), "1m".into()),
+        ]))
+        .with_status(201)
+        .with_body(worklog("10100"))
+        .create();
+
+    client(&server)
+        .add_worklog(
+            "SRE-1",
+            &WorklogRequest::new(started(), Duration::from_secs(5400)),
+            &WorklogOptions {
+                adjust_estimate: AdjustEstimate::New(Duration::from_secs(30)),
+                ..WorklogOptions::default()
+            },
+        )
+        .unwrap();
+
+    add.assert();
+}
+
+#[test]
+fn gets_worklogs_updated_since() {
+    let mut server = Server::new();
+    let first_page = server
+        .mock("GET", "/rest/api/3/worklog/updated")
+        .match_query(Matcher::UrlEncoded("since".into(), "1683720000000".into()))
+        .with_body(
+            r#"{"values": [{"worklogId": 100, "updatedTime": 1683727200000, "properties": []}],
+                "since": 1683720000000, "until": 1683727200000, "lastPage": false}"#,
+        )
+        .create();
+    let second_page = server
+        .mock("GET", "/rest/api/3/worklog/updated")
+        .match_query(Matcher::UrlEncoded("since".into(), "1683727200000".into()))
+        .with_body(
+            r#"{"values": [{"worklogId": 101, "updatedTime": 1683730800000, "properties": []}],
+                "since": 1683727200000, "until": 1683730800000, "lastPage": true}"#,
+        )
+        .create();
+    let list = server
+        .mock("POST", "/rest/api/3/worklog/list")
+        .match_query(Matcher::UrlEncoded("expand".into(), "properties".into()))
+        .match_body(Matcher::JsonString(r#"{"ids": [100, 101]}"#.to_owned()))
+        .with_body(format!("[{}, {}]", worklog("100"), worklog("101")))
+        .create();
+
+    let worklogs = client(&server)
+        .get_worklogs_updated_since(&Utc.with_ymd_and_hms(2023, 5, 10, 12, 0, 0).unwrap())
+        .unwrap();
+
+    first_page.assert();
+    second_page.assert();
+    list.assert();
+    assert_eq!(
+        worklogs
+            .iter()
+            .map(|w| w.id.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["100", "101"]
+    );
+}
+
+#[test]
+fn parses_durations_with_the_instance_settings() {
+    let mut server = Server::new();
+    server
+        .mock("GET", "/rest/api/3/configuration/timetracking/options")
+        .with_body(
+            r#"{"workingHoursPerDay": 6.0, "workingDaysPerWeek": 4.0, "timeFormat": "pretty", "defaultUnit": "hour"}"#,
+        )
+        .create();
+
+    let settings = client(&server).get_time_tracking_settings().unwrap();
+
+    assert_eq!(
+        settings,
+        TimeTrackingSettings {
+            working_hours_per_day: 6.0,
+            working_days_per_week: 4.0,
+            default_unit: TimeUnit::Hour,
+        }
+    );
+    let hours = |hours: u64| Duration::from_secs(hours * 60 * 60);
+    assert_eq!(settings.parse_duration("1w 2d 3h"), Ok(hours(24 + 12 + 3)));
+    assert_eq!(
+        settings.parse_duration("1W2D30m"),
+        Ok(hours(36) + Duration::from_secs(30 * 60))
+    );
+    assert_eq!(settings.parse_duration(" 1.5d "), Ok(hours(9)));
+    assert_eq!(settings.parse_duration("2"), Ok(hours(2)));
+    assert_eq!(settings.format_duration(hours(24 + 12 + 3)), "1w 2d 3h");
+    assert_eq!(settings.format_duration(Duration::from_secs(59)), "0m");
+
+    for invalid in ["", "1x", "1h 30", "h", "-1h", "1..5h", "1 h"] {
+        assert_eq!(
+            settings.parse_duration(invalid).unwrap_err().to_string(),
+            format!(
+                "invalid duration {:?}, expected something like \"1w 2d 3h 30m\"",
+                invalid
+            )
+        );
+    }
+}